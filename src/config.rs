@@ -2,28 +2,45 @@
 // Original code by: CryZe
 // Original repository: github.com/CryZe/livesplit-one-desktop
 // Commit: c636ba8
+use crate::discard_reset::DiscardResetKey;
 use crate::formatters::{TimeFormat, TimeFormatPreset};
+use crate::utils::comparisons::ensure_generated_comparisons;
 
 use livesplit_core::{
-    HotkeyConfig, HotkeySystem, Run, Segment, SharedTimer, Timer, TimingMethod, auto_splitting,
-    run::{parser::composite, saver::livesplit::save_timer},
+    HotkeyConfig, HotkeySystem, Run, Segment, SharedTimer, TimeSpan, Timer, TimingMethod,
+    auto_splitting,
+    hotkey::Hotkey,
+    run::{
+        parser::{TimerKind, composite},
+        saver::livesplit::save_timer,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::{BTreeMap, BTreeSet},
     fs,
+    net::SocketAddr,
     path::{Path, PathBuf},
 };
-use tracing::error;
+use tracing::{error, warn};
 
 pub type SharedConfig = std::sync::Arc<std::sync::RwLock<Config>>;
 
+/// Custom variable marking the placeholder run used while no splits are loaded.
+const DEFAULT_RUN_MARKER: &str = "TuxSplit Default Run";
+
+/// Whether `run` is the untouched placeholder run of `Config::parse_run_or_default`.
+pub fn is_default_run(run: &Run) -> bool {
+    run.attempt_count() == 0 && run.metadata().custom_variable(DEFAULT_RUN_MARKER).is_some()
+}
+
 #[derive(Default, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Config {
     #[serde(default)]
     pub general: General,
     #[serde(default)]
-    window: Window,
+    pub window: Window,
     #[serde(default)]
     pub style: Style,
     #[serde(default)]
@@ -34,6 +51,9 @@ pub struct Config {
     connections: Connections,
     #[serde(skip)]
     hotkey_system: Option<HotkeySystem>,
+    /// The discarding reset key, registered next to the hotkey system.
+    #[serde(skip)]
+    discard_reset_key: Option<DiscardResetKey>,
 }
 
 #[allow(clippy::missing_fields_in_debug)]
@@ -59,18 +79,285 @@ impl Clone for Config {
             format: self.format.clone(),
             connections: self.connections.clone(),
             hotkey_system: None,
+            discard_reset_key: None,
         }
     }
 }
 
-#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct General {
     pub splits: Option<PathBuf>,
     pub timing_method: Option<TimingMethod>,
     pub comparison: Option<String>,
     pub auto_splitter: Option<PathBuf>,
+    /// Whether the `auto_splitter` script is loaded, so it can be switched off without
+    /// forgetting it.
+    #[serde(default = "default_auto_splitter_enabled")]
+    pub auto_splitter_enabled: bool,
     pub additional_info: AdditionalInfoVisibility,
+    #[serde(default)]
+    pub click_to_split: bool,
+    #[serde(default)]
+    pub idle_display: IdleDisplay,
+    /// Possible time save sums every remaining segment instead of just the current one.
+    #[serde(default)]
+    pub possible_time_save_total: bool,
+    #[serde(default)]
+    pub load_while_running: LoadWhileRunning,
+    /// Show a prompt to load splits instead of timing the placeholder run.
+    #[serde(default)]
+    pub default_run_prompt: bool,
+    /// Warn when loaded splits have split/segment times that don't add up. The timer
+    /// repairs those either way, the setting only decides whether that gets logged.
+    #[serde(default = "default_fix_segment_sums")]
+    pub fix_segment_sums: bool,
+    /// Show real time where a split has no game time while in game time mode.
+    #[serde(default)]
+    pub game_time_fallback: bool,
+    /// Attempts per segment kept by the editor's history trim.
+    #[serde(default = "default_history_keep_attempts")]
+    pub history_keep_attempts: usize,
+    #[serde(default)]
+    pub best_possible_when_ended: BestPossibleWhenEnded,
+    #[serde(default)]
+    pub editor_commit: EditorCommit,
+    /// Comparison the "Previous Segment (Best)" info measures against, "Best Segments"
+    /// when unset.
+    #[serde(default)]
+    pub prev_segment_best_comparison: Option<String>,
+    /// Milliseconds the reset keys have to be held before resetting, instant when unset.
+    #[serde(default)]
+    pub hold_to_reset_ms: Option<u64>,
+    /// Global key resetting without saving the attempt's golds or PB.
+    #[serde(default)]
+    pub discard_reset_hotkey: Option<Hotkey>,
+    /// Pause a running attempt while the window is unfocused.
+    #[serde(default)]
+    pub pause_on_unfocus: bool,
+    /// Pause a running attempt when the machine was suspended in the middle of it.
+    #[serde(default)]
+    pub pause_on_suspend: bool,
+    /// Starting the timer primes it instead: paused at the offset until the next input.
+    #[serde(default)]
+    pub start_paused: bool,
+    #[serde(default)]
+    pub config_format: ConfigFormat,
+    /// Folder the file choosers open in, the one a file was last picked from.
+    #[serde(default)]
+    pub last_directory: Option<PathBuf>,
+    /// Final time the Current Pace info is colored against.
+    #[serde(default)]
+    pub pace_target: PaceTarget,
+    /// What passed splits show: the delta, the split time or both.
+    #[serde(default)]
+    pub split_format: SplitDisplay,
+    /// Target final time of the runner, written like "1:23:45.000".
+    #[serde(default, with = "goal_time_format")]
+    pub goal_time: Option<TimeSpan>,
+    /// Post a chat message to the Twitch channel of `connections` on a new Personal Best.
+    #[serde(default)]
+    pub announce_pb: bool,
+    /// Also post a chat message when a running attempt is reset.
+    #[serde(default)]
+    pub announce_reset: bool,
+    /// GDK backend the window is opened with, read once at startup.
+    #[serde(default)]
+    pub display_backend: DisplayBackend,
+    /// Collapsed subsplit groups, by splits file.
+    #[serde(default)]
+    pub collapsed_groups: BTreeMap<String, BTreeSet<String>>,
+}
+
+const fn default_fix_segment_sums() -> bool {
+    true
+}
+
+const fn default_auto_splitter_enabled() -> bool {
+    true
+}
+
+const fn default_history_keep_attempts() -> usize {
+    100
+}
+
+impl Default for General {
+    fn default() -> Self {
+        Self {
+            splits: None,
+            timing_method: None,
+            comparison: None,
+            auto_splitter: None,
+            auto_splitter_enabled: default_auto_splitter_enabled(),
+            additional_info: AdditionalInfoVisibility::default(),
+            click_to_split: false,
+            idle_display: IdleDisplay::default(),
+            possible_time_save_total: false,
+            load_while_running: LoadWhileRunning::default(),
+            default_run_prompt: false,
+            fix_segment_sums: default_fix_segment_sums(),
+            game_time_fallback: false,
+            history_keep_attempts: default_history_keep_attempts(),
+            best_possible_when_ended: BestPossibleWhenEnded::default(),
+            editor_commit: EditorCommit::default(),
+            prev_segment_best_comparison: None,
+            hold_to_reset_ms: None,
+            discard_reset_hotkey: None,
+            pause_on_unfocus: false,
+            pause_on_suspend: false,
+            start_paused: false,
+            config_format: ConfigFormat::default(),
+            last_directory: None,
+            pace_target: PaceTarget::default(),
+            split_format: SplitDisplay::default(),
+            goal_time: None,
+            announce_pb: false,
+            announce_reset: false,
+            display_backend: DisplayBackend::default(),
+            collapsed_groups: BTreeMap::new(),
+        }
+    }
+}
+
+/// What loading a splits file does while an attempt is in progress.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadWhileRunning {
+    /// Ask whether to discard the attempt and load the new splits.
+    #[default]
+    Confirm,
+    /// Reset the attempt (without saving it) and load the new splits.
+    Reset,
+    /// Keep the attempt running and ignore the load.
+    Refuse,
+}
+
+/// How the config file is written when saving.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfigFormat {
+    /// Every option, unset ones included, below a short header comment.
+    #[default]
+    Pretty,
+    /// Leaves out options that are unset, as they are by default.
+    Compact,
+}
+
+/// Which GDK backend TuxSplit runs on.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DisplayBackend {
+    /// X11 (through XWayland) in a Wayland session with global hotkeys bound, since
+    /// they only work there, native otherwise.
+    #[default]
+    Auto,
+    /// Always X11.
+    X11,
+    /// Whatever GDK picks, global hotkeys don't work in a Wayland session.
+    Native,
+}
+
+const CONFIG_HEADER: &str = "# TuxSplit configuration, rewritten on exit.\n\
+# Options are documented in data/config/config.yaml of the TuxSplit sources.\n";
+
+/// What the "Best Possible Time" info shows once the attempt has ended.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BestPossibleWhenEnded {
+    /// The final time of the attempt.
+    #[default]
+    FinalTime,
+    /// The last best possible time computed while the attempt was running.
+    Frozen,
+}
+
+/// What the projected final time (Current Pace) is colored against.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PaceTarget {
+    /// The final time of the Personal Best.
+    #[default]
+    PersonalBest,
+    /// `general.goal_time`.
+    GoalTime,
+    /// The final time of the imported World Record comparison.
+    WorldRecord,
+}
+
+/// What the rows of passed splits show.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SplitDisplay {
+    /// Only the delta against the comparison.
+    Delta,
+    /// Only the split time of the attempt.
+    AbsoluteTime,
+    /// The split time of the attempt and the delta.
+    #[default]
+    Both,
+}
+
+/// A goal time as written in the config file and the settings, e.g. "1:02:03.500".
+pub fn goal_time_text(goal: TimeSpan) -> String {
+    let ms = goal.total_milliseconds().max(0.0) as u64;
+    format!(
+        "{}:{:02}:{:02}.{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+/// Goal times in the config file are text, anything `parse_hms` understands.
+mod goal_time_format {
+    use crate::formatters::time::parse_hms;
+    use livesplit_core::TimeSpan;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        goal: &Option<TimeSpan>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match goal {
+            Some(goal) => serializer.serialize_some(&super::goal_time_text(*goal)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<TimeSpan>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|text| {
+                parse_hms(text.trim())
+                    .map(|goal| TimeSpan::from_seconds(goal.as_seconds_f64()))
+                    .map_err(D::Error::custom)
+            })
+            .transpose()
+    }
+}
+
+/// When the split editor applies a typed time.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EditorCommit {
+    /// When the field loses focus.
+    #[default]
+    OnUnfocus,
+    /// Shortly after typing stops, as long as the value is valid (and on unfocus).
+    OnChange,
+}
+
+/// What the footer compares against while no attempt is running and no segment is selected.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdleDisplay {
+    /// Best and comparison times of the first segment.
+    #[default]
+    FirstSegment,
+    /// Sum of best and final comparison time of the whole run.
+    WholeRun,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -79,6 +366,93 @@ pub struct Style {
     pub max_segments_displayed: Option<usize>,
     pub segments_scroll_follow_from: Option<usize>,
     pub show_icons: Option<bool>,
+    pub icon_cache_size: Option<usize>,
+    /// Pixel size of the segment icons, 12 to 64.
+    pub icon_size: Option<i32>,
+    pub info_layout: Option<InfoLayout>,
+    pub info_position: Option<InfoPosition>,
+    pub scale_delta_colors: Option<bool>,
+    /// Multiplier for the main timer font size, on top of `font_scale`.
+    pub timer_scale: Option<f64>,
+    /// Multiplier for all text of the app, the main timer included.
+    pub font_scale: Option<f64>,
+    /// Minimum width in pixels of the delta label of each segment row.
+    pub delta_min_width: Option<i32>,
+    /// Minimum width in pixels of the comparison/split time label of each segment row.
+    pub comparison_min_width: Option<i32>,
+    /// Give skipped segments the distinct `skipped` style.
+    pub mark_skipped_segments: Option<bool>,
+    /// Show the comparison time of skipped segments instead of "--".
+    pub skipped_show_comparison: Option<bool>,
+    /// Show a small second timer for the timing method that isn't the current one.
+    pub show_secondary_timer: Option<bool>,
+    /// Color the running timer green or red with the live delta of the current segment.
+    pub color_main_timer: Option<bool>,
+    /// Show the live delta of the current segment all the time, not only once it is
+    /// behind or past the best segment.
+    pub always_show_live_delta: Option<bool>,
+    /// Only the splits and the running timer: no title and no best/comparison footer.
+    pub compact: Option<bool>,
+    /// Thin bar under the splits showing how far into the run the attempt is.
+    pub show_progress_bar: Option<bool>,
+    /// Segment rows with less padding, so more of them fit.
+    pub compact_rows: Option<bool>,
+    /// Whether the timer sections are stacked or placed side by side.
+    pub orientation: Option<TimerOrientation>,
+    /// Color overrides of the delta classes, e.g. `greensplit: "#00ff00"`.
+    #[serde(default)]
+    pub colors: BTreeMap<String, String>,
+    /// Sound effects played on timer events.
+    #[serde(default)]
+    pub sounds: SoundConfig,
+    /// Group segments named like LiveSplit subsplits under collapsible rows.
+    pub subsplits: Option<bool>,
+    /// Briefly highlight a row when its segment becomes a new gold.
+    pub animations: Option<bool>,
+}
+
+pub const DEFAULT_DELTA_MIN_WIDTH: i32 = 70;
+/// Decoded segment icons kept in memory, see `style.icon_cache_size`.
+pub const DEFAULT_ICON_CACHE_SIZE: usize = 64;
+/// Pixel size of segment icons, slightly bigger than the font by default.
+pub const DEFAULT_ICON_SIZE: i32 = 24;
+pub const MIN_ICON_SIZE: i32 = 12;
+pub const MAX_ICON_SIZE: i32 = 64;
+pub const DEFAULT_COMPARISON_MIN_WIDTH: i32 = 80;
+
+/// How the additional info rows are arranged.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum InfoLayout {
+    /// One row per info, stacked vertically.
+    #[default]
+    Vertical,
+    /// Infos flow side by side and wrap, for wide layouts.
+    Grid,
+}
+
+/// Where the additional info rows sit in the timer.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum InfoPosition {
+    /// Between the title and the splits.
+    AboveSplits,
+    /// Between the splits and the footer.
+    #[default]
+    AboveFooter,
+    /// Below the footer, at the very bottom.
+    BelowFooter,
+}
+
+/// How the title, splits, info and footer of the timer are laid out.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimerOrientation {
+    /// One section below the other.
+    #[default]
+    Vertical,
+    /// Sections side by side, for wide screens.
+    Horizontal,
 }
 
 impl Default for Style {
@@ -87,24 +461,130 @@ impl Default for Style {
             max_segments_displayed: Some(10),
             segments_scroll_follow_from: Some(8),
             show_icons: Some(true),
+            icon_cache_size: Some(DEFAULT_ICON_CACHE_SIZE),
+            icon_size: Some(DEFAULT_ICON_SIZE),
+            info_layout: Some(InfoLayout::Vertical),
+            info_position: Some(InfoPosition::AboveFooter),
+            scale_delta_colors: Some(false),
+            timer_scale: Some(1.0),
+            font_scale: Some(1.0),
+            delta_min_width: Some(DEFAULT_DELTA_MIN_WIDTH),
+            comparison_min_width: Some(DEFAULT_COMPARISON_MIN_WIDTH),
+            mark_skipped_segments: Some(false),
+            skipped_show_comparison: Some(false),
+            show_secondary_timer: Some(false),
+            color_main_timer: Some(false),
+            always_show_live_delta: Some(false),
+            compact: Some(false),
+            show_progress_bar: Some(false),
+            compact_rows: Some(false),
+            orientation: Some(TimerOrientation::Vertical),
+            colors: BTreeMap::new(),
+            sounds: SoundConfig::default(),
+            subsplits: Some(false),
+            animations: Some(false),
         }
     }
 }
 
+/// Sound files played on timer events, a missing one plays nothing.
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 #[serde(default)]
-struct Window {
+pub struct SoundConfig {
+    /// Master switch of all sounds.
+    pub enabled: bool,
+    /// Played on a split that isn't a gold.
+    pub split: Option<PathBuf>,
+    /// Played on a split beating the best segment time.
+    pub gold: Option<PathBuf>,
+    /// Played when a run ends in a new Personal Best.
+    pub personal_best: Option<PathBuf>,
+    /// Played when a running attempt is reset.
+    pub reset: Option<PathBuf>,
+}
+
+#[derive(Default, Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct Window {
     always_on_top: bool,
+    /// Hide the header bar until the pointer hovers the window.
+    pub auto_hide_header: bool,
+    /// Reopen the split editor on launch if it was open when TuxSplit was closed.
+    pub restore_editor: bool,
+    /// UI state: whether the split editor was open at shutdown.
+    pub editor_open: bool,
+    /// Opacity of the whole window, opaque when unset.
+    pub opacity: Option<f64>,
+    /// Don't paint a window background, for capturing the timer as an overlay.
+    pub transparent_background: bool,
 }
 
 #[derive(Default, Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 #[serde(default)]
 struct Connections {
+    /// Twitch channel the announcements are posted to, as its owner.
     twitch: Option<String>,
+    /// Chat OAuth token of the channel owner.
+    twitch_token: Option<String>,
+    /// Address of the LiveSplit Server protocol control socket, off when unset.
+    server: Option<SocketAddr>,
+    /// OBS WebSocket server switching scenes on run events, off when unset.
+    obs: Option<ObsConfig>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "kebab-case")]
+#[serde(default)]
+pub struct ObsConfig {
+    pub host: String,
+    pub port: u16,
+    /// Password of the OBS WebSocket server, if authentication is enabled.
+    pub password: Option<String>,
+    /// Scene switched to when a run starts.
+    pub running_scene: Option<String>,
+    /// Scene switched to when a run finishes.
+    pub ended_scene: Option<String>,
+}
+
+impl Default for ObsConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_owned(),
+            port: 4455,
+            password: None,
+            running_scene: None,
+            ended_scene: None,
+        }
+    }
+}
+
+/// Why the configured auto-splitter script could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AutoSplitterError {
+    /// No file at the configured path.
+    NotFound(PathBuf),
+    /// The runtime rejected the file, e.g. it is not a valid WebAssembly module.
+    Invalid { path: PathBuf, message: String },
+}
+
+impl std::fmt::Display for AutoSplitterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(f, "Auto splitter {} not found", path.display()),
+            Self::Invalid { path, message } => write!(
+                f,
+                "Auto splitter {} failed to load: {message}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AutoSplitterError {}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "kebab-case")]
 #[serde(default)]
@@ -138,6 +618,11 @@ pub struct AdditionalInfoVisibility {
     pub show_current_pace: bool,
     pub show_total_playtime: bool,
     pub show_pb_chance: bool,
+    pub show_sum_of_best: bool,
+    pub show_segment_progress: bool,
+    pub show_attempt_count: bool,
+    pub show_average_segment: bool,
+    pub show_goal_time: bool,
 }
 
 impl Default for AdditionalInfoVisibility {
@@ -150,6 +635,11 @@ impl Default for AdditionalInfoVisibility {
             show_current_pace: false,
             show_total_playtime: false,
             show_pb_chance: false,
+            show_sum_of_best: false,
+            show_segment_progress: false,
+            show_attempt_count: false,
+            show_average_segment: false,
+            show_goal_time: false,
         }
     }
 }
@@ -161,17 +651,50 @@ impl Config {
     }
 
     pub fn save(&self, path: impl AsRef<Path>) -> Result<(), std::io::Error> {
-        let buf = serde_yaml::to_string(self).unwrap();
+        let buf = self.to_yaml().map_err(std::io::Error::other)?;
         fs::write(path, buf)?;
         Ok(())
     }
 
+    /// The config as written by `save`, following `general.config_format`. Fields keep
+    /// their declaration order, so saving twice gives the same file.
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        match self.general.config_format {
+            ConfigFormat::Pretty => Ok(format!("{CONFIG_HEADER}{}", serde_yaml::to_string(self)?)),
+            ConfigFormat::Compact => {
+                let mut value = serde_yaml::to_value(self)?;
+                strip_default_nulls(&mut value, &serde_yaml::to_value(Self::default())?);
+                serde_yaml::to_string(&value)
+            }
+        }
+    }
+
     pub fn parse_run(&self) -> Option<Run> {
-        let path = self.general.splits.clone()?;
-        let file = fs::read(&path).ok()?;
-        let mut run = composite::parse(&file, Some(&path)).ok()?.run;
-        run.fix_splits();
-        Some(run)
+        let path = self.general.splits.as_deref()?;
+        self.read_run(path).ok().map(|(run, _)| run)
+    }
+
+    /// Reads a splits file in any format livesplit-core understands (LiveSplit, WSplit,
+    /// Urn, SplitterZ, ...) and returns the run along with the detected format.
+    pub fn read_run(&self, path: &Path) -> Result<(Run, TimerKind), String> {
+        let file = fs::read(path).map_err(|err| err.to_string())?;
+        // The path lets the parser resolve relative paths and guess the format
+        let parsed = composite::parse(&file, Some(path)).map_err(|err| err.to_string())?;
+        let mut run = parsed.run;
+        if self.warns_about_segment_sums(&run) {
+            warn!(
+                "Segment times in {} don't add up, the timer repairs them",
+                path.display()
+            );
+        }
+        ensure_generated_comparisons(&mut run);
+        Ok((run, parsed.kind))
+    }
+
+    /// Whether loading `run` warns about its times, with `fix-segment-sums` on and
+    /// times livesplit-core's repair would change.
+    fn warns_about_segment_sums(&self, run: &Run) -> bool {
+        self.general.fix_segment_sums && needs_split_fixing(run)
     }
 
     pub fn parse_run_or_default(&self) -> Run {
@@ -180,10 +703,20 @@ impl Config {
             run.set_game_name("Game");
             run.set_category_name("Category");
             run.push_segment(Segment::new("Time"));
+            // Not permanent, so saving the run drops the marker
+            run.metadata_mut()
+                .custom_variable_mut(DEFAULT_RUN_MARKER)
+                .set_value("true");
             run
         })
     }
 
+    /// Whether the timer should show the load prompt instead of `run`: the placeholder
+    /// run nobody has started yet, with `general.default_run_prompt`.
+    pub fn shows_default_run_prompt(&self, run: &Run) -> bool {
+        self.general.default_run_prompt && is_default_run(run)
+    }
+
     pub fn is_game_time(&self) -> bool {
         self.general.timing_method == Some(TimingMethod::GameTime)
     }
@@ -192,7 +725,78 @@ impl Config {
         self.general.splits = Some(path);
     }
 
+    /// Key of the loaded splits file in per-run options.
+    fn splits_key(&self) -> String {
+        self.general
+            .splits
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Whether the subsplit group is collapsed in the loaded splits.
+    pub fn is_group_collapsed(&self, group: &str) -> bool {
+        self.general
+            .collapsed_groups
+            .get(&self.splits_key())
+            .is_some_and(|groups| groups.contains(group))
+    }
+
+    pub fn set_group_collapsed(&mut self, group: &str, collapsed: bool) {
+        let key = self.splits_key();
+        let groups = self
+            .general
+            .collapsed_groups
+            .entry(key.clone())
+            .or_default();
+        if collapsed {
+            groups.insert(group.to_owned());
+        } else {
+            groups.remove(group);
+        }
+        if groups.is_empty() {
+            self.general.collapsed_groups.remove(&key);
+        }
+    }
+
+    /// Where the control server listens, if it is enabled.
+    pub fn server_address(&self) -> Option<SocketAddr> {
+        self.connections.server
+    }
+
+    /// Twitch channel and token for chat announcements, if both are set.
+    pub fn twitch_account(&self) -> Option<(&str, &str)> {
+        let channel = self.connections.twitch.as_deref().map(str::trim)?;
+        let token = self.connections.twitch_token.as_deref().map(str::trim)?;
+        (!channel.is_empty() && !token.is_empty()).then_some((channel, token))
+    }
+
+    /// OBS WebSocket connection, if scene switching is enabled.
+    pub fn obs(&self) -> Option<&ObsConfig> {
+        self.connections.obs.as_ref()
+    }
+
+    /// Remembers the folder of a file picked in a file chooser.
+    pub fn remember_directory(&mut self, picked: &Path) {
+        if let Some(dir) = picked.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            self.general.last_directory = Some(dir.to_path_buf());
+        }
+    }
+
+    /// The folder file choosers should open in, if one was remembered and still exists.
+    pub fn chooser_directory(&self) -> Option<&Path> {
+        self.general
+            .last_directory
+            .as_deref()
+            .filter(|dir| dir.is_dir())
+    }
+
     pub fn disable_hotkey_system(&mut self) {
+        if let Some(discard_reset_key) = &self.discard_reset_key {
+            discard_reset_key.deactivate();
+        }
         if self.hotkey_system.is_none() {
             return;
         }
@@ -202,7 +806,7 @@ impl Config {
                 Timer::new(self.parse_run_or_default())
                     .expect("Failed to create timer")
                     .into_shared(),
-                self.hotkeys,
+                self.system_hotkeys(),
             )
             .expect("Failed to create HotkeySystem"),
         );
@@ -210,6 +814,9 @@ impl Config {
     }
 
     pub fn enable_hotkey_system(&mut self) {
+        if let Some(discard_reset_key) = &self.discard_reset_key {
+            discard_reset_key.activate();
+        }
         if self.hotkey_system.is_none() {
             return;
         }
@@ -219,39 +826,188 @@ impl Config {
                 Timer::new(self.parse_run_or_default())
                     .expect("Failed to create timer")
                     .into_shared(),
-                self.hotkeys,
+                self.system_hotkeys(),
             )
             .expect("Failed to create HotkeySystem"),
         );
         hotkey_system.activate();
     }
 
-    pub fn create_hotkey_system(&mut self, timer: SharedTimer) -> Option<()> {
-        let hotkey_system_res = HotkeySystem::with_config(timer, self.hotkeys);
-        if let Ok(hotkey_system) = hotkey_system_res {
-            self.hotkey_system = Some(hotkey_system);
-            Some(())
+    /// The `GDK_BACKEND` to force at startup, `None` to let GDK pick.
+    pub fn gdk_backend(&self, wayland_session: bool) -> Option<&'static str> {
+        match self.general.display_backend {
+            DisplayBackend::X11 => Some("x11"),
+            DisplayBackend::Native => None,
+            DisplayBackend::Auto => (wayland_session && self.has_global_hotkeys()).then_some("x11"),
+        }
+    }
+
+    /// Whether any key is bound for the global hotkey system.
+    fn has_global_hotkeys(&self) -> bool {
+        let hotkeys = self.system_hotkeys();
+        [
+            hotkeys.split,
+            hotkeys.reset,
+            hotkeys.undo,
+            hotkeys.skip,
+            hotkeys.pause,
+            hotkeys.undo_all_pauses,
+            hotkeys.previous_comparison,
+            hotkeys.next_comparison,
+            hotkeys.toggle_timing_method,
+            self.system_discard_reset(),
+        ]
+        .iter()
+        .any(Option::is_some)
+    }
+
+    /// The hotkeys handed to the global hotkey system. With hold-to-reset the reset key
+    /// is left out: the system only sees presses, so the timer window handles it instead
+    /// (see `ui::hotkeys::install_reset_keys`).
+    pub fn system_hotkeys(&self) -> HotkeyConfig {
+        let mut hotkeys = self.hotkeys;
+        if self.hold_to_reset().is_some() {
+            hotkeys.reset = None;
+        }
+        hotkeys
+    }
+
+    /// The discarding reset key registered globally. With hold-to-reset it is left to
+    /// the timer window like the reset key.
+    pub fn system_discard_reset(&self) -> Option<Hotkey> {
+        self.general
+            .discard_reset_hotkey
+            .filter(|_| self.hold_to_reset().is_none())
+    }
+
+    /// Lets the split key resume a paused timer, which the hotkey system can't split:
+    /// while `resumes` is set the key toggles the pause instead (the pause key does
+    /// nothing until then). The keys are swapped in place, the hotkey system is kept.
+    pub fn set_split_resumes(&mut self, resumes: bool) {
+        let hotkeys = self.system_hotkeys();
+        let Some(split) = hotkeys.split else {
+            return;
+        };
+        let Some(hotkey_system) = &mut self.hotkey_system else {
+            return;
+        };
+        // Each action lets go of the key before the other one takes it
+        let swapped = if resumes {
+            hotkey_system
+                .set_split(None)
+                .and_then(|()| hotkey_system.set_pause(Some(split)))
         } else {
-            None
+            hotkey_system
+                .set_pause(hotkeys.pause)
+                .and_then(|()| hotkey_system.set_split(Some(split)))
+        };
+        if let Err(err) = swapped {
+            warn!("Could not swap the split key for the primed timer: {err}");
+        }
+    }
+
+    /// How long the reset key has to be held, `None` when a press resets right away.
+    pub fn hold_to_reset(&self) -> Option<std::time::Duration> {
+        self.general
+            .hold_to_reset_ms
+            .filter(|&ms| ms > 0)
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// Whether the global hotkeys are currently listening (they handle keys even while
+    /// the window is focused).
+    pub fn hotkeys_active(&self) -> bool {
+        self.hotkey_system
+            .as_ref()
+            .is_some_and(HotkeySystem::is_active)
+    }
+
+    /// Replaces the hotkeys and the discarding reset key and re-creates the hotkey
+    /// system with them, keeping it deactivated if it was. On failure the previous keys
+    /// are restored.
+    pub fn rebind_hotkeys(
+        &mut self,
+        hotkeys: HotkeyConfig,
+        discard_reset: Option<Hotkey>,
+        timer: SharedTimer,
+    ) -> Option<()> {
+        let active = self.hotkeys_active();
+        let previous = (self.hotkeys, self.general.discard_reset_hotkey);
+
+        // Release the old keys first so the new system can register them
+        self.hotkey_system = None;
+        self.discard_reset_key = None;
+        self.hotkeys = hotkeys;
+        self.general.discard_reset_hotkey = discard_reset;
+        let created = self.create_hotkey_system(timer.clone());
+        if created.is_none() {
+            (self.hotkeys, self.general.discard_reset_hotkey) = previous;
+            self.create_hotkey_system(timer);
+        }
+        if !active {
+            self.disable_hotkey_system();
         }
+        created
+    }
+
+    /// Registers the global hotkeys, all of them or none.
+    pub fn create_hotkey_system(&mut self, timer: SharedTimer) -> Option<()> {
+        let hotkey_system = HotkeySystem::with_config(timer.clone(), self.system_hotkeys()).ok()?;
+        let discard_reset_key = match self.system_discard_reset() {
+            Some(hotkey) => Some(DiscardResetKey::new(hotkey, timer)?),
+            None => None,
+        };
+        self.hotkey_system = Some(hotkey_system);
+        self.discard_reset_key = discard_reset_key;
+        Some(())
     }
 
     pub fn configure_timer(&self, timer: &mut Timer) {
         if self.is_game_time() {
             timer.set_current_timing_method(TimingMethod::GameTime);
         }
-        if let Some(comparison) = &self.general.comparison {
-            timer.set_current_comparison(&**comparison).ok();
+        if let Some(comparison) = &self.general.comparison
+            && timer.set_current_comparison(&**comparison).is_err()
+        {
+            warn!("Comparison {comparison:?} is not available for the loaded splits");
         }
     }
 
-    pub fn save_splits(&self, timer: &Timer) {
-        if let Some(path) = &self.general.splits {
-            let mut buf = String::new();
-            let _ = save_timer(timer, &mut buf);
-            // FIXME: Don't ignore not being able to save.
-            let _ = fs::write(path, &buf);
+    /// Writes the run to the splits file and returns its path, or `None` when no
+    /// splits file is set.
+    pub fn save_splits(&self, timer: &Timer) -> Result<Option<PathBuf>, std::io::Error> {
+        let Some(path) = &self.general.splits else {
+            return Ok(None);
+        };
+        let mut buf = String::new();
+        save_timer(timer, &mut buf).map_err(std::io::Error::other)?;
+        fs::write(path, &buf)?;
+        Ok(Some(path.clone()))
+    }
+
+    /// The run as CSV, one line per segment with its PB split time, PB segment time and
+    /// best segment time for `method`. Missing times are left empty.
+    pub fn export_csv(&self, run: &Run, method: TimingMethod) -> String {
+        let mut csv = String::from("Segment,PB Split Time,Segment Time,Best Segment Time\n");
+        let mut previous_split = TimeSpan::zero();
+        for segment in run.segments() {
+            let split = segment.personal_best_split_time()[method];
+            let segment_time = split.map(|split| split - previous_split);
+            if let Some(split) = split {
+                previous_split = split;
+            }
+            let best = segment.best_segment_time()[method];
+
+            let cells = [
+                csv_field(segment.name()),
+                split.map_or_else(String::new, |t| self.format.split.format_time_span(&t)),
+                segment_time.map_or_else(String::new, |t| self.format.segment.format_time_span(&t)),
+                best.map_or_else(String::new, |t| self.format.segment.format_time_span(&t)),
+            ];
+            csv.push_str(&cells.join(","));
+            csv.push('\n');
         }
+        csv
     }
 
     pub const fn setup_logging(&self) {
@@ -289,16 +1045,596 @@ impl Config {
         // }
     }
 
-    pub fn maybe_load_auto_splitter(&self, runtime: &auto_splitting::Runtime) {
-        if let Some(auto_splitter) = &self.general.auto_splitter
-            && let Err(e) = runtime.load_script_blocking(auto_splitter.clone())
-        {
-            error!("Auto Splitter failed to load: {}", &e); // TODO: Create a custom error that
-            // pops up in the UI
+    /// Path of the auto-splitter script to run, unless none is set or it is switched off.
+    pub fn active_auto_splitter(&self) -> Option<&Path> {
+        self.general
+            .auto_splitter
+            .as_deref()
+            .filter(|_| self.general.auto_splitter_enabled)
+    }
+
+    /// Loads the configured auto-splitter script in place of the current one, or only
+    /// unloads the current one when none is configured or it is switched off.
+    pub fn maybe_load_auto_splitter(
+        &self,
+        runtime: &auto_splitting::Runtime,
+    ) -> Result<(), AutoSplitterError> {
+        // Errors when no script was loaded, nothing to undo then
+        let _ = runtime.unload_script_blocking();
+        let Some(auto_splitter) = self.active_auto_splitter() else {
+            return Ok(());
+        };
+        let result = if auto_splitter.is_file() {
+            runtime
+                .load_script_blocking(auto_splitter.to_path_buf())
+                .map_err(|e| AutoSplitterError::Invalid {
+                    path: auto_splitter.to_path_buf(),
+                    message: e.to_string(),
+                })
+        } else {
+            Err(AutoSplitterError::NotFound(auto_splitter.to_path_buf()))
+        };
+        if let Err(e) = &result {
+            error!("{e}");
         }
+        result
     }
 
     pub fn into_shared(self) -> SharedConfig {
         std::sync::Arc::new(std::sync::RwLock::new(self))
     }
 }
+
+/// Whether `Run::fix_splits`, which every timer runs on the run it's given, would
+/// change the run's split, segment or best segment times.
+fn needs_split_fixing(run: &Run) -> bool {
+    let mut fixed = run.clone();
+    fixed.fix_splits();
+    fixed != *run
+}
+
+/// Drops the unset (null) entries of a serialized config that are unset by default
+/// too. Others, like an unbound hotkey, have to stay or loading brings the default back.
+fn strip_default_nulls(value: &mut serde_yaml::Value, default: &serde_yaml::Value) {
+    let (serde_yaml::Value::Mapping(mapping), serde_yaml::Value::Mapping(defaults)) =
+        (value, default)
+    else {
+        return;
+    };
+    mapping.retain(|key, v| !(v.is_null() && defaults.get(key).is_some_and(|d| d.is_null())));
+    for (key, v) in mapping.iter_mut() {
+        if let Some(default) = defaults.get(key) {
+            strip_default_nulls(v, default);
+        }
+    }
+}
+
+/// Quotes a CSV cell when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod save_format_tests {
+    use super::{Config, ConfigFormat};
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tuxsplit-{}-{name}.yaml", std::process::id()))
+    }
+
+    fn round_trip(config: &Config, name: &str) -> Config {
+        let path = temp_path(name);
+        config.save(&path).expect("save");
+        let parsed = Config::parse(&path).expect("saved config parses");
+        let _ = std::fs::remove_file(&path);
+        parsed
+    }
+
+    #[test]
+    fn pretty_keeps_unset_options_below_a_header() {
+        let yaml = Config::default().to_yaml().expect("yaml");
+        assert!(yaml.starts_with("# TuxSplit configuration"));
+        assert!(yaml.contains("splits: null"), "{yaml}");
+    }
+
+    #[test]
+    fn compact_drops_unset_options() {
+        let mut config = Config::default();
+        config.general.config_format = ConfigFormat::Compact;
+        let yaml = config.to_yaml().expect("yaml");
+        assert!(!yaml.starts_with('#'));
+        assert!(!yaml.contains("splits:"), "{yaml}");
+        assert!(yaml.contains("config-format: compact"), "{yaml}");
+    }
+
+    #[test]
+    fn compact_keeps_options_unset_against_their_default() {
+        let mut config = Config::default();
+        config.general.config_format = ConfigFormat::Compact;
+        config.style.timer_scale = None;
+        let yaml = config.to_yaml().expect("yaml");
+        assert!(yaml.contains("timer-scale: null"), "{yaml}");
+    }
+
+    #[test]
+    fn both_formats_round_trip() {
+        for format in [ConfigFormat::Pretty, ConfigFormat::Compact] {
+            let mut config = Config::default();
+            config.general.config_format = format;
+            config.general.comparison = Some("Best Segments".to_owned());
+            config.style.timer_scale = Some(1.5);
+            config.window.opacity = None;
+
+            let parsed = round_trip(&config, &format!("{format:?}"));
+            // Serializing both the same way compares every option
+            assert_eq!(
+                serde_yaml::to_string(&parsed).unwrap(),
+                serde_yaml::to_string(&config).unwrap()
+            );
+            // Saving is stable
+            assert_eq!(parsed.to_yaml().unwrap(), config.to_yaml().unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod export_csv_tests {
+    use super::{Config, Format};
+    use crate::formatters::TimeFormatPreset;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+
+    fn segment(name: &str, split: Option<f64>, best: Option<f64>) -> Segment {
+        let mut segment = Segment::new(name);
+        segment.set_personal_best_split_time(
+            Time::new().with_real_time(split.map(TimeSpan::from_seconds)),
+        );
+        segment.set_best_segment_time(Time::new().with_real_time(best.map(TimeSpan::from_seconds)));
+        segment
+    }
+
+    fn config() -> Config {
+        Config {
+            format: Format::with_all_preset(TimeFormatPreset::ShowDecimals),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn one_line_per_segment_with_segment_times() {
+        let mut run = Run::new();
+        run.push_segment(segment("Forest", Some(60.0), Some(55.5)));
+        run.push_segment(segment("Castle", Some(150.25), Some(85.0)));
+
+        assert_eq!(
+            config().export_csv(&run, TimingMethod::RealTime),
+            "Segment,PB Split Time,Segment Time,Best Segment Time\n\
+             Forest,1:00.00,1:00.00,55.50\n\
+             Castle,2:30.25,1:30.25,1:25.00\n"
+        );
+    }
+
+    #[test]
+    fn missing_times_are_empty_cells() {
+        let mut run = Run::new();
+        run.push_segment(segment("A", Some(10.0), None));
+        run.push_segment(segment("B", None, Some(5.0)));
+        run.push_segment(segment("C", Some(30.0), None));
+
+        let csv = config().export_csv(&run, TimingMethod::RealTime);
+        let lines: Vec<&str> = csv.lines().skip(1).collect();
+        assert_eq!(lines, ["A,10.00,10.00,", "B,,,5.00", "C,30.00,20.00,"]);
+
+        // Nothing recorded for game time at all
+        let csv = config().export_csv(&run, TimingMethod::GameTime);
+        let lines: Vec<&str> = csv.lines().skip(1).collect();
+        assert_eq!(lines, ["A,,,", "B,,,", "C,,,"]);
+    }
+
+    #[test]
+    fn names_with_separators_are_quoted() {
+        let mut run = Run::new();
+        run.push_segment(segment("Boss, \"Final\"", None, None));
+
+        let csv = config().export_csv(&run, TimingMethod::RealTime);
+        assert_eq!(csv.lines().nth(1), Some("\"Boss, \"\"Final\"\"\",,,"));
+    }
+}
+
+#[cfg(test)]
+mod last_directory_tests {
+    use super::Config;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn picked_file_folder_is_remembered() {
+        let mut config = Config::default();
+        config.remember_directory(Path::new("/home/runner/splits/any%.lss"));
+        assert_eq!(
+            config.general.last_directory,
+            Some(PathBuf::from("/home/runner/splits"))
+        );
+
+        // A bare file name has no folder to remember
+        config.remember_directory(Path::new("any%.lss"));
+        assert_eq!(
+            config.general.last_directory,
+            Some(PathBuf::from("/home/runner/splits"))
+        );
+    }
+
+    #[test]
+    fn last_directory_round_trips_through_the_config_file() {
+        let dir = std::env::temp_dir();
+        let mut config = Config::default();
+        config.remember_directory(&dir.join("run.lss"));
+
+        let yaml = serde_yaml::to_string(&config).expect("serialize");
+        let reloaded: Config = serde_yaml::from_str(&yaml).expect("deserialize");
+        assert_eq!(
+            reloaded.general.last_directory.as_deref(),
+            Some(dir.as_path())
+        );
+        assert_eq!(reloaded.chooser_directory(), Some(dir.as_path()));
+    }
+
+    #[test]
+    fn missing_folders_are_not_used() {
+        let mut config = Config::default();
+        assert_eq!(config.chooser_directory(), None);
+        config.general.last_directory = Some(PathBuf::from("/nonexistent/tuxsplit/dir"));
+        assert_eq!(config.chooser_directory(), None);
+    }
+}
+
+#[cfg(test)]
+mod goal_time_tests {
+    use super::Config;
+    use livesplit_core::TimeSpan;
+
+    #[test]
+    fn goal_time_is_written_as_text_and_read_back() {
+        let mut config = Config::default();
+        config.general.goal_time = Some(TimeSpan::from_seconds(3723.5));
+
+        let yaml = serde_yaml::to_string(&config).expect("serialize");
+        assert!(yaml.contains("goal-time: 1:02:03.500"), "{yaml}");
+
+        let reloaded: Config = serde_yaml::from_str(&yaml).expect("deserialize");
+        assert_eq!(
+            reloaded.general.goal_time,
+            Some(TimeSpan::from_seconds(3723.5))
+        );
+    }
+
+    fn config_with_goal(goal: &str) -> Result<Config, serde_yaml::Error> {
+        let mut value = serde_yaml::to_value(Config::default()).expect("serialize");
+        value["general"]["goal-time"] = goal.into();
+        serde_yaml::from_value(value)
+    }
+
+    #[test]
+    fn goal_time_accepts_unit_suffixes_and_rejects_garbage() {
+        let config = config_with_goal("25m30s").expect("deserialize");
+        assert_eq!(
+            config.general.goal_time,
+            Some(TimeSpan::from_seconds(1530.0))
+        );
+
+        assert!(config_with_goal("soon").is_err());
+    }
+}
+
+#[cfg(test)]
+mod read_run_tests {
+    use super::Config;
+    use livesplit_core::run::{parser::TimerKind, saver::livesplit::save_run};
+    use livesplit_core::{Run, Segment};
+    use std::fs;
+
+    #[test]
+    fn livesplit_files_are_detected() {
+        let mut run = Run::new();
+        run.set_game_name("Game");
+        run.push_segment(Segment::new("Only"));
+        let mut lss = String::new();
+        save_run(&run, &mut lss).expect("save");
+        let path = std::env::temp_dir().join("tuxsplit-read-run-test.lss");
+        fs::write(&path, lss).expect("write");
+
+        let (parsed, kind) = Config::default().read_run(&path).expect("parse");
+        assert!(matches!(kind, TimerKind::LiveSplit));
+        assert_eq!(parsed.game_name(), "Game");
+        assert_eq!(parsed.len(), 1);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn unreadable_or_unknown_files_are_errors() {
+        let config = Config::default();
+        assert!(
+            config
+                .read_run(&std::env::temp_dir().join("tuxsplit-missing-splits.lss"))
+                .is_err()
+        );
+
+        let path = std::env::temp_dir().join("tuxsplit-read-run-garbage.txt");
+        fs::write(&path, [0xff, 0x00, 0x13, 0x37]).expect("write");
+        assert!(config.read_run(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+}
+
+#[cfg(test)]
+mod hold_to_reset_tests {
+    use super::Config;
+    use std::time::Duration;
+
+    #[test]
+    fn instant_reset_keeps_the_reset_hotkey() {
+        let config = Config::default();
+        assert_eq!(config.hold_to_reset(), None);
+        assert_eq!(config.system_hotkeys().reset, config.hotkeys.reset);
+    }
+
+    #[test]
+    fn hold_to_reset_takes_reset_away_from_the_hotkey_system() {
+        let mut config = Config::default();
+        config.general.hold_to_reset_ms = Some(750);
+
+        assert_eq!(config.hold_to_reset(), Some(Duration::from_millis(750)));
+        assert_eq!(config.system_hotkeys().reset, None);
+        assert_eq!(config.system_hotkeys().split, config.hotkeys.split);
+        // The configured key itself is kept
+        assert!(config.hotkeys.reset.is_some());
+    }
+
+    #[test]
+    fn zero_means_disabled() {
+        let mut config = Config::default();
+        config.general.hold_to_reset_ms = Some(0);
+        assert_eq!(config.hold_to_reset(), None);
+    }
+}
+
+#[cfg(test)]
+mod display_backend_tests {
+    use super::{Config, DisplayBackend};
+
+    #[test]
+    fn auto_forces_x11_only_for_hotkeys_on_wayland() {
+        let mut config = Config::default();
+        assert_eq!(config.gdk_backend(true), Some("x11"));
+        assert_eq!(config.gdk_backend(false), None);
+
+        // Nothing to listen for, so no reason to leave Wayland
+        let hotkeys = &mut config.hotkeys;
+        for slot in [
+            &mut hotkeys.split,
+            &mut hotkeys.reset,
+            &mut hotkeys.undo,
+            &mut hotkeys.skip,
+            &mut hotkeys.pause,
+            &mut hotkeys.undo_all_pauses,
+            &mut hotkeys.previous_comparison,
+            &mut hotkeys.next_comparison,
+            &mut hotkeys.toggle_timing_method,
+        ] {
+            *slot = None;
+        }
+        assert_eq!(config.gdk_backend(true), None);
+    }
+
+    #[test]
+    fn explicit_backends_ignore_the_session() {
+        let mut config = Config::default();
+        config.general.display_backend = DisplayBackend::Native;
+        assert_eq!(config.gdk_backend(true), None);
+        config.general.display_backend = DisplayBackend::X11;
+        assert_eq!(config.gdk_backend(false), Some("x11"));
+    }
+}
+
+#[cfg(test)]
+mod collapsed_groups_tests {
+    use super::Config;
+    use std::path::PathBuf;
+
+    #[test]
+    fn collapsed_groups_are_kept_per_splits_file() {
+        let mut config = Config::default();
+        config.set_splits_path(PathBuf::from("/splits/celeste.lss"));
+        config.set_group_collapsed("Chapter 1", true);
+        assert!(config.is_group_collapsed("Chapter 1"));
+
+        config.set_splits_path(PathBuf::from("/splits/hollow-knight.lss"));
+        assert!(!config.is_group_collapsed("Chapter 1"));
+
+        config.set_splits_path(PathBuf::from("/splits/celeste.lss"));
+        config.set_group_collapsed("Chapter 1", false);
+        assert!(!config.is_group_collapsed("Chapter 1"));
+        // Nothing collapsed leaves nothing behind in the config file
+        assert!(config.general.collapsed_groups.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod auto_splitter_tests {
+    use super::*;
+
+    fn runtime() -> auto_splitting::Runtime {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Only"));
+        auto_splitting::Runtime::new(Timer::new(run).expect("timer").into_shared())
+    }
+
+    #[test]
+    fn switched_off_script_is_not_loaded() {
+        let runtime = runtime();
+        let mut config = Config::default();
+        config.general.auto_splitter = Some(PathBuf::from("/nonexistent/splitter.wasm"));
+
+        assert_eq!(
+            config.maybe_load_auto_splitter(&runtime),
+            Err(AutoSplitterError::NotFound(PathBuf::from(
+                "/nonexistent/splitter.wasm"
+            )))
+        );
+        config.general.auto_splitter_enabled = false;
+        assert_eq!(config.maybe_load_auto_splitter(&runtime), Ok(()));
+    }
+
+    #[test]
+    fn invalid_module_is_told_apart_from_a_missing_file() {
+        let path = std::env::temp_dir().join("tuxsplit-invalid-splitter.wasm");
+        fs::write(&path, "not webassembly").expect("write script");
+        let mut config = Config::default();
+        config.general.auto_splitter = Some(path.clone());
+
+        let result = config.maybe_load_auto_splitter(&runtime());
+        let _ = fs::remove_file(&path);
+        assert!(matches!(
+            result,
+            Err(AutoSplitterError::Invalid { path: failed, .. }) if failed == path
+        ));
+    }
+
+    #[test]
+    fn reloading_needs_an_enabled_script() {
+        let mut config = Config::default();
+        assert_eq!(config.active_auto_splitter(), None);
+        config.general.auto_splitter = Some(PathBuf::from("splitter.wasm"));
+        assert_eq!(
+            config.active_auto_splitter(),
+            Some(Path::new("splitter.wasm"))
+        );
+        config.general.auto_splitter_enabled = false;
+        assert_eq!(config.active_auto_splitter(), None);
+    }
+
+    #[test]
+    fn no_script_is_fine() {
+        assert_eq!(
+            Config::default().maybe_load_auto_splitter(&runtime()),
+            Ok(())
+        );
+    }
+}
+
+#[cfg(test)]
+mod configure_timer_tests {
+    use super::Config;
+    use crate::utils::comparisons::ensure_generated_comparisons;
+    use livesplit_core::comparison::{average_segments, balanced_pb};
+    use livesplit_core::{Run, Segment, Timer};
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        ensure_generated_comparisons(&mut run);
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn applies_average_segments_comparison() {
+        let mut config = Config::default();
+        config.general.comparison = Some(average_segments::NAME.to_owned());
+        let mut timer = timer();
+
+        config.configure_timer(&mut timer);
+
+        assert_eq!(timer.current_comparison(), average_segments::NAME);
+    }
+
+    #[test]
+    fn applies_balanced_pb_comparison() {
+        let mut config = Config::default();
+        config.general.comparison = Some(balanced_pb::NAME.to_owned());
+        let mut timer = timer();
+
+        config.configure_timer(&mut timer);
+
+        assert_eq!(timer.current_comparison(), balanced_pb::NAME);
+        // Info widgets reading the current comparison cope with its empty times
+        let _ =
+            livesplit_core::analysis::current_pace::calculate(&timer.snapshot(), balanced_pb::NAME);
+    }
+
+    #[test]
+    fn unknown_comparison_keeps_current_one() {
+        let mut config = Config::default();
+        config.general.comparison = Some("Does Not Exist".to_owned());
+        let mut timer = timer();
+        let before = timer.current_comparison().to_owned();
+
+        config.configure_timer(&mut timer);
+
+        assert_eq!(timer.current_comparison(), before);
+    }
+}
+
+#[cfg(test)]
+mod segment_sums_tests {
+    use super::{Config, needs_split_fixing};
+    use livesplit_core::run::saver::livesplit::save_run;
+    use livesplit_core::{Run, Segment, Time, TimeSpan};
+    use std::fs;
+
+    fn split_rt(seconds: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)))
+    }
+
+    fn run_with_splits(splits: &[f64]) -> Run {
+        let mut run = Run::new();
+        for (i, &split) in splits.iter().enumerate() {
+            let mut segment = Segment::new(format!("Split {i}"));
+            segment.set_personal_best_split_time(split_rt(split));
+            run.push_segment(segment);
+        }
+        run
+    }
+
+    #[test]
+    fn consistent_run_needs_no_fixing() {
+        let mut run = run_with_splits(&[10.0, 25.0, 40.0]);
+        run.fix_splits();
+        assert!(!needs_split_fixing(&run));
+    }
+
+    #[test]
+    fn gold_slower_than_pb_segment_needs_fixing() {
+        let mut run = run_with_splits(&[10.0, 25.0]);
+        run.fix_splits();
+        *run.segment_mut(1).best_segment_time_mut() = split_rt(20.0);
+        assert!(needs_split_fixing(&run));
+    }
+
+    #[test]
+    fn loading_warns_about_inconsistent_files_only_when_enabled() {
+        let mut run = run_with_splits(&[10.0, 25.0]);
+        run.fix_splits();
+        *run.segment_mut(1).best_segment_time_mut() = split_rt(20.0);
+        let mut lss = String::new();
+        save_run(&run, &mut lss).expect("save");
+        let path = std::env::temp_dir().join("tuxsplit-segment-sums-test.lss");
+        fs::write(&path, lss).expect("write");
+
+        let mut config = Config::default();
+        let (loaded, _) = config.read_run(&path).expect("parse");
+        // Loading leaves the repair to the timer
+        assert_eq!(
+            loaded.segment(1).best_segment_time().real_time,
+            Some(TimeSpan::from_seconds(20.0))
+        );
+        assert!(config.warns_about_segment_sums(&loaded));
+
+        config.general.fix_segment_sums = false;
+        let (loaded, _) = config.read_run(&path).expect("parse");
+        assert!(!config.warns_about_segment_sums(&loaded));
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,8 +1,9 @@
 //! Global application context providing shared access to the Timer, Config,
 //! Runtime (auto-splitting), and a signal bus for run mutations.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
 
 use glib::prelude::*;
 use glib::{subclass::Signal, subclass::prelude::*};
@@ -11,19 +12,29 @@ use std::sync::OnceLock;
 use std::env;
 use std::path::{Path, PathBuf};
 
-use gtk4::gio;
+use gtk4::{EventControllerMotion, GestureClick, gdk, gio};
 
 use adw::prelude::*;
-use adw::{Application, ApplicationWindow, ToolbarView};
+use adw::{Application, ApplicationWindow, Toast, ToastOverlay, ToolbarView};
 
 use tracing::debug;
 use tracing::info;
+use tracing::warn;
 
-use livesplit_core::{Run, SharedTimer, Timer, auto_splitting::Runtime};
+use livesplit_core::hotkey::Hotkey;
+use livesplit_core::{HotkeyConfig, Run, SharedTimer, Timer, TimerPhase, auto_splitting::Runtime};
 
-use crate::config::Config;
+use crate::config::{AutoSplitterError, Config};
 use crate::ui::TuxSplitHeader;
+use crate::ui::editor::SplitEditor;
+use crate::ui::hotkeys::{install_cheat_sheet, install_reset_keys};
+use crate::ui::styles::{apply_delta_colors, apply_text_scales, apply_window_appearance};
 use crate::ui::timer::TuxSplitTimer;
+use crate::utils::comparisons::ensure_generated_comparisons;
+use crate::utils::debounce::Debouncer;
+
+/// Quiet time after the last config change before it is written to disk.
+const CONFIG_SAVE_DELAY: Duration = Duration::from_millis(500);
 
 mod imp {
     use super::*;
@@ -32,6 +43,12 @@ mod imp {
         pub timer: RefCell<SharedTimer>,
         pub runtime: RefCell<Runtime>,
         pub config: RefCell<Config>,
+        pub toast_overlay: RefCell<Option<ToastOverlay>>,
+        /// Auto-splitter load failure at startup, shown once the window is up.
+        pub auto_splitter_error: RefCell<Option<AutoSplitterError>>,
+        pub config_saver: Debouncer,
+        /// The attempt waits paused at its offset for the first input, see `prime`.
+        pub primed: Cell<bool>,
     }
 
     impl Default for TuxSplitContext {
@@ -51,6 +68,10 @@ mod imp {
                 timer: RefCell::new(shared),
                 runtime: RefCell::new(runtime),
                 config: RefCell::new(config),
+                toast_overlay: RefCell::new(None),
+                auto_splitter_error: RefCell::new(None),
+                config_saver: Debouncer::new(CONFIG_SAVE_DELAY),
+                primed: Cell::new(false),
             }
         }
     }
@@ -71,6 +92,9 @@ mod imp {
                     // (structure, times, metadata). Listeners should refresh
                     // any cached segment representations.
                     Signal::builder("run-changed").action().build(),
+                    // Emitted when the timer's current comparison changes, whatever
+                    // triggered it (hotkeys, preferences, editor).
+                    Signal::builder("comparison-changed").action().build(),
                 ]
             })
         }
@@ -95,7 +119,8 @@ impl TuxSplitContext {
         let runtime = Runtime::new(shared_timer.clone());
 
         config.configure_timer(&mut shared_timer.write().unwrap());
-        config.maybe_load_auto_splitter(&runtime);
+        // The app starts without the script, the error is shown once the window is up
+        let auto_splitter_error = config.maybe_load_auto_splitter(&runtime).err();
 
         let Some(()) = config.create_hotkey_system(shared_timer.clone()) else {
             panic!("Could not load HotkeySystem");
@@ -107,6 +132,7 @@ impl TuxSplitContext {
             imp.timer.replace(shared_timer);
             imp.runtime.replace(runtime);
             imp.config.replace(config);
+            imp.auto_splitter_error.replace(auto_splitter_error);
         }
 
         obj
@@ -143,22 +169,253 @@ impl TuxSplitContext {
         self.imp().runtime.borrow()
     }
 
+    /// Loads the auto-splitter script of the config again, after it was changed.
+    pub fn reload_auto_splitter(&self) -> Result<(), AutoSplitterError> {
+        self.config().maybe_load_auto_splitter(&self.runtime())
+    }
+
+    /// Shows a short notification over the timer window. Does nothing before the
+    /// window is built.
+    pub fn show_toast(&self, title: &str) {
+        if let Some(overlay) = self.imp().toast_overlay.borrow().as_ref() {
+            overlay.add_toast(Toast::builder().title(title).timeout(3).build());
+        }
+    }
+
     pub fn emit_run_changed(&self) {
         self.emit_by_name::<()>("run-changed", &[]);
     }
 
+    pub fn emit_comparison_changed(&self) {
+        self.emit_by_name::<()>("comparison-changed", &[]);
+    }
+
+    /// Sets the timer's current comparison and remembers it in the config so
+    /// that re-applying the config (e.g. on `set_run`) keeps it.
+    pub fn set_current_comparison(&self, comparison: &str) {
+        {
+            let timer_arc = self.timer();
+            let mut timer = timer_arc.write().unwrap();
+            if timer.current_comparison() == comparison {
+                return;
+            }
+            if timer.set_current_comparison(comparison).is_err() {
+                return;
+            }
+        }
+        if let Ok(mut cfg) = self.config_mut() {
+            cfg.general.comparison = Some(comparison.to_owned());
+        }
+        self.emit_comparison_changed();
+    }
+
+    /// Switches to the previous or next comparison of the run, wrapping around at
+    /// either end, and remembers the choice in the config.
+    pub fn cycle_comparison(&self, step: ComparisonStep) {
+        let comparison = {
+            let timer_arc = self.timer();
+            let mut timer = timer_arc.write().unwrap();
+            match step {
+                ComparisonStep::Previous => timer.switch_to_previous_comparison(),
+                ComparisonStep::Next => timer.switch_to_next_comparison(),
+            }
+            timer.current_comparison().to_owned()
+        };
+        self.remember_comparison(&comparison);
+        self.emit_comparison_changed();
+    }
+
+    /// Stores the comparison in the config so it is applied again on the next launch
+    /// and whenever the config is re-applied to the timer.
+    pub fn remember_comparison(&self, comparison: &str) {
+        if let Ok(mut cfg) = self.config_mut()
+            && cfg.general.comparison.as_deref() != Some(comparison)
+        {
+            cfg.general.comparison = Some(comparison.to_owned());
+        }
+    }
+
     /// Replace the run (full set_run) and emit run-changed. Re-configures
     /// timer based on current config (useful if comparisons / settings depend
     /// on run contents).
-    pub fn set_run(&self, new_run: Run) {
+    /// Returns false when the timer rejects the run (e.g. it has no segments), which
+    /// keeps the current run.
+    pub fn set_run(&self, mut new_run: Run) -> bool {
+        ensure_generated_comparisons(&mut new_run);
         let timer_arc = self.timer();
-        {
+        let accepted = {
             let mut timer = timer_arc.write().unwrap();
-            let _ = timer.set_run(new_run);
+            let accepted = timer.set_run(new_run).is_ok();
             // Re-apply config in case it needs to reinitialize aspects of the timer.
             self.config().configure_timer(&mut timer);
-        }
+            accepted
+        };
         self.emit_run_changed();
+        accepted
+    }
+
+    /// Starts the run, or splits if it is already running. With `general.start_paused`
+    /// starting primes the timer, and the next split resumes it.
+    pub fn split_or_start(&self) {
+        let start_paused = self.config().general.start_paused;
+        let timer = self.timer();
+        let mut timer = timer.write().unwrap();
+        match timer.current_phase() {
+            TimerPhase::NotRunning if start_paused => {
+                drop(timer);
+                self.prime();
+            }
+            TimerPhase::Paused if self.is_primed() => {
+                timer.resume();
+            }
+            _ => {
+                timer.split_or_start();
+            }
+        }
+    }
+
+    /// Starts the run, primed with `general.start_paused`.
+    pub fn start(&self) {
+        if self.config().general.start_paused {
+            self.prime();
+        } else {
+            self.timer().write().unwrap().start();
+        }
+    }
+
+    /// Starts the run and pauses it right away, so it shows its offset and only counts
+    /// from the next split (or pause) on. The paused time is left out like any other
+    /// pause, so a negative offset still counts down in full once resumed.
+    pub fn prime(&self) {
+        {
+            let timer = self.timer();
+            let mut timer = timer.write().unwrap();
+            if timer.current_phase() != TimerPhase::NotRunning {
+                return;
+            }
+            timer.start();
+            timer.pause();
+        }
+        self.set_primed(true);
+    }
+
+    /// Whether the timer waits paused at its offset for the first input.
+    pub fn is_primed(&self) -> bool {
+        self.imp().primed.get()
+    }
+
+    fn set_primed(&self, primed: bool) {
+        if self.imp().primed.replace(primed) == primed {
+            return;
+        }
+        // The global split key can't split a paused timer, it resumes it meanwhile
+        if let Ok(mut cfg) = self.config_mut() {
+            cfg.set_split_resumes(primed);
+        }
+    }
+
+    /// Resets the current attempt. `update_splits` decides whether improvements
+    /// (golds, PBs) are written back into the run.
+    pub fn reset(&self, update_splits: bool) {
+        self.timer().write().unwrap().reset(update_splits);
+    }
+
+    /// Applies new global hotkeys right away and saves them to the config file.
+    pub fn set_hotkeys(&self, hotkeys: HotkeyConfig) -> Result<(), String> {
+        let discard_reset = self.config().general.discard_reset_hotkey;
+        self.rebind_hotkeys(hotkeys, discard_reset)
+    }
+
+    /// Binds the key resetting without saving golds or PB, registers it globally and
+    /// saves it to the config file.
+    pub fn set_discard_reset_hotkey(&self, hotkey: Option<Hotkey>) -> Result<(), String> {
+        let hotkeys = self.config().hotkeys;
+        self.rebind_hotkeys(hotkeys, hotkey)
+    }
+
+    fn rebind_hotkeys(
+        &self,
+        hotkeys: HotkeyConfig,
+        discard_reset: Option<Hotkey>,
+    ) -> Result<(), String> {
+        let timer = self.timer();
+        {
+            let mut cfg = self
+                .config_mut()
+                .map_err(|_| "the config is in use".to_owned())?;
+            cfg.rebind_hotkeys(hotkeys, discard_reset, timer)
+                .ok_or_else(|| "the keys could not be registered".to_owned())?;
+            // The new hotkey system splits, a primed timer still has to be resumed
+            if self.is_primed() {
+                cfg.set_split_resumes(true);
+            }
+        }
+        self.schedule_config_save();
+        Ok(())
+    }
+
+    /// Writes the config to the user's config file shortly after the last change, so
+    /// a burst of changes (e.g. dragging a slider) is written once.
+    pub fn schedule_config_save(&self) {
+        self.imp()
+            .config_saver
+            .schedule(|| TuxSplitContext::get_instance().save_config());
+    }
+
+    /// Writes the config to the user's config file.
+    pub fn save_config(&self) {
+        let path = get_config_path().join("config.yaml");
+        if let Err(err) = self.config().save(&path) {
+            warn!("Could not save config to {}: {err}", path.display());
+        }
+    }
+
+    /// Pauses a running attempt after the machine was asleep for `gap`, if enabled.
+    pub fn pause_after_suspend(&self, gap: Duration) {
+        if !self.config().general.pause_on_suspend {
+            return;
+        }
+        {
+            let timer = self.timer();
+            let mut timer = timer.write().unwrap();
+            if timer.current_phase() != TimerPhase::Running {
+                return;
+            }
+            timer.pause();
+        }
+        warn!("Paused the timer after a {}s suspend", gap.as_secs());
+        self.show_toast(&format!(
+            "Paused: the system was asleep for {}s",
+            gap.as_secs()
+        ));
+    }
+
+    /// Tells the integrations that the timer left the `previous` phase, and keeps
+    /// track of the primed timer.
+    pub fn phase_changed(&self, previous: TimerPhase, timer: &Timer) {
+        let phase = timer.current_phase();
+        let start_paused = self.config().general.start_paused;
+        if start_paused && previous == TimerPhase::NotRunning && phase == TimerPhase::Running {
+            // Started by a global hotkey or the auto splitter, which don't go through
+            // `split_or_start`: pause it as soon as the refresh loop notices. To the
+            // integrations the run only starts once resumed.
+            self.timer().write().unwrap().pause();
+            self.set_primed(true);
+            return;
+        }
+        let Some(previous) = self.observed_phase(previous, phase) else {
+            return;
+        };
+        if phase != TimerPhase::Paused {
+            self.set_primed(false);
+        }
+        crate::integrations::on_phase_transition(&self.config(), previous, timer);
+    }
+
+    /// The phase the integrations and sounds see the timer leave for `phase`, `None`
+    /// when they see no change (see `integration_phase`).
+    pub fn observed_phase(&self, previous: TimerPhase, phase: TimerPhase) -> Option<TimerPhase> {
+        integration_phase(previous, phase, self.is_primed())
     }
 
     pub fn disable_hotkeys(&self) {
@@ -180,16 +437,232 @@ pub fn build_ui(app: &Application) {
         .title("TuxSplit")
         .build();
 
+    {
+        let ctx = TuxSplitContext::get_instance();
+        let cfg = ctx.config();
+        apply_text_scales(
+            cfg.style.timer_scale.unwrap_or(1.0),
+            cfg.style.font_scale.unwrap_or(1.0),
+        );
+        apply_delta_colors(&cfg.style.colors);
+        crate::server::install(&cfg);
+        apply_window_appearance(
+            &window,
+            cfg.window.opacity,
+            cfg.window.transparent_background,
+        );
+    }
+
     let toolbar_view = ToolbarView::new();
     let header = TuxSplitHeader::new(&window);
     toolbar_view.add_top_bar(header.header());
 
+    let auto_hide = TuxSplitContext::get_instance()
+        .config()
+        .window
+        .auto_hide_header;
+    toolbar_view.set_reveal_top_bars(header_revealed(auto_hide, false));
+
+    let motion = EventControllerMotion::new();
+    let toolbar_binding = toolbar_view.downgrade();
+    let update_reveal = move |hovered: bool| {
+        if let Some(toolbar) = toolbar_binding.upgrade() {
+            let auto_hide = TuxSplitContext::get_instance()
+                .config()
+                .window
+                .auto_hide_header;
+            toolbar.set_reveal_top_bars(header_revealed(auto_hide, hovered));
+        }
+    };
+    let on_enter = update_reveal.clone();
+    motion.connect_enter(move |_, _, _| on_enter(true));
+    motion.connect_leave(move |_| update_reveal(false));
+    toolbar_view.add_controller(motion);
+
     let mut timer_widget = TuxSplitTimer::new();
     timer_widget.start_refresh_loop();
-    toolbar_view.set_content(Some(timer_widget.clamped()));
+    let timer_overlay = gtk4::Overlay::new();
+    timer_overlay.set_child(Some(timer_widget.clamped()));
+    toolbar_view.set_content(Some(&timer_overlay));
+
+    let click = GestureClick::new();
+    click.connect_pressed(|gesture, _, _, _| {
+        let ctx = TuxSplitContext::get_instance();
+        let enabled = ctx.config().general.click_to_split;
+        match click_action(enabled, gesture.current_event_state()) {
+            Some(ClickAction::SplitOrStart) => ctx.split_or_start(),
+            Some(ClickAction::Reset) => ctx.reset(true),
+            None => {}
+        }
+    });
+    timer_widget.clamped().add_controller(click);
 
-    window.set_content(Some(&toolbar_view));
+    let toast_overlay = ToastOverlay::new();
+    toast_overlay.set_child(Some(&toolbar_view));
+    TuxSplitContext::get_instance()
+        .imp()
+        .toast_overlay
+        .replace(Some(toast_overlay.clone()));
+
+    window.set_content(Some(&toast_overlay));
+    install_reset_keys(&window);
+    install_cheat_sheet(&window, &timer_overlay);
+    install_pause_on_unfocus(&window);
     window.present();
+
+    let auto_splitter_error = TuxSplitContext::get_instance()
+        .imp()
+        .auto_splitter_error
+        .take();
+    if let Some(err) = auto_splitter_error {
+        present_auto_splitter_error(&window, &err);
+    }
+
+    let startup = {
+        let ctx = TuxSplitContext::get_instance();
+        let cfg = ctx.config();
+        startup_view(cfg.window.restore_editor, cfg.window.editor_open)
+    };
+    if startup == StartupView::Editor {
+        SplitEditor::new().present();
+    }
+}
+
+/// Tells which auto-splitter script failed to load, and why.
+pub fn present_auto_splitter_error(parent: &impl IsA<gtk4::Widget>, err: &AutoSplitterError) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Auto Splitter Not Loaded")
+        .body(err.to_string())
+        .default_response("close")
+        .close_response("close")
+        .build();
+    dialog.add_response("close", "Close");
+    dialog.present(Some(parent));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonStep {
+    Previous,
+    Next,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StartupView {
+    Timer,
+    Editor,
+}
+
+/// The timer always opens; the editor joins it when restoring is enabled and it was
+/// left open last time.
+fn startup_view(restore_editor: bool, editor_open: bool) -> StartupView {
+    if restore_editor && editor_open {
+        StartupView::Editor
+    } else {
+        StartupView::Timer
+    }
+}
+
+/// Whether the header bar should be shown. With auto-hide it only shows while hovered.
+fn header_revealed(auto_hide: bool, hovered: bool) -> bool {
+    !auto_hide || hovered
+}
+
+/// The phase the integrations see the timer leave for `phase`, `None` when they see no
+/// change: a primed timer hasn't started yet, so pausing it (a tick after a hotkey
+/// started it) isn't a pause, resuming it starts the run and resetting it doesn't reset
+/// one.
+fn integration_phase(previous: TimerPhase, phase: TimerPhase, primed: bool) -> Option<TimerPhase> {
+    if !primed {
+        return Some(previous);
+    }
+    match (previous, phase) {
+        (_, TimerPhase::Paused) => None,
+        (TimerPhase::Paused, _) => Some(TimerPhase::NotRunning),
+        _ => Some(previous),
+    }
+}
+
+/// Refresh ticks further apart than this mean the machine was suspended in between.
+pub const SUSPEND_GAP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// The time between two refresh ticks, if it is long enough to mean a suspend. Uses the
+/// wall clock because the monotonic clock stops while the machine sleeps.
+pub fn suspend_gap(last_tick: SystemTime, now: SystemTime) -> Option<Duration> {
+    // A clock set backwards is not a suspend
+    let gap = now.duration_since(last_tick).ok()?;
+    (gap >= SUSPEND_GAP_THRESHOLD).then_some(gap)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FocusPauseAction {
+    Pause,
+    Resume,
+}
+
+/// Decides what a focus change does to the timer with pause-on-unfocus. Only attempts
+/// paused by losing focus are resumed, so a pause from the hotkey is left alone.
+fn focus_pause_action(
+    enabled: bool,
+    focused: bool,
+    phase: TimerPhase,
+    paused_by_focus: bool,
+) -> Option<FocusPauseAction> {
+    if !enabled {
+        return None;
+    }
+    match (focused, phase) {
+        (false, TimerPhase::Running) => Some(FocusPauseAction::Pause),
+        (true, TimerPhase::Paused) if paused_by_focus => Some(FocusPauseAction::Resume),
+        _ => None,
+    }
+}
+
+fn install_pause_on_unfocus(window: &ApplicationWindow) {
+    let paused_by_focus = Cell::new(false);
+    window.connect_is_active_notify(move |window| {
+        let ctx = TuxSplitContext::get_instance();
+        let enabled = ctx.config().general.pause_on_unfocus;
+        let timer = ctx.timer();
+        let mut timer = timer.write().unwrap();
+        let focused = window.is_active();
+        match focus_pause_action(
+            enabled,
+            focused,
+            timer.current_phase(),
+            paused_by_focus.get(),
+        ) {
+            Some(FocusPauseAction::Pause) => {
+                timer.pause();
+                paused_by_focus.set(true);
+            }
+            Some(FocusPauseAction::Resume) => {
+                timer.resume();
+            }
+            None => {}
+        }
+        if focused {
+            paused_by_focus.set(false);
+        }
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClickAction {
+    SplitOrStart,
+    Reset,
+}
+
+/// Maps a click on the timer to a timer action when click-to-split is enabled.
+/// A plain click splits (or starts), Ctrl/Shift + click resets.
+fn click_action(enabled: bool, modifiers: gdk::ModifierType) -> Option<ClickAction> {
+    if !enabled {
+        return None;
+    }
+    if modifiers.intersects(gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::SHIFT_MASK) {
+        Some(ClickAction::Reset)
+    } else {
+        Some(ClickAction::SplitOrStart)
+    }
 }
 
 pub fn shutdown() {
@@ -200,7 +673,7 @@ pub fn shutdown() {
         .expect("Failed to save config on shutdown");
 }
 
-fn load_config() -> Config {
+pub fn load_config() -> Config {
     let user_cfg = get_config_path().join("config.yaml");
     if user_cfg.is_file()
         && let Some(cfg) = Config::parse(&user_cfg)
@@ -235,3 +708,304 @@ fn register_gresource(resource_path: &Path) {
         debug!("Registered GResource from {}", resource_path.display());
     }
 }
+
+#[cfg(test)]
+mod click_action_tests {
+    use super::*;
+
+    #[test]
+    fn disabled_click_does_nothing() {
+        assert_eq!(click_action(false, gdk::ModifierType::empty()), None);
+        assert_eq!(click_action(false, gdk::ModifierType::CONTROL_MASK), None);
+    }
+
+    #[test]
+    fn plain_click_splits() {
+        assert_eq!(
+            click_action(true, gdk::ModifierType::empty()),
+            Some(ClickAction::SplitOrStart)
+        );
+        // Unrelated modifiers (e.g. the button mask itself) do not turn it into a reset
+        assert_eq!(
+            click_action(true, gdk::ModifierType::BUTTON1_MASK),
+            Some(ClickAction::SplitOrStart)
+        );
+    }
+
+    #[test]
+    fn modifier_click_resets() {
+        assert_eq!(
+            click_action(true, gdk::ModifierType::CONTROL_MASK),
+            Some(ClickAction::Reset)
+        );
+        assert_eq!(
+            click_action(true, gdk::ModifierType::SHIFT_MASK),
+            Some(ClickAction::Reset)
+        );
+    }
+}
+
+#[cfg(test)]
+mod suspend_gap_tests {
+    use super::*;
+
+    #[test]
+    fn regular_ticks_are_no_suspend() {
+        let last = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        assert_eq!(suspend_gap(last, last + Duration::from_millis(16)), None);
+        assert_eq!(
+            suspend_gap(
+                last,
+                last + SUSPEND_GAP_THRESHOLD - Duration::from_millis(1)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn gaps_from_the_threshold_on_are_reported() {
+        let last = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        assert_eq!(
+            suspend_gap(last, last + SUSPEND_GAP_THRESHOLD),
+            Some(SUSPEND_GAP_THRESHOLD)
+        );
+        assert_eq!(
+            suspend_gap(last, last + Duration::from_secs(3600)),
+            Some(Duration::from_secs(3600))
+        );
+    }
+
+    #[test]
+    fn clock_going_backwards_is_ignored() {
+        let last = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        assert_eq!(suspend_gap(last, last - Duration::from_secs(60)), None);
+    }
+}
+
+#[cfg(test)]
+mod focus_pause_tests {
+    use super::*;
+
+    #[test]
+    fn disabled_never_touches_the_timer() {
+        assert_eq!(
+            focus_pause_action(false, false, TimerPhase::Running, false),
+            None
+        );
+        assert_eq!(
+            focus_pause_action(false, true, TimerPhase::Paused, true),
+            None
+        );
+    }
+
+    #[test]
+    fn losing_focus_pauses_only_a_running_attempt() {
+        assert_eq!(
+            focus_pause_action(true, false, TimerPhase::Running, false),
+            Some(FocusPauseAction::Pause)
+        );
+        for phase in [
+            TimerPhase::NotRunning,
+            TimerPhase::Paused,
+            TimerPhase::Ended,
+        ] {
+            assert_eq!(focus_pause_action(true, false, phase, false), None);
+        }
+    }
+
+    #[test]
+    fn regaining_focus_resumes_only_its_own_pause() {
+        assert_eq!(
+            focus_pause_action(true, true, TimerPhase::Paused, true),
+            Some(FocusPauseAction::Resume)
+        );
+        // Paused with the hotkey
+        assert_eq!(
+            focus_pause_action(true, true, TimerPhase::Paused, false),
+            None
+        );
+        // Resumed with the hotkey while unfocused
+        assert_eq!(
+            focus_pause_action(true, true, TimerPhase::Running, true),
+            None
+        );
+        assert_eq!(
+            focus_pause_action(true, true, TimerPhase::Ended, true),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod header_reveal_tests {
+    use super::header_revealed;
+
+    #[test]
+    fn header_always_shown_without_auto_hide() {
+        assert!(header_revealed(false, false));
+        assert!(header_revealed(false, true));
+    }
+
+    #[test]
+    fn auto_hide_reveals_on_hover_only() {
+        assert!(!header_revealed(true, false));
+        assert!(header_revealed(true, true));
+        // Leaving again hides it
+        assert!(!header_revealed(true, false));
+    }
+}
+
+#[cfg(test)]
+mod startup_view_tests {
+    use super::*;
+
+    #[test]
+    fn editor_reopens_only_when_enabled_and_left_open() {
+        assert_eq!(startup_view(false, false), StartupView::Timer);
+        assert_eq!(startup_view(false, true), StartupView::Timer);
+        assert_eq!(startup_view(true, false), StartupView::Timer);
+        assert_eq!(startup_view(true, true), StartupView::Editor);
+    }
+
+    #[test]
+    fn editor_open_flag_round_trips() {
+        let mut config = Config::default();
+        config.window.restore_editor = true;
+        config.window.editor_open = true;
+
+        let yaml = serde_yaml::to_string(&config).expect("serialize");
+        let parsed: Config = serde_yaml::from_str(&yaml).expect("deserialize");
+        assert!(parsed.window.editor_open);
+        assert_eq!(
+            startup_view(parsed.window.restore_editor, parsed.window.editor_open),
+            StartupView::Editor
+        );
+
+        // Older configs without the fields start on the timer
+        let parsed: Config =
+            serde_yaml::from_str("window:\n  always-on-top: false\n").expect("deserialize");
+        assert_eq!(
+            startup_view(parsed.window.restore_editor, parsed.window.editor_open),
+            StartupView::Timer
+        );
+    }
+}
+
+#[cfg(test)]
+mod primed_tests {
+    use super::*;
+    use livesplit_core::Segment;
+
+    #[test]
+    fn integrations_see_a_primed_run_start_when_resumed() {
+        assert_eq!(
+            integration_phase(TimerPhase::Paused, TimerPhase::Running, true),
+            Some(TimerPhase::NotRunning)
+        );
+        assert_eq!(
+            integration_phase(TimerPhase::Paused, TimerPhase::Running, false),
+            Some(TimerPhase::Paused)
+        );
+        // Priming a run a hotkey started pauses it a tick later, that isn't a pause
+        assert_eq!(
+            integration_phase(TimerPhase::Running, TimerPhase::Paused, true),
+            None
+        );
+        assert_eq!(
+            integration_phase(TimerPhase::Running, TimerPhase::Paused, false),
+            Some(TimerPhase::Running)
+        );
+    }
+
+    #[test]
+    fn hotkey_starts_are_primed_without_a_pause() {
+        let ctx = TuxSplitContext::get_instance();
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        ctx.set_run(run);
+        ctx.config_mut().unwrap().general.start_paused = true;
+
+        // A global hotkey starts the timer behind the context's back
+        ctx.timer().write().unwrap().start();
+        let timer = ctx.timer().read().unwrap().clone();
+        ctx.phase_changed(TimerPhase::NotRunning, &timer);
+        assert!(ctx.is_primed());
+
+        // The refresh loop then sees the timer go from running to paused
+        let timer = ctx.timer().read().unwrap().clone();
+        assert_eq!(timer.current_phase(), TimerPhase::Paused);
+        assert_eq!(
+            ctx.observed_phase(TimerPhase::Running, TimerPhase::Paused),
+            None
+        );
+        ctx.phase_changed(TimerPhase::Running, &timer);
+        assert!(ctx.is_primed());
+
+        ctx.config_mut().unwrap().general.start_paused = false;
+        ctx.reset(false);
+        let timer = ctx.timer().read().unwrap().clone();
+        ctx.phase_changed(TimerPhase::Paused, &timer);
+        assert!(!ctx.is_primed());
+    }
+
+    #[test]
+    fn split_resumes_a_primed_timer() {
+        let ctx = TuxSplitContext::get_instance();
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        run.push_segment(Segment::new("B"));
+        ctx.set_run(run);
+        let phase = || ctx.timer().read().unwrap().current_phase();
+
+        ctx.prime();
+        assert!(ctx.is_primed());
+        assert_eq!(phase(), TimerPhase::Paused);
+
+        ctx.split_or_start();
+        assert_eq!(phase(), TimerPhase::Running);
+        assert_eq!(ctx.timer().read().unwrap().current_split_index(), Some(0));
+
+        let timer = ctx.timer().read().unwrap().clone();
+        ctx.phase_changed(TimerPhase::Paused, &timer);
+        assert!(!ctx.is_primed());
+        ctx.reset(false);
+    }
+}
+
+#[cfg(test)]
+mod comparison_cycle_tests {
+    use super::*;
+    use livesplit_core::Segment;
+
+    #[test]
+    fn cycling_wraps_around_and_is_remembered() {
+        let ctx = TuxSplitContext::get_instance();
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        ctx.set_run(run);
+        ctx.set_current_comparison("Personal Best");
+
+        let comparisons: Vec<String> = ctx.get_run().comparisons().map(str::to_owned).collect();
+        assert!(comparisons.len() > 1);
+        let current = || ctx.timer().read().unwrap().current_comparison().to_owned();
+
+        // Personal Best is the first comparison, so going back wraps to the last
+        assert_eq!(comparisons[0], "Personal Best");
+        ctx.cycle_comparison(ComparisonStep::Previous);
+        assert_eq!(current(), *comparisons.last().unwrap());
+        assert_eq!(
+            ctx.config().general.comparison.as_deref(),
+            comparisons.last().map(String::as_str)
+        );
+
+        // And forward again wraps to the first
+        ctx.cycle_comparison(ComparisonStep::Next);
+        assert_eq!(current(), "Personal Best");
+        ctx.cycle_comparison(ComparisonStep::Next);
+        assert_eq!(current(), comparisons[1]);
+        assert_eq!(
+            ctx.config().general.comparison.as_deref(),
+            Some(comparisons[1].as_str())
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! The global key resetting an attempt without saving it. The hotkey system only knows
+//! the saving reset, so this key is registered with a hook of its own.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use livesplit_core::SharedTimer;
+use livesplit_core::hotkey::{Hook, Hotkey};
+
+/// The discarding reset key, resetting without saving the attempt's golds and PB.
+pub struct DiscardResetKey {
+    // Unregisters the key when dropped
+    _hook: Hook,
+    active: Arc<AtomicBool>,
+}
+
+impl DiscardResetKey {
+    pub fn new(hotkey: Hotkey, timer: SharedTimer) -> Option<Self> {
+        let hook = Hook::new().ok()?;
+        let active = Arc::new(AtomicBool::new(true));
+
+        let active_binding = active.clone();
+        hook.register(hotkey, move || {
+            if active_binding.load(Ordering::Acquire) {
+                timer.write().unwrap().reset(false);
+            }
+        })
+        .ok()?;
+
+        Some(Self {
+            _hook: hook,
+            active,
+        })
+    }
+
+    pub fn activate(&self) {
+        self.active.store(true, Ordering::Release);
+    }
+
+    pub fn deactivate(&self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
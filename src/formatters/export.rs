@@ -0,0 +1,133 @@
+use livesplit_core::{Run, Time, TimingMethod};
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON export schema, bumped whenever a field changes meaning or goes away.
+pub const RUN_EXPORT_VERSION: u32 = 1;
+
+/// A run in the JSON export schema.
+///
+/// All times are in seconds and `null` when not recorded. Only the data below is
+/// exported, the attempt history and comparisons other than the Personal Best are left out.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunExport {
+    /// Schema version, see `RUN_EXPORT_VERSION`.
+    pub version: u32,
+    pub game: String,
+    pub category: String,
+    pub attempt_count: u32,
+    pub segments: Vec<SegmentExport>,
+}
+
+/// A segment of an exported run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SegmentExport {
+    pub name: String,
+    /// Split time of the Personal Best at the end of this segment.
+    pub personal_best_split: TimesExport,
+    /// Best time ever achieved for this segment alone (the gold).
+    pub best_segment: TimesExport,
+}
+
+/// A time for both timing methods.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TimesExport {
+    pub real_time: Option<f64>,
+    pub game_time: Option<f64>,
+}
+
+impl From<Time> for TimesExport {
+    fn from(time: Time) -> Self {
+        Self {
+            real_time: time[TimingMethod::RealTime].map(|t| t.total_seconds()),
+            game_time: time[TimingMethod::GameTime].map(|t| t.total_seconds()),
+        }
+    }
+}
+
+impl RunExport {
+    pub fn from_run(run: &Run) -> Self {
+        Self {
+            version: RUN_EXPORT_VERSION,
+            game: run.game_name().to_owned(),
+            category: run.category_name().to_owned(),
+            attempt_count: run.attempt_count(),
+            segments: run
+                .segments()
+                .iter()
+                .map(|segment| SegmentExport {
+                    name: segment.name().to_owned(),
+                    personal_best_split: segment.personal_best_split_time().into(),
+                    best_segment: segment.best_segment_time().into(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod run_export_tests {
+    use super::*;
+
+    fn two_segment_run() -> Run {
+        let mut run = Run::new();
+        run.set_game_name("Celeste");
+        run.set_category_name("Any%");
+        run.set_attempt_count(12);
+
+        let mut first = Segment::new("Forsaken City");
+        first.set_personal_best_split_time(
+            Time::new()
+                .with_real_time(Some(TimeSpan::from_seconds(90.5)))
+                .with_game_time(Some(TimeSpan::from_seconds(88.0))),
+        );
+        first
+            .set_best_segment_time(Time::new().with_real_time(Some(TimeSpan::from_seconds(85.25))));
+        run.push_segment(first);
+        run.push_segment(Segment::new("Old Site"));
+        run
+    }
+
+    #[test]
+    fn two_segment_run_json() {
+        let json = RunExport::from_run(&two_segment_run())
+            .to_json()
+            .expect("serialize");
+        assert_eq!(
+            json,
+            r#"{
+  "version": 1,
+  "game": "Celeste",
+  "category": "Any%",
+  "attempt_count": 12,
+  "segments": [
+    {
+      "name": "Forsaken City",
+      "personal_best_split": {
+        "real_time": 90.5,
+        "game_time": 88.0
+      },
+      "best_segment": {
+        "real_time": 85.25,
+        "game_time": null
+      }
+    },
+    {
+      "name": "Old Site",
+      "personal_best_split": {
+        "real_time": null,
+        "game_time": null
+      },
+      "best_segment": {
+        "real_time": null,
+        "game_time": null
+      }
+    }
+  ]
+}"#
+        );
+    }
+}
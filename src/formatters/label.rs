@@ -6,7 +6,7 @@ fn get_formatter() -> HashMap<&'static str, &'static str> {
         ("Balanced PB", "Balanced"),
         ("Best Segments", "SOB"),
         ("Best Split Times", "Best Split"),
-        ("Average Segments", "Avg"),
+        ("Average Segments", "Average"),
         ("Median Segments", "Median"),
         ("Worst Segments", "Worst Split"),
         ("Latest Run", "Latest"),
@@ -16,3 +16,21 @@ fn get_formatter() -> HashMap<&'static str, &'static str> {
 pub fn format_label(input: &str) -> &str {
     get_formatter().get(input).copied().unwrap_or(input)
 }
+
+#[cfg(test)]
+mod label_tests {
+    use super::format_label;
+
+    #[test]
+    fn known_comparisons_are_shortened() {
+        assert_eq!(format_label("Personal Best"), "PB");
+        assert_eq!(format_label("Best Segments"), "SOB");
+        assert_eq!(format_label("Average Segments"), "Average");
+        assert_eq!(format_label("Balanced PB"), "Balanced");
+    }
+
+    #[test]
+    fn custom_comparisons_keep_their_name() {
+        assert_eq!(format_label("World Record"), "World Record");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod export;
 pub mod label;
 pub mod time;
 pub use time::*;
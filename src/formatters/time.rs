@@ -8,12 +8,22 @@ use time::Duration as TimeDuration;
 #[serde(default)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct TimeFormat {
+    pub show_days: bool,
     pub show_hours: bool,
+    /// Print the hours even when they are zero ("0:01:23.45").
+    pub always_show_hours: bool,
     pub show_minutes: bool,
     pub show_seconds: bool,
     pub show_decimals: bool,
-    pub decimal_places: u8,
+    /// Digits after the separator. Left unset, picking a preset also picks its count.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimal_places: Option<u8>,
     pub dynamic: bool,
+    pub decimal_separator: char,
+    pub always_signed: bool,
+    /// Let the largest shown unit absorb the hidden ones ("65:00" rather than "5:00").
+    /// Set by the presets that hide hours; older formats keep wrapping.
+    pub absorb_hidden_units: bool,
     cached_pattern: Option<String>,
 }
 
@@ -21,22 +31,52 @@ impl Default for TimeFormat {
     fn default() -> Self {
         // Default mirrors "h:m:s.dd"
         Self {
+            show_days: false,
             show_hours: true,
+            always_show_hours: false,
             show_minutes: true,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 2,
+            decimal_places: None,
             dynamic: false,
+            decimal_separator: '.',
+            always_signed: false,
+            absorb_hidden_units: false,
             cached_pattern: None,
         }
     }
 }
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(clippy::enum_variant_names)]
 pub enum TimeFormatPreset {
     ShowDecimals,
     SmartDecimals,
     NoDecimals,
+    MinutesSeconds,
+    SecondsMillis,
+    HoursAlways,
+}
+
+impl TimeFormatPreset {
+    pub const ALL: [Self; 6] = [
+        Self::ShowDecimals,
+        Self::SmartDecimals,
+        Self::NoDecimals,
+        Self::MinutesSeconds,
+        Self::SecondsMillis,
+        Self::HoursAlways,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ShowDecimals => "Show decimals",
+            Self::SmartDecimals => "Smart decimals",
+            Self::NoDecimals => "No decimals",
+            Self::MinutesSeconds => "Minutes and seconds only",
+            Self::SecondsMillis => "Seconds only with milliseconds",
+            Self::HoursAlways => "Hours always shown",
+        }
+    }
 }
 
 impl TimeFormat {
@@ -50,12 +90,17 @@ impl TimeFormat {
         dynamic: bool,
     ) -> Self {
         Self {
+            show_days: false,
             show_hours,
+            always_show_hours: false,
             show_minutes,
             show_seconds,
             show_decimals,
-            decimal_places: decimal_places.clamp(1, 3),
+            decimal_places: Some(decimal_places.clamp(1, 3)),
             dynamic,
+            decimal_separator: '.',
+            always_signed: false,
+            absorb_hidden_units: false,
             cached_pattern: None,
         }
     }
@@ -64,19 +109,83 @@ impl TimeFormat {
     /// `ShowDecimals`: fixed H:M:S with decimals.
     /// `SmartDecimals`: dynamic format that hides decimals over a minute/hour.
     /// `NoDecimals`: fixed H:M:S without decimals.
+    /// `MinutesSeconds`: M:S without decimals, minutes keep counting past an hour.
+    /// `SecondsMillis`: total seconds with three decimals.
+    /// `HoursAlways`: H:M:S with decimals, printing zero hours too.
     pub fn from_preset(preset: TimeFormatPreset) -> Self {
         match preset {
             TimeFormatPreset::ShowDecimals => Self::new(true, true, true, true, 2, false),
             TimeFormatPreset::SmartDecimals => Self::new(true, true, true, true, 2, true),
             TimeFormatPreset::NoDecimals => Self::new(true, true, true, false, 2, false),
+            TimeFormatPreset::MinutesSeconds => Self {
+                absorb_hidden_units: true,
+                ..Self::new(false, true, true, false, 2, false)
+            },
+            TimeFormatPreset::SecondsMillis => Self {
+                absorb_hidden_units: true,
+                ..Self::new(false, false, true, true, 3, false)
+            },
+            TimeFormatPreset::HoursAlways => Self {
+                always_show_hours: true,
+                ..Self::new(true, true, true, true, 2, false)
+            },
         }
     }
 
+    /// Switches the visible components and the decimal count to those of `preset`,
+    /// keeping the remaining settings (days, separator, sign).
+    pub fn apply_preset(&mut self, preset: TimeFormatPreset) {
+        let p = Self::from_preset(preset);
+        self.show_hours = p.show_hours;
+        self.always_show_hours = p.always_show_hours;
+        self.show_minutes = p.show_minutes;
+        self.show_seconds = p.show_seconds;
+        self.show_decimals = p.show_decimals;
+        self.dynamic = p.dynamic;
+        self.absorb_hidden_units = p.absorb_hidden_units;
+        self.decimal_places = p.decimal_places;
+        self.cached_pattern = None;
+    }
+
+    /// The preset whose components match this format, ignoring the decimal count.
+    pub fn preset(&self) -> Option<TimeFormatPreset> {
+        TimeFormatPreset::ALL.into_iter().find(|&preset| {
+            let p = Self::from_preset(preset);
+            p.show_hours == self.show_hours
+                && p.always_show_hours == self.always_show_hours
+                && p.show_minutes == self.show_minutes
+                && p.show_seconds == self.show_seconds
+                && p.show_decimals == self.show_decimals
+                && p.dynamic == self.dynamic
+                && p.absorb_hidden_units == self.absorb_hidden_units
+        })
+    }
+
+    /// Digits after the separator, two unless chosen otherwise.
+    pub fn decimal_places(&self) -> u8 {
+        self.decimal_places.unwrap_or(2)
+    }
+
     pub fn set_decimal_places(&mut self, places: u8) {
-        self.decimal_places = places.clamp(1, 3);
+        self.decimal_places = Some(places.clamp(1, 3));
         self.cached_pattern = None;
     }
 
+    pub fn set_decimal_separator(&mut self, separator: char) {
+        self.decimal_separator = separator;
+        self.cached_pattern = None;
+    }
+
+    /// The separator placed before the fractional digits. Falls back to '.' when the
+    /// configured character would be mistaken for a pattern token.
+    pub fn decimal_separator(&self) -> char {
+        if self.decimal_separator.is_alphanumeric() {
+            '.'
+        } else {
+            self.decimal_separator
+        }
+    }
+
     fn get_pattern(&mut self, total_millis: Option<i64>) -> String {
         if self.dynamic || self.cached_pattern.is_none() {
             self.cached_pattern = Some(self.compute_pattern(total_millis));
@@ -89,6 +198,7 @@ impl TimeFormat {
     /// If `dynamic` is enabled and `total_millis` is provided, this adjusts the
     /// pattern to match the duration. For example, with minutes+seconds+decimals
     /// enabled and under a minute, this yields "s.dd"; over a minute, "m:s".
+    /// When `show_days` is enabled alongside hours, the pattern is prefixed with "D ".
     fn compute_pattern(&self, total_millis: Option<i64>) -> String {
         // Resolve dynamic visibility for each component
         let mut show_hours = self.show_hours;
@@ -126,6 +236,9 @@ impl TimeFormat {
             }
         };
 
+        if self.show_days && show_hours {
+            pattern.push_str("D ");
+        }
         if show_hours {
             pattern.push('h');
         }
@@ -137,9 +250,9 @@ impl TimeFormat {
             push_sep(':', &mut pattern);
             pattern.push('s');
         }
-        if show_decimals && self.decimal_places > 0 {
-            pattern.push('.');
-            for _ in 0..self.decimal_places {
+        if show_decimals && self.decimal_places() > 0 {
+            pattern.push(self.decimal_separator());
+            for _ in 0..self.decimal_places() {
                 pattern.push('d');
             }
         }
@@ -148,9 +261,9 @@ impl TimeFormat {
         if pattern.is_empty() {
             if self.show_seconds {
                 pattern.push('s');
-                if self.show_decimals && self.decimal_places > 0 {
-                    pattern.push('.');
-                    for _ in 0..self.decimal_places {
+                if self.show_decimals && self.decimal_places() > 0 {
+                    pattern.push(self.decimal_separator());
+                    for _ in 0..self.decimal_places() {
                         pattern.push('d');
                     }
                 }
@@ -173,34 +286,66 @@ impl TimeFormat {
     /// Formats a `TimeSpan` using the class `pattern`.
     ///
     /// Supported tokens:
+    /// - D                -> days followed by "d" (omitted when zero). Hours wrap at 24 after it.
     /// - h                -> hours (0+)
     /// - m                -> minutes (0-59)
     /// - s                -> seconds (0-59)
     /// - d / dd / ddd...  -> fractional seconds (tenths/centiseconds/milliseconds). Truncated, not rounded.
     ///
-    /// Any other characters are treated as literals (e.g., ":" or ".").
+    /// Any other characters are treated as literals (e.g., ":" or the decimal separator).
     ///
     /// Examples:
     /// - "h:m:ss"       ->  "1:02:03"
     /// - "m:s.dd"       ->  "2:03.45"
     /// - "h:m:s.d"      ->  "1:02:03.4"
     /// - "m:s.ddd"      ->  "2:03.456"
+    /// - "D h:m:s"      ->  "2d 3:04:05"
     ///
     /// Notes:
-    /// - Negative values are prefixed with "-".
+    /// - No sign is emitted unless `always_signed` is set, in which case positive
+    ///   values are prefixed with "+" and negative ones with "-".
     pub fn format_time_span(&self, span: &TimeSpan) -> String {
-        // Determine sign and absolute time in milliseconds
         let total_ms = span.total_milliseconds();
-        let abs_ms = total_ms.abs() as i64;
+        let out = self.format_magnitude(total_ms.abs() as i64);
 
-        let hours = abs_ms / 3_600_000;
-        let minutes = (abs_ms / 60_000) % 60;
-        let seconds = (abs_ms / 1_000) % 60;
+        if !self.always_signed {
+            return out;
+        }
+        if total_ms >= 1.0 {
+            format!("+{out}")
+        } else if total_ms <= -1.0 {
+            format!("-{out}")
+        } else {
+            out
+        }
+    }
+
+    /// Formats the absolute value of a duration, never emitting a sign. Useful for callers
+    /// that render their own sign (e.g. split deltas).
+    pub fn format_duration_magnitude(&self, duration: &TimeDuration) -> String {
+        self.format_magnitude(duration.whole_milliseconds().unsigned_abs() as i64)
+    }
+
+    fn format_magnitude(&self, abs_ms: i64) -> String {
+        let days = abs_ms / 86_400_000;
+        let mut hours = abs_ms / 3_600_000;
+        let mut minutes = (abs_ms / 60_000) % 60;
+        let mut seconds = (abs_ms / 1_000) % 60;
         let millis = abs_ms % 1_000;
 
         let pattern = self.compute_pattern(Some(abs_ms));
+        if pattern.contains('D') {
+            hours %= 24;
+        }
+        if self.absorb_hidden_units && !pattern.contains('h') {
+            minutes = abs_ms / 60_000;
+            if !pattern.contains('m') {
+                seconds = abs_ms / 1_000;
+            }
+        }
 
         let mut out = String::new();
+        let mut after_days = false;
 
         // Tokenize the pattern by runs of the same character
         let mut chars = pattern.chars().peekable();
@@ -217,7 +362,17 @@ impl TimeFormat {
             }
 
             match ch {
-                'h' => Self::append_number(&mut out, hours, false),
+                'D' => {
+                    if days > 0 {
+                        let _ = write!(out, "{days}d");
+                        after_days = true;
+                    }
+                }
+                'h' if after_days => {
+                    // Hours right after the day count read naturally ("2d 3:04:05")
+                    let _ = write!(out, "{hours}");
+                }
+                'h' => Self::append_number(&mut out, hours, self.always_show_hours),
                 'm' => Self::append_number(&mut out, minutes, false),
                 's' => Self::append_number(&mut out, seconds, true),
                 'd' => Self::append_fraction(&mut out, millis, count),
@@ -254,6 +409,12 @@ impl TimeFormat {
 
     /// Formats the overall timer's current attempt duration into a string using this format.
     pub fn format_timer(&self, timer: &Timer) -> String {
+        self.format_timer_with_method(timer, timer.current_timing_method())
+    }
+
+    /// Like `format_timer`, but for the given timing method instead of the timer's
+    /// current one.
+    pub fn format_timer_with_method(&self, timer: &Timer, method: TimingMethod) -> String {
         let dur = timer
             .current_attempt_duration()
             .to_duration()
@@ -261,15 +422,20 @@ impl TimeFormat {
             .unwrap_or_default()
             .checked_sub(timer.get_pause_time().unwrap_or_default().to_duration())
             .unwrap_or_default()
-            .checked_sub(if timer.current_timing_method() == TimingMethod::GameTime {
+            .checked_sub(if method == TimingMethod::GameTime {
                 timer.loading_times().to_duration()
             } else {
                 TimeDuration::ZERO
             })
             .unwrap_or_default();
-        let out = self.format_duration(&dur);
-        if dur < TimeDuration::ZERO {
+        // Format the magnitude and add the sign here, so a negative offset can never
+        // pick up a second minus from the span formatting
+        let out = self.format_duration_magnitude(&dur);
+        let millis = dur.whole_milliseconds();
+        if millis < 0 {
             format!("-{out}")
+        } else if self.always_signed && millis > 0 {
+            format!("+{out}")
         } else {
             out
         }
@@ -336,7 +502,13 @@ impl std::fmt::Display for TimeParseError {
     }
 }
 
+/// Parses a duration typed by the user, either in colon notation (`h:m:s.frac`,
+/// `m:s.frac`, `s.frac`) or with unit suffixes (`1h2m3.5s`, `2m30s`, `45s`).
 pub fn parse_hms(input: &str) -> Result<TimeDuration, TimeParseError> {
+    if input.contains(['h', 'm', 's']) {
+        return parse_unit_suffixed(input);
+    }
+
     let parts: Vec<&str> = input.split(':').collect();
 
     let (hours, mins, secs_part) = match parts.len() {
@@ -366,24 +538,82 @@ pub fn parse_hms(input: &str) -> Result<TimeDuration, TimeParseError> {
         return Err(TimeParseError);
     }
 
+    let nanos = parse_fraction_nanos(s_frac)?;
+
+    let total_secs = hours * 3600 + mins * 60 + secs;
+
+    Ok(TimeDuration::new(total_secs as i64, nanos as i32))
+}
+
+/// Parses `1h2m3.5s`-style input. Units must appear in h/m/s order, each at most
+/// once, and only seconds may carry a fraction. Like the colon notation, a unit
+/// that follows a larger one must stay below 60.
+fn parse_unit_suffixed(input: &str) -> Result<TimeDuration, TimeParseError> {
+    if input.contains(':') {
+        return Err(TimeParseError);
+    }
+
+    let mut rest = input;
+    let mut hours = None;
+    let mut mins = None;
+    for (unit, slot) in [('h', &mut hours), ('m', &mut mins)] {
+        if let Some((value, tail)) = rest.split_once(unit) {
+            *slot = Some(parse_digits(value)?);
+            rest = tail;
+        }
+    }
+
+    let (secs, nanos) = match rest.strip_suffix('s') {
+        Some(value) => match value.split_once('.') {
+            Some((whole, frac)) => (Some(parse_digits(whole)?), parse_fraction_nanos(frac)?),
+            None => (Some(parse_digits(value)?), 0),
+        },
+        None if rest.is_empty() => (None, 0),
+        None => return Err(TimeParseError),
+    };
+
+    if hours.is_none() && mins.is_none() && secs.is_none() {
+        return Err(TimeParseError);
+    }
+    let (hours, mins, secs) = (hours.unwrap_or(0), mins, secs.unwrap_or(0));
+    let has_larger_than_secs = hours > 0 || mins.is_some();
+    if hours >= 60 || (hours > 0 && mins.unwrap_or(0) >= 60) || (has_larger_than_secs && secs >= 60)
+    {
+        return Err(TimeParseError);
+    }
+
+    let total_secs = hours * 3600 + mins.unwrap_or(0) * 60 + secs;
+
+    Ok(TimeDuration::new(total_secs as i64, nanos as i32))
+}
+
+fn parse_digits(value: &str) -> Result<u64, TimeParseError> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(TimeParseError);
+    }
+    value.parse().map_err(|_| TimeParseError)
+}
+
+/// Turns the digits after the decimal point into nanoseconds, truncating past 9 digits.
+fn parse_fraction_nanos(frac: &str) -> Result<u64, TimeParseError> {
+    if frac.is_empty() {
+        return Err(TimeParseError);
+    }
+
     // Normalize
-    let mut frac_str = s_frac.to_string();
+    let mut frac_str = frac.to_string();
     if frac_str.len() > 9 {
         frac_str.truncate(9);
     } else if frac_str.len() < 9 {
         frac_str.push_str(&"0".repeat(9 - frac_str.len()));
     }
 
-    let nanos: u64 = frac_str.parse().map_err(|_| TimeParseError)?;
-
-    let total_secs = hours * 3600 + mins * 60 + secs;
-
-    Ok(TimeDuration::new(total_secs as i64, nanos as i32))
+    frac_str.parse().map_err(|_| TimeParseError)
 }
 
 #[cfg(test)]
 mod format_tests {
-    use super::TimeFormat;
+    use super::{TimeFormat, TimeFormatPreset};
     use livesplit_core::TimeSpan;
 
     fn make_tf(hours: bool, minutes: bool, seconds: bool, decimals: u8) -> TimeFormat {
@@ -392,9 +622,10 @@ mod format_tests {
             show_minutes: minutes,
             show_seconds: seconds,
             show_decimals: decimals > 0,
-            decimal_places: decimals,
+            decimal_places: Some(decimals),
             dynamic: false,
             cached_pattern: None,
+            ..TimeFormat::default()
         }
     }
 
@@ -405,9 +636,10 @@ mod format_tests {
             show_minutes: true,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 2,
+            decimal_places: Some(2),
             dynamic: false,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         assert_eq!(tf.compute_pattern(None), "h:m:s.dd");
         assert_eq!(tf.compute_pattern(Some(500)), "h:m:s.dd");
@@ -422,9 +654,10 @@ mod format_tests {
             show_minutes: true,
             show_seconds: true,
             show_decimals: false,
-            decimal_places: 3,
+            decimal_places: Some(3),
             dynamic: false,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         assert_eq!(tf.compute_pattern(None), "m:s");
         assert_eq!(tf.compute_pattern(Some(59_999)), "m:s");
@@ -437,9 +670,10 @@ mod format_tests {
             show_minutes: true,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 2,
+            decimal_places: Some(2),
             dynamic: true,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         // under 1 minute -> hide minutes, keep s.dd
         assert_eq!(tf.compute_pattern(Some(59_500)), "s.dd");
@@ -452,9 +686,10 @@ mod format_tests {
             show_minutes: true,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 3,
+            decimal_places: Some(3),
             dynamic: true,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         // >= 1 minute and < 1 hour -> m:s (no decimals)
         assert_eq!(tf.compute_pattern(Some(60_000)), "m:s");
@@ -468,9 +703,10 @@ mod format_tests {
             show_minutes: true,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 2,
+            decimal_places: Some(2),
             dynamic: true,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         // >= 1 hour -> h:m:s (no decimals)
         assert_eq!(tf.compute_pattern(Some(3_600_000)), "h:m:s");
@@ -484,9 +720,10 @@ mod format_tests {
             show_minutes: false,
             show_seconds: true,
             show_decimals: true,
-            decimal_places: 4,
+            decimal_places: Some(4),
             dynamic: false,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         assert_eq!(tf.compute_pattern(None), "s.dddd");
     }
@@ -498,9 +735,10 @@ mod format_tests {
             show_minutes: false,
             show_seconds: false,
             show_decimals: false,
-            decimal_places: 0,
+            decimal_places: Some(0),
             dynamic: false,
             cached_pattern: None,
+            ..TimeFormat::default()
         };
         assert_eq!(tf.compute_pattern(None), "s");
     }
@@ -536,6 +774,207 @@ mod format_tests {
         assert_eq!(tf_hmsddd.format_time_span(&t), "1:04:05.999");
     }
 
+    #[test]
+    fn days_pattern_only_when_enabled_with_hours() {
+        let mut tf = make_tf(true, true, true, 0);
+        assert_eq!(tf.compute_pattern(None), "h:m:s");
+        tf.show_days = true;
+        assert_eq!(tf.compute_pattern(None), "D h:m:s");
+        tf.show_hours = false;
+        assert_eq!(tf.compute_pattern(None), "m:s");
+    }
+
+    #[test]
+    fn format_time_span_25_hours() {
+        let t = TimeSpan::from_milliseconds(25.0 * 3_600_000.0 + 245_000.0); // 25:04:05
+        let mut tf = make_tf(true, true, true, 0);
+        assert_eq!(tf.format_time_span(&t), "25:04:05");
+        tf.show_days = true;
+        assert_eq!(tf.format_time_span(&t), "1d 1:04:05");
+    }
+
+    #[test]
+    fn format_time_span_49_hours() {
+        let t = TimeSpan::from_milliseconds(49.0 * 3_600_000.0 + 245_000.0); // 49:04:05
+        let mut tf = make_tf(true, true, true, 2);
+        assert_eq!(tf.format_time_span(&t), "49:04:05.00");
+        tf.show_days = true;
+        assert_eq!(tf.format_time_span(&t), "2d 1:04:05.00");
+    }
+
+    #[test]
+    fn format_time_span_days_enabled_under_a_day_is_unchanged() {
+        let t = TimeSpan::from_milliseconds(3_845_999.0); // 01:04:05.999
+        let mut tf = make_tf(true, true, true, 0);
+        tf.show_days = true;
+        assert_eq!(tf.format_time_span(&t), "1:04:05");
+    }
+
+    #[test]
+    fn format_time_span_comma_separator() {
+        let t = TimeSpan::from_milliseconds(125_340.0); // 00:02:05.340
+        let mut tf = make_tf(false, true, true, 2);
+        tf.set_decimal_separator(',');
+        assert_eq!(tf.compute_pattern(None), "m:s,dd");
+        assert_eq!(tf.format_time_span(&t), "2:05,34");
+    }
+
+    #[test]
+    fn alphanumeric_separator_falls_back_to_period() {
+        let t = TimeSpan::from_milliseconds(3_145.0);
+        let mut tf = make_tf(false, false, true, 2);
+        tf.set_decimal_separator('d');
+        assert_eq!(tf.format_time_span(&t), "3.14");
+    }
+
+    #[test]
+    fn always_signed_prefixes_positive_values() {
+        let t = TimeSpan::from_milliseconds(83_450.0); // 00:01:23.450
+        let mut tf = make_tf(false, true, true, 2);
+        assert_eq!(tf.format_time_span(&t), "1:23.45");
+        tf.always_signed = true;
+        assert_eq!(tf.format_time_span(&t), "+1:23.45");
+        assert_eq!(
+            tf.format_duration(&time::Duration::milliseconds(83_450)),
+            "+1:23.45"
+        );
+    }
+
+    #[test]
+    fn always_signed_prefixes_negative_values_once() {
+        let t = TimeSpan::from_milliseconds(-61_230.0);
+        let mut tf = make_tf(false, true, true, 2);
+        tf.always_signed = true;
+        assert_eq!(tf.format_time_span(&t), "-1:01.23");
+        assert_eq!(
+            tf.format_duration_magnitude(&time::Duration::milliseconds(-61_230)),
+            "1:01.23"
+        );
+    }
+
+    #[test]
+    fn always_signed_timer_with_offsets() {
+        use livesplit_core::{Run, Segment, Timer};
+
+        let mut tf = make_tf(false, false, true, 2);
+        tf.always_signed = true;
+
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split 1"));
+        run.set_offset(TimeSpan::from_seconds(5.0));
+        let timer = Timer::new(run).expect("timer");
+        assert_eq!(tf.format_timer(&timer), "+5.00");
+
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split 1"));
+        run.set_offset(TimeSpan::from_seconds(-5.0));
+        let timer = Timer::new(run).expect("timer");
+        assert_eq!(tf.format_timer(&timer), "-5.00");
+    }
+
+    #[test]
+    fn negative_offset_timer_has_a_single_minus() {
+        use livesplit_core::{Run, Segment, Timer};
+
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split 1"));
+        run.set_offset(TimeSpan::from_seconds(-5.0));
+        let timer = Timer::new(run).expect("timer");
+
+        let mut tf = make_tf(false, false, true, 2);
+        assert_eq!(tf.format_timer(&timer), "-5.00");
+        tf.always_signed = true;
+        assert_eq!(tf.format_timer(&timer), "-5.00");
+    }
+
+    #[test]
+    fn timer_with_method_subtracts_loading_times_for_game_time() {
+        use livesplit_core::{Run, Segment, Timer, TimingMethod};
+
+        let tf = make_tf(false, false, true, 2);
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split 1"));
+        run.set_offset(TimeSpan::from_seconds(10.0));
+        let mut timer = Timer::new(run).expect("timer");
+        timer.set_current_timing_method(TimingMethod::RealTime);
+        timer.set_loading_times(TimeSpan::from_seconds(2.0));
+
+        assert_eq!(
+            tf.format_timer_with_method(&timer, TimingMethod::RealTime),
+            "10.00"
+        );
+        assert_eq!(
+            tf.format_timer_with_method(&timer, TimingMethod::GameTime),
+            "8.00"
+        );
+        assert_eq!(tf.format_timer(&timer), "10.00");
+    }
+
+    #[test]
+    fn minutes_seconds_preset_keeps_counting_minutes() {
+        let tf = TimeFormat::from_preset(TimeFormatPreset::MinutesSeconds);
+        assert_eq!(tf.compute_pattern(None), "m:s");
+        let t = TimeSpan::from_milliseconds(3_845_999.0); // 01:04:05.999
+        assert_eq!(tf.format_time_span(&t), "64:05");
+    }
+
+    #[test]
+    fn seconds_millis_preset_shows_total_seconds() {
+        let tf = TimeFormat::from_preset(TimeFormatPreset::SecondsMillis);
+        assert_eq!(tf.compute_pattern(None), "s.ddd");
+        let t = TimeSpan::from_milliseconds(90_250.0);
+        assert_eq!(tf.format_time_span(&t), "90.250");
+    }
+
+    #[test]
+    fn hours_always_preset_prints_zero_hours() {
+        let tf = TimeFormat::from_preset(TimeFormatPreset::HoursAlways);
+        let t = TimeSpan::from_milliseconds(83_450.0); // 00:01:23.450
+        assert_eq!(tf.format_time_span(&t), "0:01:23.45");
+        let show = TimeFormat::from_preset(TimeFormatPreset::ShowDecimals);
+        assert_eq!(show.format_time_span(&t), "1:23.45");
+    }
+
+    #[test]
+    fn apply_preset_sets_components_and_round_trips() {
+        for preset in TimeFormatPreset::ALL {
+            let mut tf = TimeFormat::default();
+            tf.set_decimal_separator(',');
+            tf.apply_preset(preset);
+
+            let expected = TimeFormat::from_preset(preset);
+            assert_eq!(tf.show_hours, expected.show_hours);
+            assert_eq!(tf.always_show_hours, expected.always_show_hours);
+            assert_eq!(tf.show_minutes, expected.show_minutes);
+            assert_eq!(tf.show_seconds, expected.show_seconds);
+            assert_eq!(tf.show_decimals, expected.show_decimals);
+            assert_eq!(tf.decimal_places, expected.decimal_places);
+            assert_eq!(tf.dynamic, expected.dynamic);
+            assert_eq!(tf.decimal_separator(), ',');
+            assert_eq!(tf.preset(), Some(preset));
+        }
+    }
+
+    #[test]
+    fn apply_preset_sets_its_decimal_places() {
+        let mut tf = TimeFormat::default();
+        tf.set_decimal_places(1);
+        tf.apply_preset(TimeFormatPreset::SecondsMillis);
+        assert_eq!(tf.decimal_places(), 3);
+        assert_eq!(tf.compute_pattern(None), "s.ddd");
+
+        tf.apply_preset(TimeFormatPreset::MinutesSeconds);
+        assert_eq!(tf.decimal_places(), 2);
+    }
+
+    #[test]
+    fn formats_without_hours_keep_wrapping_minutes() {
+        // Formats predating the presets, e.g. loaded from an existing config
+        let tf = make_tf(false, true, true, 0);
+        let t = TimeSpan::from_milliseconds(3_845_999.0); // 01:04:05.999
+        assert_eq!(tf.format_time_span(&t), "4:05");
+    }
+
     #[test]
     fn format_time_span_negative() {
         let t = TimeSpan::from_milliseconds(-61_230.0); // -00:01:01.230
@@ -681,4 +1120,45 @@ mod parse_tests {
     fn test_minutes_seconds_missing_fraction() {
         assert_eq!(parse_hms("1:44").err(), Some(TimeParseError));
     }
+
+    #[test]
+    fn test_units_hms() {
+        let d = parse_hms("1h2m3s").unwrap();
+        assert_eq!(d, parse_hms("1:2:3.0").unwrap());
+        assert_eq!(d.whole_seconds(), 1 * 3600 + 2 * 60 + 3);
+        assert_eq!(d.subsec_nanoseconds(), 0);
+    }
+
+    #[test]
+    fn test_units_seconds_over_a_minute() {
+        let d = parse_hms("90s").unwrap();
+        assert_eq!(d, parse_hms("1:30.0").unwrap());
+    }
+
+    #[test]
+    fn test_units_minutes_seconds_fraction() {
+        let d = parse_hms("2m30.25s").unwrap();
+        assert_eq!(d, parse_hms("2:30.25").unwrap());
+        assert_eq!(d.subsec_nanoseconds(), 250_000_000);
+    }
+
+    #[test]
+    fn test_units_fractional_seconds_with_hours() {
+        assert_eq!(
+            parse_hms("1h2m3.5s").unwrap(),
+            parse_hms("1:2:3.5").unwrap()
+        );
+        assert_eq!(parse_hms("1h").unwrap().whole_seconds(), 3600);
+    }
+
+    #[test]
+    fn test_units_invalid() {
+        assert_eq!(parse_hms("1:2h").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("1m2h").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("1.5m").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("1m60s").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("h").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("3s4").err(), Some(TimeParseError));
+        assert_eq!(parse_hms("2.s").err(), Some(TimeParseError));
+    }
 }
@@ -0,0 +1,14 @@
+//! Optional connections to external services, driven by timer phase changes.
+
+pub mod obs;
+pub mod twitch;
+
+use livesplit_core::{Timer, TimerPhase};
+
+use crate::config::Config;
+
+/// Called whenever the timer phase changes, with the phase it changed from.
+pub fn on_phase_transition(config: &Config, previous: TimerPhase, timer: &Timer) {
+    obs::on_phase_transition(config, previous, timer);
+    twitch::on_phase_transition(config, previous, timer);
+}
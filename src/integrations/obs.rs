@@ -0,0 +1,245 @@
+//! OBS WebSocket (protocol 5) client switching scenes when a run starts or finishes,
+//! configured in `connections.obs`.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use livesplit_core::{Timer, TimerPhase};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use tungstenite::{Message, WebSocket};
+
+use crate::config::{Config, ObsConfig};
+use crate::context::TuxSplitContext;
+
+const RPC_VERSION: u64 = 1;
+/// Give up on an unresponsive OBS instead of keeping the thread around.
+const OBS_TIMEOUT: Duration = Duration::from_secs(5);
+
+const OP_HELLO: u64 = 0;
+const OP_IDENTIFY: u64 = 1;
+const OP_IDENTIFIED: u64 = 2;
+const OP_EVENT: u64 = 5;
+const OP_REQUEST: u64 = 6;
+const OP_REQUEST_RESPONSE: u64 = 7;
+
+/// The scene to switch to for a phase change, if one is configured for it.
+pub fn scene_for(obs: &ObsConfig, previous: TimerPhase, current: TimerPhase) -> Option<&str> {
+    let scene = match (previous, current) {
+        (TimerPhase::NotRunning, TimerPhase::Running) => obs.running_scene.as_deref(),
+        (_, TimerPhase::Ended) => obs.ended_scene.as_deref(),
+        _ => None,
+    }?;
+    (!scene.trim().is_empty()).then_some(scene)
+}
+
+/// Answer to the authentication challenge of the Hello message.
+fn authentication(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(Sha256::digest(format!("{password}{salt}")));
+    BASE64.encode(Sha256::digest(format!("{secret}{challenge}")))
+}
+
+/// The Identify message answering `hello`.
+fn identify_message(hello: &Value, password: Option<&str>) -> Result<Value, String> {
+    if hello["op"] != OP_HELLO {
+        return Err("unexpected greeting from OBS".to_owned());
+    }
+    let mut identify = json!({ "op": OP_IDENTIFY, "d": { "rpcVersion": RPC_VERSION } });
+    let auth = &hello["d"]["authentication"];
+    if !auth.is_null() {
+        let password = password.ok_or("OBS asks for a password, none is configured")?;
+        let (Some(salt), Some(challenge)) = (auth["salt"].as_str(), auth["challenge"].as_str())
+        else {
+            return Err("malformed authentication request from OBS".to_owned());
+        };
+        identify["d"]["authentication"] = authentication(password, salt, challenge).into();
+    }
+    Ok(identify)
+}
+
+fn scene_request(scene: &str) -> Value {
+    json!({
+        "op": OP_REQUEST,
+        "d": {
+            "requestType": "SetCurrentProgramScene",
+            "requestId": "tuxsplit-scene",
+            "requestData": { "sceneName": scene },
+        },
+    })
+}
+
+/// Switches the scene on a background thread, so the UI never waits on OBS.
+pub fn on_phase_transition(config: &Config, previous: TimerPhase, timer: &Timer) {
+    let Some(obs) = config.obs() else {
+        return;
+    };
+    let Some(scene) = scene_for(obs, previous, timer.current_phase()) else {
+        return;
+    };
+    let (obs, scene) = (obs.clone(), scene.to_owned());
+    thread::spawn(move || match switch_scene(&obs, &scene) {
+        Ok(()) => info!("Switched OBS to the \"{scene}\" scene"),
+        Err(err) => {
+            warn!("Could not switch the OBS scene: {err}");
+            glib::MainContext::default().invoke(move || {
+                TuxSplitContext::get_instance()
+                    .show_toast(&format!("Could not switch the OBS scene: {err}"));
+            });
+        }
+    });
+}
+
+fn switch_scene(obs: &ObsConfig, scene: &str) -> Result<(), String> {
+    let address = (obs.host.as_str(), obs.port)
+        .to_socket_addrs()
+        .map_err(|err| err.to_string())?
+        .next()
+        .ok_or_else(|| format!("unknown host \"{}\"", obs.host))?;
+    let stream =
+        TcpStream::connect_timeout(&address, OBS_TIMEOUT).map_err(|err| err.to_string())?;
+    stream
+        .set_read_timeout(Some(OBS_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(OBS_TIMEOUT)))
+        .map_err(|err| err.to_string())?;
+    let url = format!("ws://{}:{}", obs.host, obs.port);
+    let (mut socket, _) = tungstenite::client(url, stream).map_err(|err| err.to_string())?;
+
+    let hello = receive(&mut socket)?;
+    send(
+        &mut socket,
+        &identify_message(&hello, obs.password.as_deref())?,
+    )?;
+    if receive(&mut socket)?["op"] != OP_IDENTIFIED {
+        return Err("OBS refused the connection, check the password".to_owned());
+    }
+
+    send(&mut socket, &scene_request(scene))?;
+    let response = receive(&mut socket)?;
+    let status = &response["d"]["requestStatus"];
+    let result = if response["op"] == OP_REQUEST_RESPONSE && status["result"] == true {
+        Ok(())
+    } else {
+        Err(status["comment"]
+            .as_str()
+            .unwrap_or("unexpected response from OBS")
+            .to_owned())
+    };
+    let _ = socket.close(None);
+    result
+}
+
+fn send(socket: &mut WebSocket<TcpStream>, message: &Value) -> Result<(), String> {
+    socket
+        .send(Message::text(message.to_string()))
+        .map_err(|err| err.to_string())
+}
+
+/// Reads the next JSON message, skipping pings and events.
+fn receive(socket: &mut WebSocket<TcpStream>) -> Result<Value, String> {
+    loop {
+        match socket.read().map_err(|err| err.to_string())? {
+            Message::Text(text) => {
+                let message: Value =
+                    serde_json::from_str(text.as_str()).map_err(|err| err.to_string())?;
+                // Events arrive whenever OBS feels like it
+                if message["op"] != OP_EVENT {
+                    return Ok(message);
+                }
+            }
+            Message::Close(_) => return Err("OBS closed the connection".to_owned()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod obs_tests {
+    use super::*;
+
+    fn obs_config() -> ObsConfig {
+        ObsConfig {
+            running_scene: Some("Gameplay".to_owned()),
+            ended_scene: Some("Results".to_owned()),
+            ..ObsConfig::default()
+        }
+    }
+
+    #[test]
+    fn scenes_follow_run_start_and_finish() {
+        let obs = obs_config();
+        assert_eq!(
+            scene_for(&obs, TimerPhase::NotRunning, TimerPhase::Running),
+            Some("Gameplay")
+        );
+        assert_eq!(
+            scene_for(&obs, TimerPhase::Running, TimerPhase::Ended),
+            Some("Results")
+        );
+        // Resuming a paused attempt is not a start
+        assert_eq!(
+            scene_for(&obs, TimerPhase::Paused, TimerPhase::Running),
+            None
+        );
+        assert_eq!(
+            scene_for(&obs, TimerPhase::Running, TimerPhase::NotRunning),
+            None
+        );
+
+        let unset = ObsConfig {
+            ended_scene: Some(String::new()),
+            ..ObsConfig::default()
+        };
+        assert_eq!(
+            scene_for(&unset, TimerPhase::Running, TimerPhase::Ended),
+            None
+        );
+    }
+
+    #[test]
+    fn authentication_matches_the_protocol_example() {
+        // Example from the OBS WebSocket protocol documentation
+        assert_eq!(
+            authentication(
+                "supersecretpassword",
+                "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=",
+                "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY=",
+            ),
+            "1Ct943GAT+6YQUUX47Ia/ncufilbe6+oD6lY+5kaCu4="
+        );
+    }
+
+    #[test]
+    fn identify_answers_the_hello() {
+        let open = json!({ "op": 0, "d": { "obsWebSocketVersion": "5.0.0", "rpcVersion": 1 } });
+        assert_eq!(
+            identify_message(&open, None),
+            Ok(json!({ "op": 1, "d": { "rpcVersion": 1 } }))
+        );
+
+        let protected = json!({
+            "op": 0,
+            "d": {
+                "rpcVersion": 1,
+                "authentication": { "challenge": "challenge", "salt": "salt" },
+            },
+        });
+        assert!(identify_message(&protected, None).is_err());
+        let identify = identify_message(&protected, Some("hunter2")).expect("identify");
+        assert_eq!(
+            identify["d"]["authentication"],
+            authentication("hunter2", "salt", "challenge")
+        );
+    }
+
+    #[test]
+    fn scene_request_sets_the_program_scene() {
+        let request = scene_request("Gameplay");
+        assert_eq!(request["op"], 6);
+        assert_eq!(request["d"]["requestType"], "SetCurrentProgramScene");
+        assert_eq!(request["d"]["requestData"]["sceneName"], "Gameplay");
+    }
+}
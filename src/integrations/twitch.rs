@@ -0,0 +1,200 @@
+//! Minimal Twitch chat client announcing new personal bests (and, optionally, resets)
+//! in the channel set in `connections.twitch`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use livesplit_core::{Timer, TimerPhase};
+use native_tls::TlsConnector;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::context::TuxSplitContext;
+use crate::utils::comparisons::is_new_personal_best;
+
+const TWITCH_IRC_HOST: &str = "irc.chat.twitch.tv";
+/// The TLS port, the OAuth token isn't sent in plain text.
+const TWITCH_IRC_PORT: u16 = 6697;
+/// Give up on an unreachable or silent server instead of keeping the thread around.
+const IRC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The chat message for a phase change, if it is one worth announcing.
+pub fn announcement(config: &Config, previous: TimerPhase, timer: &Timer) -> Option<String> {
+    let run = timer.run();
+    let title = format!("{} {}", run.game_name(), run.category_name());
+    let title = title.trim();
+    match (previous, timer.current_phase()) {
+        (TimerPhase::Running, TimerPhase::Ended)
+            if config.general.announce_pb && is_new_personal_best(timer) =>
+        {
+            let final_time = config.format.timer.format_timer(timer);
+            Some(format!("New personal best in {title}: {final_time}!"))
+        }
+        (TimerPhase::Running | TimerPhase::Paused, TimerPhase::NotRunning)
+            if config.general.announce_reset =>
+        {
+            Some(format!("{title} run reset."))
+        }
+        _ => None,
+    }
+}
+
+/// Lines sent to the IRC server to post `message` as the channel owner.
+fn login_lines(channel: &str, token: &str) -> [String; 3] {
+    let token = token.trim_start_matches("oauth:");
+    let channel = channel.trim_start_matches('#').to_lowercase();
+    [
+        format!("PASS oauth:{token}\r\n"),
+        format!("NICK {channel}\r\n"),
+        format!("JOIN #{channel}\r\n"),
+    ]
+}
+
+fn message_line(channel: &str, message: &str) -> String {
+    let channel = channel.trim_start_matches('#').to_lowercase();
+    // A line break would end the command early
+    let message = message.replace(['\r', '\n'], " ");
+    format!("PRIVMSG #{channel} :{message}\r\n")
+}
+
+/// Posts the announcement on a background thread, so the UI never waits on the network.
+pub fn on_phase_transition(config: &Config, previous: TimerPhase, timer: &Timer) {
+    let Some((channel, token)) = config.twitch_account() else {
+        return;
+    };
+    let Some(message) = announcement(config, previous, timer) else {
+        return;
+    };
+    let (channel, token) = (channel.to_owned(), token.to_owned());
+    thread::spawn(move || match post_message(&channel, &token, &message) {
+        Ok(()) => info!("Posted \"{message}\" to #{channel}"),
+        Err(err) => {
+            warn!("Could not post to Twitch chat: {err}");
+            glib::MainContext::default().invoke(move || {
+                TuxSplitContext::get_instance()
+                    .show_toast(&format!("Could not post to Twitch chat: {err}"));
+            });
+        }
+    });
+}
+
+fn post_message(channel: &str, token: &str, message: &str) -> std::io::Result<()> {
+    let address = (TWITCH_IRC_HOST, TWITCH_IRC_PORT)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::other("could not resolve the chat server"))?;
+    let tcp = TcpStream::connect_timeout(&address, IRC_TIMEOUT)?;
+    tcp.set_read_timeout(Some(IRC_TIMEOUT))?;
+    tcp.set_write_timeout(Some(IRC_TIMEOUT))?;
+    let mut stream = TlsConnector::new()
+        .map_err(std::io::Error::other)?
+        .connect(TWITCH_IRC_HOST, tcp)
+        .map_err(|err| std::io::Error::other(err.to_string()))?;
+    for line in login_lines(channel, token) {
+        stream.write_all(line.as_bytes())?;
+    }
+
+    // Messages sent before the channel is joined are dropped, wait for the end of
+    // the member list (366) that confirms the join
+    let mut reader = BufReader::new(&mut stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(std::io::Error::other("connection closed before joining"));
+        }
+        if line.contains("Login authentication failed") {
+            return Err(std::io::Error::other("login authentication failed"));
+        }
+        if line.split(' ').nth(1) == Some("366") {
+            break;
+        }
+    }
+
+    stream.write_all(message_line(channel, message).as_bytes())?;
+    stream.write_all(b"QUIT\r\n")?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod twitch_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment, Time, TimeSpan};
+
+    fn timer_with_pb(pb: Option<f64>) -> Timer {
+        let mut run = Run::new();
+        run.set_game_name("Celeste");
+        run.set_category_name("Any%");
+        let mut segment = Segment::new("End");
+        segment.set_personal_best_split_time(
+            Time::new().with_real_time(pb.map(TimeSpan::from_seconds)),
+        );
+        run.push_segment(segment);
+        Timer::new(run).expect("timer")
+    }
+
+    fn announcing_config() -> Config {
+        let mut config = Config::default();
+        config.general.announce_pb = true;
+        config.general.announce_reset = true;
+        config
+    }
+
+    #[test]
+    fn first_finished_run_is_a_personal_best() {
+        let mut timer = timer_with_pb(None);
+        timer.start();
+        timer.split();
+        assert_eq!(timer.current_phase(), TimerPhase::Ended);
+        assert!(is_new_personal_best(&timer));
+
+        let message = announcement(&announcing_config(), TimerPhase::Running, &timer);
+        assert!(message.is_some_and(|m| m.starts_with("New personal best in Celeste Any%")));
+    }
+
+    #[test]
+    fn slower_run_is_not_announced() {
+        let mut timer = timer_with_pb(Some(0.0));
+        timer.start();
+        timer.split();
+        assert_eq!(timer.current_phase(), TimerPhase::Ended);
+        assert!(!is_new_personal_best(&timer));
+        assert_eq!(
+            announcement(&announcing_config(), TimerPhase::Running, &timer),
+            None
+        );
+    }
+
+    #[test]
+    fn resets_are_announced_only_when_enabled() {
+        let timer = timer_with_pb(None);
+        let mut config = announcing_config();
+        assert_eq!(
+            announcement(&config, TimerPhase::Running, &timer).as_deref(),
+            Some("Celeste Any% run reset.")
+        );
+        // Resetting a finished run is not a reset worth telling chat about
+        assert_eq!(announcement(&config, TimerPhase::Ended, &timer), None);
+
+        config.general.announce_reset = false;
+        assert_eq!(announcement(&config, TimerPhase::Running, &timer), None);
+    }
+
+    #[test]
+    fn irc_lines_follow_the_protocol() {
+        assert_eq!(
+            login_lines("#MyChannel", "oauth:abc123"),
+            [
+                "PASS oauth:abc123\r\n".to_owned(),
+                "NICK mychannel\r\n".to_owned(),
+                "JOIN #mychannel\r\n".to_owned(),
+            ]
+        );
+        assert_eq!(
+            message_line("MyChannel", "New PB!\nGG"),
+            "PRIVMSG #mychannel :New PB! GG\r\n"
+        );
+    }
+}
@@ -1,14 +1,18 @@
 mod config;
 mod context;
+mod discard_reset;
 mod formatters;
+mod integrations;
+mod server;
 mod ui;
 mod utils;
 
+use std::env;
 use std::path::Path;
 
 use tracing::info;
 
-use crate::context::{build_ui, shutdown};
+use crate::context::{build_ui, load_config, shutdown};
 use adw::Application;
 use adw::prelude::*;
 use gtk4::{
@@ -21,15 +25,13 @@ const RESOURCE_ICONS: &str = "/com/tunixr/tuxsplit/icons";
 const RESOURCE_CSS: &str = "/com/tunixr/tuxsplit/css/tuxsplit.css";
 
 fn main() {
-    unsafe {
-        std::env::set_var("GDK_BACKEND", "x11"); // Livesplit-core does not support Wayland global shortcut portal yet
-    }
-
     // Set tracing to stdout
     tracing_subscriber::fmt()
         .with_max_level(tracing::Level::DEBUG)
         .init();
 
+    select_gdk_backend();
+
     register_gresource();
     info!("Starting TuxSplit");
     adw::init().expect("Failed to initialize libadwaita");
@@ -52,6 +54,23 @@ fn main() {
     app.run();
 }
 
+/// Livesplit-core does not support the Wayland global shortcut portal yet, so global
+/// hotkeys need X11. Must run before GTK is initialized.
+fn select_gdk_backend() {
+    if env::var_os("GDK_BACKEND").is_some() {
+        return;
+    }
+    let wayland_session = env::var_os("WAYLAND_DISPLAY").is_some()
+        || env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland");
+    if let Some(backend) = load_config().gdk_backend(wayland_session) {
+        info!("Using the {backend} GDK backend");
+        // SAFETY: no other thread has been started yet
+        unsafe {
+            env::set_var("GDK_BACKEND", backend);
+        }
+    }
+}
+
 fn load_styles() {
     let display = Display::default().expect("Could not connect to a display");
     let css_provider = CssProvider::new();
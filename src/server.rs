@@ -0,0 +1,318 @@
+//! Local control server speaking the LiveSplit Server text protocol, so external
+//! scripts can drive the timer. Disabled unless `connections.server` is set.
+//!
+//! Every command is one line, arguments are separated by a space. Supported commands:
+//!
+//! - `starttimer`, `startorsplit`, `split`, `unsplit`, `skipsplit`
+//! - `pause`, `resume`, `reset`
+//! - `initgametime`, `setgametime <time>`, `setloadingtimes <time>`,
+//!   `pausegametime`, `unpausegametime`
+//! - `setcomparison <name>`
+//! - `getcurrenttime`, `getsplitindex`, `getcurrentsplitname`, `getcurrenttimerphase`
+//!
+//! Times are written like "1:23.45" or as plain seconds. The `get` commands answer with
+//! one line; `getcurrenttime` uses the `format.timer` time format.
+
+use std::cell::RefCell;
+use std::net::SocketAddr;
+
+use gtk4::gio;
+use gtk4::prelude::*;
+use livesplit_core::{TimeSpan, Timer, TimerPhase};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::context::TuxSplitContext;
+use crate::formatters::time::parse_hms;
+
+thread_local! {
+    static SERVICE: RefCell<Option<gio::SocketService>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerCommand {
+    StartTimer,
+    StartOrSplit,
+    Split,
+    Unsplit,
+    SkipSplit,
+    Pause,
+    Resume,
+    Reset,
+    InitGameTime,
+    SetGameTime(TimeSpan),
+    SetLoadingTimes(TimeSpan),
+    PauseGameTime,
+    UnpauseGameTime,
+    SetComparison(String),
+    GetCurrentTime,
+    GetSplitIndex,
+    GetCurrentSplitName,
+    GetCurrentTimerPhase,
+}
+
+/// Parses one protocol line. Command names are case insensitive.
+pub fn parse_command(line: &str) -> Result<ServerCommand, String> {
+    let line = line.trim();
+    let (name, argument) = line
+        .split_once(' ')
+        .map_or((line, ""), |(name, argument)| (name, argument.trim()));
+    let command = match name.to_ascii_lowercase().as_str() {
+        "starttimer" => ServerCommand::StartTimer,
+        "startorsplit" => ServerCommand::StartOrSplit,
+        "split" => ServerCommand::Split,
+        "unsplit" => ServerCommand::Unsplit,
+        "skipsplit" => ServerCommand::SkipSplit,
+        "pause" => ServerCommand::Pause,
+        "resume" => ServerCommand::Resume,
+        "reset" => ServerCommand::Reset,
+        "initgametime" => ServerCommand::InitGameTime,
+        "setgametime" => ServerCommand::SetGameTime(parse_time(argument)?),
+        "setloadingtimes" => ServerCommand::SetLoadingTimes(parse_time(argument)?),
+        "pausegametime" => ServerCommand::PauseGameTime,
+        "unpausegametime" => ServerCommand::UnpauseGameTime,
+        "setcomparison" if !argument.is_empty() => {
+            ServerCommand::SetComparison(argument.to_owned())
+        }
+        "getcurrenttime" => ServerCommand::GetCurrentTime,
+        "getsplitindex" => ServerCommand::GetSplitIndex,
+        "getcurrentsplitname" => ServerCommand::GetCurrentSplitName,
+        "getcurrenttimerphase" => ServerCommand::GetCurrentTimerPhase,
+        _ => return Err(format!("unknown command \"{line}\"")),
+    };
+    Ok(command)
+}
+
+fn parse_time(argument: &str) -> Result<TimeSpan, String> {
+    parse_hms(argument)
+        .map(|time| TimeSpan::from_seconds(time.as_seconds_f64()))
+        .or_else(|_| argument.parse::<f64>().map(TimeSpan::from_seconds))
+        .map_err(|_| format!("invalid time \"{argument}\""))
+}
+
+/// Applies a command to the timer and returns the reply line of the `get` commands.
+pub fn execute(command: &ServerCommand, timer: &mut Timer, config: &Config) -> Option<String> {
+    match command {
+        ServerCommand::StartTimer => {
+            timer.start();
+        }
+        ServerCommand::StartOrSplit => {
+            timer.split_or_start();
+        }
+        ServerCommand::Split => {
+            timer.split();
+        }
+        ServerCommand::Unsplit => {
+            timer.undo_split();
+        }
+        ServerCommand::SkipSplit => {
+            timer.skip_split();
+        }
+        ServerCommand::Pause => {
+            timer.pause();
+        }
+        ServerCommand::Resume => {
+            timer.resume();
+        }
+        ServerCommand::Reset => {
+            timer.reset(true);
+        }
+        ServerCommand::InitGameTime => {
+            timer.initialize_game_time();
+        }
+        ServerCommand::SetGameTime(time) => {
+            timer.set_game_time(*time);
+        }
+        ServerCommand::SetLoadingTimes(time) => {
+            timer.set_loading_times(*time);
+        }
+        ServerCommand::PauseGameTime => {
+            timer.pause_game_time();
+        }
+        ServerCommand::UnpauseGameTime => {
+            timer.resume_game_time();
+        }
+        // The refresh loop notices the switch and remembers it
+        ServerCommand::SetComparison(name) => {
+            let _ = timer.set_current_comparison(name.as_str());
+        }
+        ServerCommand::GetCurrentTime => return Some(config.format.timer.format_timer(timer)),
+        ServerCommand::GetSplitIndex => {
+            let index = timer.current_split_index().map_or(-1, |index| index as i64);
+            return Some(index.to_string());
+        }
+        ServerCommand::GetCurrentSplitName => {
+            return Some(
+                timer
+                    .current_split()
+                    .map(|segment| segment.name().to_owned())
+                    .unwrap_or_default(),
+            );
+        }
+        ServerCommand::GetCurrentTimerPhase => {
+            return Some(
+                match timer.current_phase() {
+                    TimerPhase::NotRunning => "NotRunning",
+                    TimerPhase::Running => "Running",
+                    TimerPhase::Paused => "Paused",
+                    TimerPhase::Ended => "Ended",
+                }
+                .to_owned(),
+            );
+        }
+    }
+    None
+}
+
+/// Starts listening on `connections.server`, if set.
+pub fn install(config: &Config) {
+    let Some(address) = config.server_address() else {
+        return;
+    };
+    let service = gio::SocketService::new();
+    if let Err(err) = service.add_address(
+        &gio::InetSocketAddress::from(address),
+        gio::SocketType::Stream,
+        gio::SocketProtocol::Tcp,
+        None::<&glib::Object>,
+    ) {
+        warn!("Could not start the control server on {address}: {err}");
+        return;
+    }
+    service.connect_incoming(|_, connection, _| {
+        let connection = connection.clone();
+        glib::spawn_future_local(async move {
+            if let Err(err) = serve(connection).await {
+                debug!("Control server connection closed: {err}");
+            }
+        });
+        false
+    });
+    service.start();
+    info!("Control server listening on {address}");
+    SERVICE.with(|slot| slot.replace(Some(service)));
+}
+
+async fn serve(connection: gio::SocketConnection) -> Result<(), glib::Error> {
+    let input = gio::DataInputStream::new(&connection.input_stream());
+    let output = connection.output_stream();
+    while let Some(line) = input.read_line_utf8_future(glib::Priority::DEFAULT).await? {
+        let reply = match parse_command(&line) {
+            // Through the context, which primes the timer with `general.start_paused`
+            Ok(ServerCommand::StartTimer) => {
+                TuxSplitContext::get_instance().start();
+                None
+            }
+            Ok(ServerCommand::StartOrSplit) => {
+                TuxSplitContext::get_instance().split_or_start();
+                None
+            }
+            Ok(command) => {
+                let ctx = TuxSplitContext::get_instance();
+                let shared_timer = ctx.timer();
+                let mut timer = shared_timer.write().unwrap();
+                execute(&command, &mut timer, &ctx.config())
+            }
+            Err(err) => {
+                warn!("Control server: {err}");
+                None
+            }
+        };
+        if let Some(reply) = reply {
+            output
+                .write_all_future(format!("{reply}\r\n").into_bytes(), glib::Priority::DEFAULT)
+                .await
+                .map_err(|(_, err)| err)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod server_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment};
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("First"));
+        run.push_segment(Segment::new("Second"));
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn commands_are_parsed_case_insensitively() {
+        assert_eq!(
+            parse_command("starttimer\r\n"),
+            Ok(ServerCommand::StartTimer)
+        );
+        assert_eq!(
+            parse_command("StartOrSplit"),
+            Ok(ServerCommand::StartOrSplit)
+        );
+        assert_eq!(
+            parse_command("setcomparison Best Segments"),
+            Ok(ServerCommand::SetComparison("Best Segments".to_owned()))
+        );
+        assert!(parse_command("setcomparison").is_err());
+        assert!(parse_command("explode").is_err());
+    }
+
+    #[test]
+    fn times_accept_colon_notation_and_plain_seconds() {
+        assert_eq!(
+            parse_command("setgametime 1:02.5"),
+            Ok(ServerCommand::SetGameTime(TimeSpan::from_seconds(62.5)))
+        );
+        assert_eq!(
+            parse_command("setloadingtimes 12"),
+            Ok(ServerCommand::SetLoadingTimes(TimeSpan::from_seconds(12.0)))
+        );
+        assert!(parse_command("setgametime soon").is_err());
+    }
+
+    #[test]
+    fn commands_drive_the_timer_and_getters_reply() {
+        let mut timer = timer();
+        let config = Config::default();
+        let run = |line: &str, timer: &mut Timer| {
+            execute(&parse_command(line).expect("command"), timer, &config)
+        };
+
+        assert_eq!(
+            run("getcurrenttimerphase", &mut timer).as_deref(),
+            Some("NotRunning")
+        );
+        assert_eq!(run("getsplitindex", &mut timer).as_deref(), Some("-1"));
+
+        assert_eq!(run("starttimer", &mut timer), None);
+        assert_eq!(
+            run("getcurrentsplitname", &mut timer).as_deref(),
+            Some("First")
+        );
+        run("split", &mut timer);
+        assert_eq!(run("getsplitindex", &mut timer).as_deref(), Some("1"));
+        run("unsplit", &mut timer);
+        assert_eq!(run("getsplitindex", &mut timer).as_deref(), Some("0"));
+
+        run("pause", &mut timer);
+        assert_eq!(
+            run("getcurrenttimerphase", &mut timer).as_deref(),
+            Some("Paused")
+        );
+        run("resume", &mut timer);
+        run("reset", &mut timer);
+        assert_eq!(
+            run("getcurrenttimerphase", &mut timer).as_deref(),
+            Some("NotRunning")
+        );
+    }
+
+    #[test]
+    fn current_time_uses_the_timer_format() {
+        let mut timer = timer();
+        let config = Config::default();
+        let reply = execute(&ServerCommand::GetCurrentTime, &mut timer, &config);
+        assert_eq!(reply, Some(config.format.timer.format_timer(&timer)));
+    }
+}
@@ -5,9 +5,19 @@ use glib::prelude::*;
 use glib::subclass::prelude::*;
 
 use glib::{Properties, subclass::signal::Signal};
-use livesplit_core::{RunEditor, TimeSpan, Timer, TimingMethod};
+use livesplit_core::settings::{Image, ImageType};
+use livesplit_core::{Run, RunEditor, Time, TimeSpan, Timer, TimingMethod};
+use tracing::warn;
 
 use crate::context::TuxSplitContext;
+use crate::ui::timer::icons::forget_segment_icon;
+use crate::utils::notes::{segment_note, segment_notes, set_segment_note, set_segment_notes};
+
+/// Maximum number of edits that can be undone.
+pub const UNDO_DEPTH: usize = 50;
+
+/// Largest image accepted as a segment icon. Icons are stored inside the splits file.
+pub const MAX_ICON_BYTES: usize = 1024 * 1024;
 
 pub enum SegmentMoveDirection {
     Up,
@@ -17,7 +27,7 @@ pub enum SegmentMoveDirection {
 mod imp {
     use super::{
         Cell, DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass, OnceLock,
-        Properties, Signal, TimingMethod,
+        Properties, RefCell, Run, Signal, TimingMethod,
     };
 
     #[derive(Properties)]
@@ -25,12 +35,18 @@ mod imp {
     pub struct EditorContext {
         // Timing method used for edits: 0 = RealTime, 1 = GameTime
         pub timing_method: Cell<i32>,
+        // Snapshots of the run before each edit, most recent last
+        pub undo_stack: RefCell<Vec<Run>>,
+        // Snapshots undone since the last edit, most recent last
+        pub redo_stack: RefCell<Vec<Run>>,
     }
 
     impl Default for EditorContext {
         fn default() -> Self {
             Self {
                 timing_method: Cell::new(0), // Default to RealTime
+                undo_stack: RefCell::new(Vec::new()),
+                redo_stack: RefCell::new(Vec::new()),
             }
         }
     }
@@ -116,172 +132,457 @@ impl EditorContext {
         ctx.emit_run_changed();
     }
 
-    /// Sets the segment name at `index`. Returns true if the operation succeeded.
-    ///
-    /// Mirrors the existing behavior in table.rs: clones the run, mutates it,
-    /// then sets it back on the timer.
-    pub fn set_segment_name(&self, index: usize, name: String) {
+    /// Replaces the run with an edited one, remembering the previous run for `undo`.
+    /// Any redo history is dropped, as it no longer follows from the current run.
+    /// A run the timer rejects leaves both histories untouched.
+    pub fn commit_run(&self, run: Run) {
         let ctx = TuxSplitContext::get_instance();
-
-        let mut run = ctx.get_run();
-        if index >= run.segments().len() {
+        let previous = ctx.get_run();
+        if !ctx.set_run(run) {
+            warn!("Edited run was rejected, keeping the current one");
             return;
         }
-
-        run.segments_mut()[index].set_name(name);
-        ctx.set_run(run);
+        push_capped(&mut self.imp().undo_stack.borrow_mut(), previous);
+        self.imp().redo_stack.borrow_mut().clear();
 
         self.emit_run_changed();
     }
 
-    /// Sets the split time at `index` in milliseconds for the current timing method.
-    /// Returns true if the operation succeeded.
-    ///
-    /// Uses `RunEditor` to set the "Personal Best" comparison time, mirroring table.rs.
-    pub fn set_split_time_ms(&self, index: usize, ms: i64) {
-        if ms < 0 {
-            return;
-        }
+    /// Whether there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.imp().undo_stack.borrow().is_empty()
+    }
+
+    /// Whether there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.imp().redo_stack.borrow().is_empty()
+    }
 
+    /// Restores the run as it was before the last edit.
+    pub fn undo(&self) {
+        let Some(previous) = self.imp().undo_stack.borrow_mut().pop() else {
+            return;
+        };
         let ctx = TuxSplitContext::get_instance();
+        push_capped(&mut self.imp().redo_stack.borrow_mut(), ctx.get_run());
 
-        let mut run_editor = RunEditor::new(ctx.get_run()).ok().unwrap();
-        if index >= run_editor.run().segments().len() {
+        ctx.set_run(previous);
+        self.emit_run_changed();
+    }
+
+    /// Re-applies the last undone edit.
+    pub fn redo(&self) {
+        let Some(next) = self.imp().redo_stack.borrow_mut().pop() else {
             return;
+        };
+        let ctx = TuxSplitContext::get_instance();
+        push_capped(&mut self.imp().undo_stack.borrow_mut(), ctx.get_run());
+
+        ctx.set_run(next);
+        self.emit_run_changed();
+    }
+
+    /// Applies `edit` to a copy of the current run and commits the result. Edits
+    /// return `None` when there is nothing to change, e.g. for an out of range index.
+    fn edit(&self, edit: impl FnOnce(Run) -> Option<Run>) {
+        if let Some(run) = edit(TuxSplitContext::get_instance().get_run()) {
+            self.commit_run(run);
         }
+    }
 
-        run_editor.select_additionally(index);
-        run_editor.select_timing_method(self.timing_method());
-        run_editor.active_segment().set_comparison_time(
-            "Personal Best",
-            Some(TimeSpan::from_milliseconds(ms as f64)),
-        );
-        run_editor.unselect(index);
+    /// Sets the segment name at `index`.
+    pub fn set_segment_name(&self, index: usize, name: String) {
+        self.edit(|run| with_segment_name(run, index, name));
+    }
 
-        ctx.set_run(run_editor.close());
+    /// Sets the note shown while the segment at `index` is running. An empty note
+    /// removes it.
+    pub fn set_segment_note(&self, index: usize, note: String) {
+        self.edit(|run| with_segment_note(run, index, &note));
+    }
 
-        self.emit_run_changed();
+    /// Sets the split time at `index` in milliseconds for the current timing method.
+    ///
+    /// Uses `RunEditor` to set the "Personal Best" comparison time, mirroring table.rs.
+    pub fn set_split_time_ms(&self, index: usize, ms: i64) {
+        self.edit(|run| with_split_time_ms(run, index, ms, self.timing_method()));
     }
 
     /// Sets the segment time at `index` in milliseconds for the current timing method.
-    /// Returns true if the operation succeeded.
     ///
     /// Uses `RunEditor.active_segment().set_segment_time()`, mirroring table.rs.
     pub fn set_segment_time_ms(&self, index: usize, ms: i64) {
-        if ms < 0 {
-            return;
-        }
+        self.edit(|run| with_segment_time_ms(run, index, ms, self.timing_method()));
+    }
 
-        let ctx = TuxSplitContext::get_instance();
+    /// Sets the best segment time at `index` in milliseconds for the current timing method.
+    ///
+    /// Mutates the Run directly, mirroring the best segment logic in table.rs.
+    pub fn set_best_time_ms(&self, index: usize, ms: i64) {
+        self.edit(|run| with_best_time_ms(run, index, ms, self.timing_method()));
+    }
 
-        let mut run_editor = RunEditor::new(ctx.get_run().to_owned()).ok().unwrap();
-        if index >= run_editor.run().segments().len() {
-            return;
-        }
+    /// Moves a given segment up/down by one position.
+    pub fn move_segment(&self, index: usize, direction: SegmentMoveDirection) {
+        self.edit(|run| with_segment_moved(run, index, direction));
+    }
 
-        run_editor.select_additionally(index);
-        run_editor.select_timing_method(self.timing_method());
-        run_editor
-            .active_segment()
-            .set_segment_time(Some(TimeSpan::from_milliseconds(ms as f64)));
-        run_editor.unselect(index);
+    /// Moves the segment at `from` so it ends up at position `to`, as a single edit.
+    /// Moving a segment onto itself or out of bounds does nothing.
+    pub fn reorder_segment(&self, from: usize, to: usize) {
+        self.edit(|run| with_segment_reordered(run, from, to));
+    }
 
-        ctx.set_run(run_editor.close());
+    pub fn add_segment(&self, index: usize, direction: SegmentMoveDirection) {
+        self.edit(|run| with_segment_added(run, index, direction));
+    }
 
-        self.emit_run_changed();
+    pub fn remove_segment(&self, index: usize) {
+        self.edit(|run| with_segment_removed(run, index));
     }
 
-    /// Sets the best segment time at `index` in milliseconds for the current timing method.
-    /// Returns true if the operation succeeded.
-    ///
-    /// Mutates the Run directly, mirroring the best segment logic in table.rs.
-    pub fn set_best_time_ms(&self, index: usize, ms: i64) {
-        if ms < 0 {
-            return;
-        }
+    /// Inserts a copy of the segment at `index` directly below it, with the same name,
+    /// segment time and best segment time for both timing methods. The copy's split
+    /// time follows from its segment time, pushing the later splits back accordingly.
+    pub fn duplicate_segment(&self, index: usize) {
+        self.edit(|run| with_segment_duplicated(run, index));
+    }
 
-        let ctx = TuxSplitContext::get_instance();
+    /// Clears the Personal Best split time (and with it the segment time) and the best
+    /// segment time of the segment at `index`, for both timing methods.
+    pub fn clear_segment_times(&self, index: usize) {
+        self.edit(|run| with_segment_times_cleared(run, index));
+    }
 
-        let mut run = ctx.get_run();
-        if index >= run.segments().len() {
+    /// Adds `delta_ms` (negative to subtract) to every Personal Best split time of the
+    /// current timing method. Segments without a split time are skipped. A shift that
+    /// would move a split to zero or below is refused, as it would leave a zero segment.
+    /// Returns whether the split times were shifted.
+    pub fn shift_all_split_times(&self, delta_ms: i64) -> bool {
+        let run = TuxSplitContext::get_instance().get_run();
+        let Some(run) = with_split_times_shifted(run, delta_ms, self.timing_method()) else {
+            return false;
+        };
+        self.commit_run(run);
+        true
+    }
+
+    /// Sets the icon of the segment at `index` from PNG or JPEG file contents.
+    /// Unsupported or oversized images are ignored with a warning.
+    pub fn set_segment_icon(&self, index: usize, bytes: &[u8]) {
+        if !is_supported_icon(bytes) {
+            warn!("Segment icon is not a PNG or JPEG image, ignoring it");
+            return;
+        }
+        if bytes.len() > MAX_ICON_BYTES {
+            warn!(
+                "Segment icon is {} bytes, larger than the {MAX_ICON_BYTES} bytes allowed",
+                bytes.len()
+            );
             return;
         }
 
-        let method = self.timing_method();
-        *run.segment_mut(index).best_segment_time_mut() = run
-            .segment_mut(index)
-            .best_segment_time_mut()
-            .with_timing_method(method, Some(TimeSpan::from_milliseconds(ms as f64)));
+        self.edit(|run| with_segment_icon(run, index, bytes));
+    }
+}
 
-        ctx.set_run(run);
+fn with_segment_name(mut run: Run, index: usize, name: String) -> Option<Run> {
+    if index >= run.segments().len() {
+        return None;
+    }
 
-        self.emit_run_changed();
+    run.segments_mut()[index].set_name(name);
+    Some(run)
+}
+
+fn with_segment_note(mut run: Run, index: usize, note: &str) -> Option<Run> {
+    if index >= run.segments().len() || segment_note(&run, index).unwrap_or_default() == note {
+        return None;
     }
 
-    /// Moves a given segment up/down by one position.
-    pub fn move_segment(&self, index: usize, direction: SegmentMoveDirection) {
-        let ctx = TuxSplitContext::get_instance();
+    set_segment_note(&mut run, index, note);
+    Some(run)
+}
 
-        let mut run_editor = RunEditor::new(ctx.get_run().to_owned()).ok().unwrap();
-        run_editor.select_only(index);
+fn with_split_time_ms(run: Run, index: usize, ms: i64, method: TimingMethod) -> Option<Run> {
+    if ms < 0 {
+        return None;
+    }
+
+    let mut run_editor = open_run_editor(run)?;
+    if index >= run_editor.run().segments().len() {
+        return None;
+    }
+
+    run_editor.select_additionally(index);
+    run_editor.select_timing_method(method);
+    run_editor.active_segment().set_comparison_time(
+        "Personal Best",
+        Some(TimeSpan::from_milliseconds(ms as f64)),
+    );
+    run_editor.unselect(index);
+
+    Some(run_editor.close())
+}
+
+fn with_segment_time_ms(run: Run, index: usize, ms: i64, method: TimingMethod) -> Option<Run> {
+    if ms < 0 {
+        return None;
+    }
+
+    let mut run_editor = open_run_editor(run)?;
+    if index >= run_editor.run().segments().len() {
+        return None;
+    }
+
+    run_editor.select_additionally(index);
+    run_editor.select_timing_method(method);
+    run_editor
+        .active_segment()
+        .set_segment_time(Some(TimeSpan::from_milliseconds(ms as f64)));
+    run_editor.unselect(index);
+
+    Some(run_editor.close())
+}
+
+fn with_best_time_ms(mut run: Run, index: usize, ms: i64, method: TimingMethod) -> Option<Run> {
+    if ms < 0 || index >= run.segments().len() {
+        return None;
+    }
+
+    *run.segment_mut(index).best_segment_time_mut() = run
+        .segment_mut(index)
+        .best_segment_time_mut()
+        .with_timing_method(method, Some(TimeSpan::from_milliseconds(ms as f64)));
+
+    Some(run)
+}
 
-        match direction {
-            SegmentMoveDirection::Up => {
-                if run_editor.can_move_segments_up() {
-                    run_editor.move_segments_up();
-                } else {
-                    return;
-                }
+fn with_segment_moved(run: Run, index: usize, direction: SegmentMoveDirection) -> Option<Run> {
+    let mut run_editor = open_run_editor(run)?;
+    let mut notes = segment_notes(run_editor.run());
+    run_editor.select_only(index);
+
+    match direction {
+        SegmentMoveDirection::Up => {
+            if !run_editor.can_move_segments_up() {
+                return None;
             }
-            SegmentMoveDirection::Down => {
-                if run_editor.can_move_segments_down() {
-                    run_editor.move_segments_down();
-                } else {
-                    return;
-                }
+            run_editor.move_segments_up();
+            notes.swap(index, index - 1);
+        }
+        SegmentMoveDirection::Down => {
+            if !run_editor.can_move_segments_down() {
+                return None;
             }
+            run_editor.move_segments_down();
+            notes.swap(index, index + 1);
         }
+    }
 
-        ctx.set_run(run_editor.close());
+    Some(with_notes(run_editor.close(), &notes))
+}
 
-        self.emit_run_changed();
+fn with_segment_reordered(run: Run, from: usize, to: usize) -> Option<Run> {
+    if from == to || from >= run.len() || to >= run.len() {
+        return None;
     }
+    let mut notes = segment_notes(&run);
+    let mut run_editor = open_run_editor(run)?;
+    run_editor.select_only(from);
 
-    pub fn add_segment(&self, index: usize, direction: SegmentMoveDirection) {
-        let ctx = TuxSplitContext::get_instance();
+    for _ in 0..from.abs_diff(to) {
+        if from > to {
+            run_editor.move_segments_up();
+        } else {
+            run_editor.move_segments_down();
+        }
+    }
+    let note = notes.remove(from);
+    notes.insert(to, note);
 
-        let mut run_editor = RunEditor::new(ctx.get_run().to_owned()).ok().unwrap();
-        run_editor.select_only(index);
+    Some(with_notes(run_editor.close(), &notes))
+}
 
-        match direction {
-            SegmentMoveDirection::Up => {
-                run_editor.insert_segment_above();
-            }
-            SegmentMoveDirection::Down => {
-                run_editor.insert_segment_below();
-            }
+fn with_segment_added(run: Run, index: usize, direction: SegmentMoveDirection) -> Option<Run> {
+    let mut run_editor = open_run_editor(run)?;
+    let mut notes = segment_notes(run_editor.run());
+    run_editor.select_only(index);
+
+    match direction {
+        SegmentMoveDirection::Up => {
+            run_editor.insert_segment_above();
+            notes.insert(index.min(notes.len()), String::new());
         }
+        SegmentMoveDirection::Down => {
+            run_editor.insert_segment_below();
+            notes.insert((index + 1).min(notes.len()), String::new());
+        }
+    }
 
-        ctx.set_run(run_editor.close());
+    Some(with_notes(run_editor.close(), &notes))
+}
 
-        self.emit_run_changed();
+fn with_segment_removed(run: Run, index: usize) -> Option<Run> {
+    let mut run_editor = open_run_editor(run)?;
+    let mut notes = segment_notes(run_editor.run());
+    run_editor.select_only(index);
+
+    if !run_editor.can_remove_segments() {
+        return None;
     }
+    run_editor.remove_segments();
+    notes.remove(index);
 
-    pub fn remove_segment(&self, index: usize) {
-        let ctx = TuxSplitContext::get_instance();
+    Some(with_notes(run_editor.close(), &notes))
+}
+
+fn with_segment_duplicated(run: Run, index: usize) -> Option<Run> {
+    let source = run.segments().get(index)?;
+    let name = source.name().to_owned();
+    let best = source.best_segment_time();
+    let segment_times = [TimingMethod::RealTime, TimingMethod::GameTime]
+        .map(|method| (method, pb_segment_time(&run, index, method)));
+
+    let mut notes = segment_notes(&run);
+    notes.insert(index + 1, notes[index].clone());
+    let mut run_editor = open_run_editor(run)?;
+    run_editor.select_only(index);
+    run_editor.insert_segment_below();
+
+    // The inserted segment is now the active one
+    run_editor.active_segment().set_name(name);
+    for (method, segment_time) in segment_times {
+        run_editor.select_timing_method(method);
+        run_editor.active_segment().set_segment_time(segment_time);
+        run_editor
+            .active_segment()
+            .set_best_segment_time(best[method]);
+    }
+
+    Some(with_notes(run_editor.close(), &notes))
+}
 
-        let mut run_editor = RunEditor::new(ctx.get_run()).ok().unwrap();
+fn with_segment_times_cleared(mut run: Run, index: usize) -> Option<Run> {
+    if index >= run.segments().len() {
+        return None;
+    }
+
+    let segment = run.segment_mut(index);
+    segment.set_personal_best_split_time(Time::default());
+    *segment.best_segment_time_mut() = Time::default();
+
+    Some(run)
+}
+
+fn with_split_times_shifted(run: Run, delta_ms: i64, method: TimingMethod) -> Option<Run> {
+    if delta_ms == 0 {
+        return None;
+    }
+
+    let mut run_editor = open_run_editor(run)?;
+    let delta = TimeSpan::from_milliseconds(delta_ms as f64);
+    let shifted: Vec<(usize, TimeSpan)> = run_editor
+        .run()
+        .segments()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, segment)| {
+            let split = segment.personal_best_split_time()[method]?;
+            Some((index, split + delta))
+        })
+        .collect();
+    // Fixing the splits would turn a zero segment into a zero gold
+    if shifted.iter().any(|&(_, time)| time <= TimeSpan::zero()) {
+        warn!("Shifting the split times by {delta_ms} ms would zero a segment, ignoring it");
+        return None;
+    }
+
+    run_editor.select_timing_method(method);
+    // Edit in the direction of the shift, so no split passes a not yet shifted one
+    let ordered: Box<dyn Iterator<Item = &(usize, TimeSpan)>> = if delta_ms > 0 {
+        Box::new(shifted.iter().rev())
+    } else {
+        Box::new(shifted.iter())
+    };
+    for &(index, time) in ordered {
         run_editor.select_only(index);
+        run_editor
+            .active_segment()
+            .set_comparison_time("Personal Best", Some(time));
+    }
 
-        if run_editor.can_remove_segments() {
-            run_editor.remove_segments();
-        } else {
-            return;
-        }
+    Some(run_editor.close())
+}
 
-        ctx.set_run(run_editor.close());
+fn with_segment_icon(run: Run, index: usize, bytes: &[u8]) -> Option<Run> {
+    let mut run_editor = open_run_editor(run)?;
+    if index >= run_editor.run().segments().len() {
+        return None;
+    }
 
-        self.emit_run_changed();
+    // Drop the decoded old icon, unless another segment still shows it
+    let segments = run_editor.run().segments();
+    let old_icon = segments[index].icon().data();
+    if segments
+        .iter()
+        .filter(|segment| segment.icon().data() == old_icon)
+        .count()
+        == 1
+    {
+        forget_segment_icon(old_icon);
+    }
+
+    run_editor.select_only(index);
+    run_editor
+        .active_segment()
+        .set_icon(Image::new(bytes.into(), ImageType::Icon));
+
+    Some(run_editor.close())
+}
+
+/// Whether `bytes` start like a PNG or JPEG file.
+fn is_supported_icon(bytes: &[u8]) -> bool {
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF];
+    bytes.starts_with(PNG) || bytes.starts_with(JPEG)
+}
+
+/// Personal Best segment time of the segment at `index`, `None` when it or the
+/// previous split has no time.
+fn pb_segment_time(run: &Run, index: usize, method: TimingMethod) -> Option<TimeSpan> {
+    let split_time = run.segment(index).personal_best_split_time()[method]?;
+    let previous = if index == 0 {
+        TimeSpan::zero()
+    } else {
+        run.segment(index - 1).personal_best_split_time()[method]?
+    };
+    Some(split_time - previous)
+}
+
+/// Pushes a snapshot, dropping the oldest one once `UNDO_DEPTH` is reached.
+fn push_capped(stack: &mut Vec<Run>, run: Run) {
+    if stack.len() >= UNDO_DEPTH {
+        stack.remove(0);
+    }
+    stack.push(run);
+}
+
+/// Notes are stored by segment position, so they have to follow moved segments.
+fn with_notes(mut run: Run, notes: &[String]) -> Run {
+    set_segment_notes(&mut run, notes);
+    run
+}
+
+/// Opens a `RunEditor` on `run`, logging instead of panicking when the run is
+/// rejected (e.g. it momentarily has no segments while being edited).
+fn open_run_editor(run: Run) -> Option<RunEditor> {
+    match RunEditor::new(run) {
+        Ok(editor) => Some(editor),
+        Err(err) => {
+            warn!("Cannot edit run: {err:?}");
+            None
+        }
     }
 }
 
@@ -496,6 +797,402 @@ mod tests {
         }
     }
 
+    #[test]
+    fn open_run_editor_rejects_zero_segment_run() {
+        assert!(open_run_editor(Run::new()).is_none());
+
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        assert!(open_run_editor(run).is_some());
+    }
+
+    #[test]
+    fn setters_do_not_panic_on_zero_segment_run() {
+        // The timer refuses empty runs, so the edits behind every setter are fed one
+        // directly; none of them may panic or produce a run to commit.
+        let method = TimingMethod::RealTime;
+        let png = b"\x89PNG\r\n\x1a\n";
+
+        assert!(with_segment_name(Run::new(), 0, "Nope".to_owned()).is_none());
+        assert!(with_segment_note(Run::new(), 0, "Nope").is_none());
+        assert!(with_split_time_ms(Run::new(), 0, 1000, method).is_none());
+        assert!(with_segment_time_ms(Run::new(), 0, 1000, method).is_none());
+        assert!(with_best_time_ms(Run::new(), 0, 1000, method).is_none());
+        assert!(with_segment_moved(Run::new(), 0, SegmentMoveDirection::Up).is_none());
+        assert!(with_segment_moved(Run::new(), 0, SegmentMoveDirection::Down).is_none());
+        assert!(with_segment_reordered(Run::new(), 0, 1).is_none());
+        assert!(with_segment_added(Run::new(), 0, SegmentMoveDirection::Up).is_none());
+        assert!(with_segment_added(Run::new(), 0, SegmentMoveDirection::Down).is_none());
+        assert!(with_segment_removed(Run::new(), 0).is_none());
+        assert!(with_segment_duplicated(Run::new(), 0).is_none());
+        assert!(with_segment_times_cleared(Run::new(), 0).is_none());
+        assert!(with_split_times_shifted(Run::new(), 1000, method).is_none());
+        assert!(with_segment_icon(Run::new(), 0, png).is_none());
+    }
+
+    #[test]
+    fn reorder_segment_moves_in_both_directions_with_one_signal() {
+        {
+            let mut run = Run::new();
+            for name in ["A", "B", "C", "D"] {
+                run.push_segment(Segment::new(name));
+            }
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        let count = Rc::new(Cell::new(0));
+        let c2 = count.clone();
+        ctx.connect_local("run-changed", false, move |_v| {
+            c2.set(c2.get() + 1);
+            None
+        });
+        let names = || {
+            TuxSplitContext::get_instance()
+                .get_run()
+                .segments()
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        ctx.reorder_segment(0, 2);
+        assert_eq!(names(), ["B", "C", "A", "D"]);
+        assert_eq!(count.get(), 1);
+
+        ctx.reorder_segment(3, 0);
+        assert_eq!(names(), ["D", "B", "C", "A"]);
+        assert_eq!(count.get(), 2);
+
+        // Onto itself and out of bounds are no-ops
+        ctx.reorder_segment(1, 1);
+        ctx.reorder_segment(1, 4);
+        assert_eq!(names(), ["D", "B", "C", "A"]);
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn notes_follow_their_segments() {
+        {
+            let mut run = Run::new();
+            for name in ["A", "B", "C"] {
+                run.push_segment(Segment::new(name));
+            }
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        let note = |index| {
+            segment_note(&TuxSplitContext::get_instance().get_run(), index).map(str::to_owned)
+        };
+
+        ctx.set_segment_note(0, "Jump early".to_owned());
+        assert_eq!(note(0).as_deref(), Some("Jump early"));
+        // Setting the same note again is not an edit
+        ctx.set_segment_note(0, "Jump early".to_owned());
+        ctx.undo();
+        assert_eq!(note(0), None);
+        ctx.redo();
+
+        ctx.reorder_segment(0, 2);
+        assert_eq!(note(0), None);
+        assert_eq!(note(2).as_deref(), Some("Jump early"));
+
+        ctx.add_segment(0, SegmentMoveDirection::Up);
+        assert_eq!(note(3).as_deref(), Some("Jump early"));
+
+        ctx.remove_segment(0);
+        ctx.move_segment(2, SegmentMoveDirection::Up);
+        assert_eq!(note(1).as_deref(), Some("Jump early"));
+
+        ctx.set_segment_note(1, String::new());
+        assert_eq!(note(1), None);
+    }
+
+    #[test]
+    fn duplicate_last_segment_copies_name_and_times() {
+        let ms = |v: f64| TimeSpan::from_milliseconds(v);
+        {
+            let mut run = Run::new();
+            let mut a = Segment::new("A");
+            a.set_personal_best_split_time(
+                Time::new()
+                    .with_real_time(Some(ms(1000.0)))
+                    .with_game_time(Some(ms(900.0))),
+            );
+            let mut b = Segment::new("B");
+            b.set_personal_best_split_time(
+                Time::new()
+                    .with_real_time(Some(ms(3000.0)))
+                    .with_game_time(Some(ms(2500.0))),
+            );
+            *b.best_segment_time_mut() = Time::new()
+                .with_real_time(Some(ms(1800.0)))
+                .with_game_time(Some(ms(1500.0)));
+            run.push_segment(a);
+            run.push_segment(b);
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+
+        ctx.duplicate_segment(1);
+
+        let run = TuxSplitContext::get_instance().get_run();
+        assert_eq!(run.len(), 3);
+        let copy = run.segment(2);
+        assert_eq!(copy.name(), "B");
+        assert_eq!(copy.best_segment_time(), run.segment(1).best_segment_time());
+        for method in [TimingMethod::RealTime, TimingMethod::GameTime] {
+            assert_eq!(
+                pb_segment_time(&run, 2, method),
+                pb_segment_time(&run, 1, method)
+            );
+        }
+        assert_eq!(copy.personal_best_split_time().real_time, Some(ms(5000.0)));
+    }
+
+    #[test]
+    fn clear_segment_times_resets_pb_and_best_for_both_methods() {
+        let both = |rt: f64, gt: f64| {
+            Time::new()
+                .with_real_time(Some(TimeSpan::from_seconds(rt)))
+                .with_game_time(Some(TimeSpan::from_seconds(gt)))
+        };
+        {
+            let mut run = Run::new();
+            let mut a = Segment::new("A");
+            a.set_personal_best_split_time(both(10.0, 9.0));
+            *a.best_segment_time_mut() = both(8.0, 7.0);
+            run.push_segment(a);
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+
+        ctx.clear_segment_times(0);
+
+        let run = TuxSplitContext::get_instance().get_run();
+        let seg = run.segment(0);
+        assert_eq!(seg.name(), "A");
+        assert_eq!(seg.personal_best_split_time().real_time, None);
+        assert_eq!(seg.personal_best_split_time().game_time, None);
+        assert_eq!(seg.best_segment_time().real_time, None);
+        assert_eq!(seg.best_segment_time().game_time, None);
+    }
+
+    #[test]
+    fn set_segment_icon_accepts_png_and_rejects_others() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("A"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        let icon_len = || {
+            TuxSplitContext::get_instance()
+                .get_run()
+                .segment(0)
+                .icon()
+                .data()
+                .len()
+        };
+
+        ctx.set_segment_icon(0, b"GIF89a not supported");
+        assert_eq!(icon_len(), 0);
+
+        let mut oversized = b"\x89PNG\r\n\x1a\n".to_vec();
+        oversized.resize(MAX_ICON_BYTES + 1, 0);
+        ctx.set_segment_icon(0, &oversized);
+        assert_eq!(icon_len(), 0);
+        assert!(!ctx.can_undo());
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend_from_slice(&[0; 32]);
+        ctx.set_segment_icon(0, &png);
+        assert!(icon_len() > 0);
+    }
+
+    #[test]
+    fn supported_icon_formats() {
+        assert!(is_supported_icon(b"\x89PNG\r\n\x1a\nrest"));
+        assert!(is_supported_icon(&[0xFF, 0xD8, 0xFF, 0xE0]));
+        assert!(!is_supported_icon(b""));
+        assert!(!is_supported_icon(b"<svg></svg>"));
+    }
+
+    #[test]
+    fn shift_all_split_times_moves_every_split_and_keeps_segments() {
+        {
+            let mut run = Run::new();
+            for (name, ms) in [("A", 1000.0), ("B", 3000.0)] {
+                let mut segment = Segment::new(name);
+                segment.set_personal_best_split_time(
+                    Time::new().with_real_time(Some(TimeSpan::from_milliseconds(ms))),
+                );
+                run.push_segment(segment);
+            }
+            run.push_segment(Segment::new("No time"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        let count = Rc::new(Cell::new(0));
+        let c2 = count.clone();
+        ctx.connect_local("run-changed", false, move |_v| {
+            c2.set(c2.get() + 1);
+            None
+        });
+        let splits = || {
+            let run = TuxSplitContext::get_instance().get_run();
+            run.segments()
+                .iter()
+                .map(|s| {
+                    s.personal_best_split_time()
+                        .real_time
+                        .map(|t| t.to_duration().whole_milliseconds())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(ctx.shift_all_split_times(500));
+        assert_eq!(splits(), vec![Some(1500), Some(3500), None]);
+        assert_eq!(count.get(), 1);
+
+        // The first split would reach zero: nothing moves
+        assert!(!ctx.shift_all_split_times(-1500));
+        assert_eq!(splits(), vec![Some(1500), Some(3500), None]);
+        assert_eq!(count.get(), 1);
+
+        assert!(ctx.shift_all_split_times(-1000));
+        assert_eq!(splits(), vec![Some(500), Some(2500), None]);
+        assert_eq!(count.get(), 2);
+
+        // Zero is a no-op
+        assert!(!ctx.shift_all_split_times(0));
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn duplicate_out_of_bounds_is_ignored() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("A"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+
+        ctx.duplicate_segment(4);
+        assert_eq!(TuxSplitContext::get_instance().get_run().len(), 1);
+        assert!(!ctx.can_undo());
+    }
+
+    #[test]
+    fn undo_and_redo_restore_snapshots() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("A"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        let name = || {
+            TuxSplitContext::get_instance().get_run().segments()[0]
+                .name()
+                .to_owned()
+        };
+
+        assert!(!ctx.can_undo());
+        ctx.set_segment_name(0, "B".to_owned());
+        ctx.set_segment_name(0, "C".to_owned());
+        assert!(ctx.can_undo());
+
+        ctx.undo();
+        assert_eq!(name(), "B");
+        ctx.undo();
+        assert_eq!(name(), "A");
+        assert!(!ctx.can_undo());
+
+        // Nothing left: no change
+        ctx.undo();
+        assert_eq!(name(), "A");
+
+        ctx.redo();
+        assert_eq!(name(), "B");
+        assert!(ctx.can_redo());
+
+        // A new edit drops the redo history
+        ctx.set_segment_name(0, "D".to_owned());
+        assert!(!ctx.can_redo());
+        ctx.redo();
+        assert_eq!(name(), "D");
+    }
+
+    #[test]
+    fn undo_restores_removed_segment_and_emits() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("A"));
+            run.push_segment(Segment::new("B"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+
+        let count = Rc::new(Cell::new(0));
+        let c2 = count.clone();
+        ctx.connect_local("run-changed", false, move |_v| {
+            c2.set(c2.get() + 1);
+            None
+        });
+
+        ctx.remove_segment(0);
+        assert_eq!(TuxSplitContext::get_instance().get_run().len(), 1);
+
+        ctx.undo();
+        let run = TuxSplitContext::get_instance().get_run();
+        assert_eq!(run.len(), 2);
+        assert_eq!(run.segments()[0].name(), "A");
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    fn rejected_commit_keeps_the_undo_history() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("A"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+        ctx.set_segment_name(0, "B".to_owned());
+        ctx.undo();
+        assert!(ctx.can_redo());
+
+        // The timer refuses a run without segments
+        ctx.commit_run(Run::new());
+        assert!(!ctx.can_undo());
+        assert!(ctx.can_redo());
+        assert_eq!(TuxSplitContext::get_instance().get_run().len(), 1);
+    }
+
+    #[test]
+    fn undo_stack_is_capped() {
+        {
+            let mut run = Run::new();
+            run.push_segment(Segment::new("0"));
+            TuxSplitContext::get_instance().set_run(run);
+        }
+        let ctx = EditorContext::new();
+
+        for i in 1..=UNDO_DEPTH + 5 {
+            ctx.set_segment_name(0, i.to_string());
+        }
+        let mut undone = 0;
+        while ctx.can_undo() {
+            ctx.undo();
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_DEPTH);
+        // The oldest snapshots were dropped
+        assert_eq!(
+            TuxSplitContext::get_instance().get_run().segments()[0].name(),
+            "5"
+        );
+    }
+
     #[test]
     fn run_changed_signal_emitted_on_successful_mutations_only() {
         {
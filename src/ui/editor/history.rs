@@ -0,0 +1,226 @@
+use adw::prelude::*;
+use adw::{ActionRow, PreferencesGroup, PreferencesPage};
+use glib::SignalHandlerId;
+use gtk4::{Align, Button, ListBox, SelectionMode};
+use livesplit_core::{Run, RunEditor, Time, TimingMethod};
+use tracing::{debug, warn};
+
+use crate::context::TuxSplitContext;
+use crate::ui::editor::EditorContext;
+
+/// An attempt of the run as listed in the History page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttemptEntry {
+    pub index: i32,
+    /// Final time, `None` for attempts that were reset before the end.
+    pub time: Option<livesplit_core::TimeSpan>,
+    /// When the attempt started, as a Unix timestamp.
+    pub started: Option<i64>,
+}
+
+/// The attempts of the run, newest first.
+pub fn attempt_entries(run: &Run, method: TimingMethod) -> Vec<AttemptEntry> {
+    let mut entries: Vec<AttemptEntry> = run
+        .attempt_history()
+        .iter()
+        .map(|attempt| AttemptEntry {
+            index: attempt.index(),
+            time: attempt.time()[method],
+            started: attempt
+                .started()
+                .map(|started| started.time.unix_timestamp()),
+        })
+        .collect();
+    entries.sort_by(|a, b| b.index.cmp(&a.index));
+    entries
+}
+
+/// Deletes an attempt and its segment times from the history. Returns false when the
+/// run has no attempt with that index. Personal Best and best segment times are kept.
+pub fn remove_attempt(run: &mut Run, index: i32) -> bool {
+    if !run.attempt_history().iter().any(|a| a.index() == index) {
+        return false;
+    }
+    let attempts: Vec<_> = run
+        .attempt_history()
+        .iter()
+        .filter(|a| a.index() != index)
+        .cloned()
+        .collect();
+    let segment_histories: Vec<Vec<(i32, Time)>> = run
+        .segments()
+        .iter()
+        .map(|segment| {
+            segment
+                .segment_history()
+                .iter()
+                .filter(|&&(id, _)| id != index)
+                .copied()
+                .collect()
+        })
+        .collect();
+
+    // The history can only be cleared as a whole, so rebuild it without the attempt
+    let Ok(mut editor) = RunEditor::new(run.clone()) else {
+        warn!("Could not open the run editor to delete attempt {index}");
+        return false;
+    };
+    editor.clear_history();
+    *run = editor.close();
+
+    for attempt in attempts {
+        run.add_attempt_with_index(
+            attempt.time(),
+            attempt.index(),
+            attempt.started(),
+            attempt.ended(),
+            attempt.pause_time(),
+        );
+    }
+    for (segment, history) in run.segments_mut().iter_mut().zip(segment_histories) {
+        for (id, time) in history {
+            segment.segment_history_mut().insert(id, time);
+        }
+    }
+    true
+}
+
+fn format_started(started: Option<i64>) -> String {
+    started
+        .and_then(|timestamp| glib::DateTime::from_unix_local(timestamp).ok())
+        .and_then(|date| date.format("%Y-%m-%d %H:%M").ok())
+        .map_or_else(|| "Unknown date".to_owned(), |date| date.to_string())
+}
+
+/// Page listing the past attempts, deleting one marks the run as edited. Also returns
+/// the global "run-changed" handler keeping the list current, to disconnect once the
+/// editor is closed.
+pub fn build_history_page(editor_ctx: &EditorContext) -> (PreferencesPage, SignalHandlerId) {
+    let page = PreferencesPage::builder().title("History").build();
+    let group = PreferencesGroup::builder()
+        .title("Attempts")
+        .description("Past attempts of the run, newest first")
+        .build();
+
+    let list = ListBox::builder()
+        .selection_mode(SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    fill_attempt_list(&list, editor_ctx);
+    group.add(&list);
+    page.add(&group);
+
+    let list_weak = list.downgrade();
+    let editor_ctx_weak = editor_ctx.downgrade();
+    let handler = TuxSplitContext::get_instance().connect_local("run-changed", false, move |_| {
+        if let Some(list) = list_weak.upgrade()
+            && let Some(editor_ctx) = editor_ctx_weak.upgrade()
+        {
+            fill_attempt_list(&list, &editor_ctx);
+        }
+        None
+    });
+
+    (page, handler)
+}
+
+fn fill_attempt_list(list: &ListBox, editor_ctx: &EditorContext) {
+    list.remove_all();
+
+    let ctx = TuxSplitContext::get_instance();
+    let (entries, format) = {
+        let timer = ctx.timer();
+        let timer = timer.read().unwrap();
+        let entries = attempt_entries(timer.run(), timer.current_timing_method());
+        (entries, ctx.config().format.timer.clone())
+    };
+
+    if entries.is_empty() {
+        list.append(&ActionRow::builder().title("No attempts yet").build());
+        return;
+    }
+
+    for entry in entries {
+        let title = entry
+            .time
+            .map_or_else(|| "Reset".to_owned(), |time| format.format_time_span(&time));
+        let row = ActionRow::builder()
+            .title(title)
+            .subtitle(format!(
+                "#{} · {}",
+                entry.index,
+                format_started(entry.started)
+            ))
+            .build();
+
+        let delete = Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Delete Attempt")
+            .valign(Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let index = entry.index;
+        let editor_ctx = editor_ctx.clone();
+        delete.connect_clicked(move |_| {
+            let mut run = TuxSplitContext::get_instance().get_run();
+            if remove_attempt(&mut run, index) {
+                debug!("Deleted attempt {index}");
+                editor_ctx.commit_run(run);
+            }
+        });
+        row.add_suffix(&delete);
+
+        list.append(&row);
+    }
+}
+
+#[cfg(test)]
+mod attempt_history_tests {
+    use super::*;
+    use livesplit_core::{Segment, TimeSpan};
+
+    fn time_rt(seconds: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)))
+    }
+
+    fn run_with_attempts() -> Run {
+        let mut run = Run::new();
+        let mut segment = Segment::new("End");
+        for id in 1..=3 {
+            segment
+                .segment_history_mut()
+                .insert(id, time_rt(f64::from(id)));
+        }
+        run.push_segment(segment);
+        run.add_attempt_with_index(time_rt(100.0), 1, None, None, None);
+        run.add_attempt_with_index(Time::new(), 2, None, None, None);
+        run.add_attempt_with_index(time_rt(90.0), 3, None, None, None);
+        run
+    }
+
+    #[test]
+    fn entries_are_newest_first() {
+        let entries = attempt_entries(&run_with_attempts(), TimingMethod::RealTime);
+        let indices: Vec<i32> = entries.iter().map(|e| e.index).collect();
+        assert_eq!(indices, vec![3, 2, 1]);
+        assert_eq!(entries[0].time, Some(TimeSpan::from_seconds(90.0)));
+        // Reset attempts have no final time
+        assert_eq!(entries[1].time, None);
+        assert_eq!(entries[2].started, None);
+    }
+
+    #[test]
+    fn removing_an_attempt_drops_its_segment_times() {
+        let mut run = run_with_attempts();
+        assert!(remove_attempt(&mut run, 2));
+
+        let indices: Vec<i32> = run.attempt_history().iter().map(|a| a.index()).collect();
+        assert_eq!(indices, vec![1, 3]);
+        let history = run.segment(0).segment_history();
+        assert!(history.get(2).is_none());
+        assert!(history.get(1).is_some());
+        assert!(history.get(3).is_some());
+
+        assert!(!remove_attempt(&mut run, 42));
+    }
+}
@@ -1,5 +1,6 @@
 mod action_bar;
 mod context;
+mod history;
 mod model;
 mod row;
 mod table;
@@ -7,21 +8,36 @@ pub use context::EditorContext;
 pub use model::SegmentsModel;
 
 use crate::context::TuxSplitContext;
+use crate::ui::editor::history::build_history_page;
 use crate::ui::editor::table::SegmentsEditor;
-use gtk4::{ActionBar, StringList};
-use livesplit_core::{Run, TimeSpan};
+use crate::ui::header::open_in_last_directory;
+use crate::utils::comparisons::{DEFAULT_PINNED_COMPARISON, pin_personal_best_comparison};
+use gtk4::{ActionBar, FileChooserDialog, FileFilter, Label, StringList};
+use livesplit_core::{Run, TimeSpan, Timer};
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
 use adw::prelude::*;
 use adw::{
-    ComboRow, EntryRow, HeaderBar, PreferencesGroup, PreferencesPage, ToolbarView, ViewStack,
-    ViewSwitcher, Window,
+    ActionRow, ButtonRow, ComboRow, EntryRow, HeaderBar, PreferencesGroup, PreferencesPage,
+    SpinRow, SwitchRow, ToolbarView, ViewStack, ViewSwitcher, Window,
 };
+use tracing::{debug, warn};
 
 #[derive(Clone)]
 pub struct SplitEditor {
     dialog: ToolbarView,
     run_snapshot: Arc<RwLock<Run>>,
+    // Edits since the last save/cancel of the action bar
+    dirty: Rc<Cell<bool>>,
+    // Shared by every page, so all edits land in the same undo history
+    editor_ctx: EditorContext,
+    // The editor's "run-changed" handler, disconnected once the editor is closed
+    run_changed_handler: Rc<Cell<Option<glib::SignalHandlerId>>>,
+    // The history page's global "run-changed" handler, disconnected along with it
+    history_handler: Rc<Cell<Option<glib::SignalHandlerId>>>,
 }
 
 impl SplitEditor {
@@ -38,6 +54,10 @@ impl SplitEditor {
         let this = Self {
             dialog,
             run_snapshot,
+            dirty: Rc::new(Cell::new(false)),
+            editor_ctx: EditorContext::new(),
+            run_changed_handler: Rc::new(Cell::new(None)),
+            history_handler: Rc::new(Cell::new(None)),
         };
 
         let run_info = this.build_run_info_page();
@@ -50,6 +70,11 @@ impl SplitEditor {
         content
             .add_titled(&segment_editor, None, "Segments")
             .set_icon_name(Some("view-list-symbolic"));
+        let (history, history_handler) = build_history_page(&this.editor_ctx);
+        this.history_handler.set(Some(history_handler));
+        content
+            .add_titled(&history, None, "History")
+            .set_icon_name(Some("document-open-recent-symbolic"));
 
         let headerbar = HeaderBar::builder().show_end_title_buttons(true).build();
         let switcher = ViewSwitcher::builder()
@@ -66,15 +91,20 @@ impl SplitEditor {
         this.dialog.set_bottom_bar_style(adw::ToolbarStyle::Raised);
         this.dialog.set_extend_content_to_bottom_edge(true); // Content below action bar
 
-        // Call show Cancel/save on run-changed
+        // Show Cancel/Save once the editor commits an edit, other run changes (a
+        // finished attempt, discarding) aren't edits
         let action_bar_binding = action_bar.clone();
-        ctx.connect_local("run-changed", false, move |_| {
-            if !action_bar_binding.is_revealed() {
-                // cancel will trigger run-changed
-                action_bar_binding.set_revealed(true);
-            }
-            None
-        });
+        let dirty_binding = this.dirty.clone();
+        let handler = this
+            .editor_ctx
+            .connect_local("run-changed", false, move |_| {
+                dirty_binding.set(true);
+                if !action_bar_binding.is_revealed() {
+                    action_bar_binding.set_revealed(true);
+                }
+                None
+            });
+        this.run_changed_handler.set(Some(handler));
 
         this
     }
@@ -89,9 +119,82 @@ impl SplitEditor {
             .width_request(800) // Arbitrary I know
             .build();
         window.set_content(Some(self.dialog()));
+        let run_snapshot = self.run_snapshot.clone();
+        let dirty = self.dirty.clone();
+        let editor_ctx = self.editor_ctx.clone();
+        let run_changed_handler = self.run_changed_handler.clone();
+        let history_handler = self.history_handler.clone();
+        window.connect_close_request(move |window| {
+            if dirty.get() {
+                Self::present_unsaved_dialog(window, &run_snapshot, &dirty);
+                return glib::Propagation::Stop;
+            }
+            if let Some(handler) = run_changed_handler.take() {
+                editor_ctx.disconnect(handler);
+            }
+            if let Some(handler) = history_handler.take() {
+                TuxSplitContext::get_instance().disconnect(handler);
+            }
+            // Only a closed editor clears the flag, quitting with it open keeps it set
+            if let Ok(mut cfg) = TuxSplitContext::get_instance().config_mut() {
+                cfg.window.editor_open = false;
+            }
+            glib::Propagation::Proceed
+        });
+        if let Ok(mut cfg) = TuxSplitContext::get_instance().config_mut() {
+            cfg.window.editor_open = true;
+        }
         window.present();
     }
 
+    fn keep_edits(run_snapshot: &Arc<RwLock<Run>>, dirty: &Cell<bool>) {
+        if let Ok(mut snapshot) = run_snapshot.try_write() {
+            *snapshot = TuxSplitContext::get_instance().get_run();
+        }
+        dirty.set(false);
+    }
+
+    fn discard_edits(run_snapshot: &Arc<RwLock<Run>>, dirty: &Cell<bool>) {
+        // Not an editor commit, so it doesn't mark the editor dirty again
+        TuxSplitContext::get_instance().set_run(run_snapshot.read().unwrap().clone());
+        dirty.set(false);
+    }
+
+    /// Asks whether to keep or discard unsaved edits before the editor closes.
+    fn present_unsaved_dialog(
+        window: &Window,
+        run_snapshot: &Arc<RwLock<Run>>,
+        dirty: &Rc<Cell<bool>>,
+    ) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Save Changes?")
+            .body("The splits were edited. Keep the changes or discard them?")
+            .default_response("save")
+            .close_response("cancel")
+            .build();
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("discard", "Discard");
+        dialog.add_response("save", "Save");
+        dialog.set_response_appearance("discard", adw::ResponseAppearance::Destructive);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+
+        let window_weak = window.downgrade();
+        let run_snapshot = run_snapshot.clone();
+        let dirty = dirty.clone();
+        dialog.connect_response(None, move |_, response| {
+            match response {
+                "save" => Self::keep_edits(&run_snapshot, &dirty),
+                "discard" => Self::discard_edits(&run_snapshot, &dirty),
+                _ => return,
+            }
+            if let Some(window) = window_weak.upgrade() {
+                window.close();
+            }
+        });
+
+        dialog.present(Some(window));
+    }
+
     fn build_cancel_banner(&self) -> ActionBar {
         let action_bar = ActionBar::builder()
             .css_classes(["undershoot-top", "undershoot-bottom"])
@@ -114,19 +217,19 @@ impl SplitEditor {
 
         // Connect save button
         let snapshot_binding = self.run_snapshot.clone();
+        let dirty_binding = self.dirty.clone();
         let action_bar_binding = action_bar.clone();
         save_button.connect_clicked(move |_| {
-            if let Ok(mut snapshot) = snapshot_binding.try_write() {
-                *snapshot = TuxSplitContext::get_instance().get_run();
-            }
+            Self::keep_edits(&snapshot_binding, &dirty_binding);
             action_bar_binding.set_revealed(false);
         });
 
         // Connect cancel button
         let snapshot_binding = Arc::clone(&self.run_snapshot);
+        let dirty_binding = self.dirty.clone();
         let action_bar_binding = action_bar.clone();
         cancel_button.connect_clicked(move |_| {
-            TuxSplitContext::get_instance().set_run(snapshot_binding.read().unwrap().clone());
+            Self::discard_edits(&snapshot_binding, &dirty_binding);
             action_bar_binding.set_revealed(false);
         });
 
@@ -141,11 +244,13 @@ impl SplitEditor {
 
         let run_info_group = self.build_run_info_preferences();
         let timer_group = self.build_timer_preferences();
-        // let autosplit_group = self.build_autosplit_preferences();
+        let history_group = self.build_history_preferences();
+        let autosplit_group = Self::build_autosplit_preferences();
 
         page.add(&run_info_group);
         page.add(&timer_group);
-        // page.add(&autosplit_group);
+        page.add(&history_group);
+        page.add(&autosplit_group);
 
         page
     }
@@ -167,6 +272,7 @@ impl SplitEditor {
             .build();
 
         {
+            let editor_ctx = self.editor_ctx.clone();
             name.connect_text_notify(move |entry| {
                 let new_name = entry.text().to_string();
                 let ctx = TuxSplitContext::get_instance();
@@ -175,10 +281,11 @@ impl SplitEditor {
 
                 run.set_game_name(new_name);
 
-                ctx.set_run(run);
+                editor_ctx.commit_run(run);
             });
         }
         {
+            let editor_ctx = self.editor_ctx.clone();
             category.connect_text_notify(move |entry| {
                 let new_category = entry.text().to_string();
                 let ctx = TuxSplitContext::get_instance();
@@ -187,7 +294,7 @@ impl SplitEditor {
 
                 run.set_category_name(new_category);
 
-                ctx.set_run(run);
+                editor_ctx.commit_run(run);
             });
         }
 
@@ -226,7 +333,9 @@ impl SplitEditor {
             .model(&options)
             .selected(initial_method)
             .build();
+        let comparison = Self::build_comparison_row(&timer);
 
+        let editor_ctx = self.editor_ctx.clone();
         offset.connect_text_notify(move |entry| {
             // Offset must be a valid f64 value
             if entry.text().parse::<f64>().is_ok() {
@@ -239,7 +348,7 @@ impl SplitEditor {
 
                 run.set_offset(TimeSpan::from_seconds(new_offset));
 
-                ctx.set_run(run);
+                editor_ctx.commit_run(run);
             } else {
                 entry.set_title("Start at (entry must be a valid number)");
                 entry.add_css_class("error");
@@ -257,19 +366,288 @@ impl SplitEditor {
                     _ => (),
                 }
                 drop(t);
-                ctx.emit_by_name::<()>("run-changed", &[]);
+                ctx.emit_run_changed();
             }
         });
 
+        let pin_row = ButtonRow::builder()
+            .title("Pin Personal Best as Comparison")
+            .build();
+        let editor_ctx = self.editor_ctx.clone();
+        pin_row.connect_activated(move |row| Self::present_pin_comparison_dialog(row, &editor_ctx));
+
         group.add(&offset);
         group.add(&timing_method);
+        group.add(&comparison);
+        group.add(&pin_row);
 
         group
     }
 
-    fn build_autosplit_preferences(&self) -> PreferencesGroup {
-        // Logic to create autosplitter preferences UI component
-        unimplemented!()
+    fn build_comparison_row(timer: &Timer) -> ComboRow {
+        let row = ComboRow::builder()
+            .title("Comparison")
+            .subtitle("Comparison shown by the timer")
+            .model(&comparison_model(timer.run()))
+            .selected(comparison_position(timer.run(), timer.current_comparison()))
+            .build();
+
+        // Set while the row follows the timer, so it doesn't write the selection back
+        let syncing = Rc::new(Cell::new(false));
+
+        let syncing_binding = syncing.clone();
+        row.connect_selected_notify(move |r| {
+            if syncing_binding.get() {
+                return;
+            }
+            if let Some(model) = r.model().and_downcast::<StringList>()
+                && let Some(name) = model.string(r.selected())
+            {
+                TuxSplitContext::get_instance().set_current_comparison(name.as_str());
+            }
+        });
+
+        // Follow comparison cycling from hotkeys and run edits (new custom comparisons)
+        let row_weak = row.downgrade();
+        let sync = move |_: &[gtk4::glib::Value]| {
+            if let Some(row) = row_weak.upgrade() {
+                let timer = TuxSplitContext::get_instance().timer();
+                let timer = timer.read().unwrap();
+                let model = comparison_model(timer.run());
+                let position = comparison_position(timer.run(), timer.current_comparison());
+                drop(timer);
+                syncing.set(true);
+                row.set_model(Some(&model));
+                row.set_selected(position);
+                syncing.set(false);
+            }
+            None
+        };
+        let ctx = TuxSplitContext::get_instance();
+        let handlers = Cell::new(Some([
+            ctx.connect_local("comparison-changed", false, sync.clone()),
+            ctx.connect_local("run-changed", false, sync),
+        ]));
+        // The context outlives the editor, so its handlers go away with the row
+        row.connect_destroy(move |_| {
+            for handler in handlers.take().into_iter().flatten() {
+                TuxSplitContext::get_instance().disconnect(handler);
+            }
+        });
+
+        row
+    }
+
+    /// Asks for a name and adds the current Personal Best as a custom comparison,
+    /// which then becomes the selected one.
+    fn present_pin_comparison_dialog(parent: &ButtonRow, editor_ctx: &EditorContext) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Pin Comparison")
+            .body("Keep the current Personal Best split times as a named comparison")
+            .default_response("pin")
+            .close_response("cancel")
+            .build();
+
+        let name_entry = gtk4::Entry::builder()
+            .placeholder_text(DEFAULT_PINNED_COMPARISON)
+            .activates_default(true)
+            .build();
+        dialog.set_extra_child(Some(&name_entry));
+
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("pin", "Pin");
+        dialog.set_response_appearance("pin", adw::ResponseAppearance::Suggested);
+
+        let editor_ctx = editor_ctx.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "pin" {
+                return;
+            }
+            let ctx = TuxSplitContext::get_instance();
+            let Some((run, name)) = pin_personal_best_comparison(ctx.get_run(), &name_entry.text())
+            else {
+                warn!("Could not pin the Personal Best as {:?}", name_entry.text());
+                return;
+            };
+            debug!("Pinned the Personal Best as comparison {name:?}");
+
+            editor_ctx.commit_run(run);
+            // Also stores it in the config, which applies it on the next launch
+            ctx.set_current_comparison(&name);
+        });
+
+        dialog.present(Some(parent));
+    }
+
+    fn build_history_preferences(&self) -> PreferencesGroup {
+        let group = PreferencesGroup::builder()
+            .title("History")
+            .description("Trim old attempts from the segment history to shrink the splits file")
+            .build();
+
+        let keep = TuxSplitContext::get_instance()
+            .config()
+            .general
+            .history_keep_attempts;
+        let keep_row = SpinRow::with_range(1.0, 10000.0, 1.0);
+        keep_row.set_title("Attempts to keep");
+        keep_row.set_value(keep as f64);
+        keep_row.connect_value_notify(|r| {
+            if let Ok(mut cfg) = TuxSplitContext::get_instance().config_mut() {
+                cfg.general.history_keep_attempts = r.value().round().max(1.0) as usize;
+            }
+        });
+
+        let trim_row = ButtonRow::builder()
+            .title("Trim Segment History")
+            .css_classes(["destructive-action"])
+            .build();
+        let editor_ctx = self.editor_ctx.clone();
+        trim_row.connect_activated(move |_| {
+            let ctx = TuxSplitContext::get_instance();
+            let keep = ctx.config().general.history_keep_attempts;
+
+            let mut run = ctx.get_run();
+            let removed = trim_segment_history(&mut run, keep);
+            debug!("Trimmed {removed} segment history entries");
+
+            if removed > 0 {
+                editor_ctx.commit_run(run);
+            }
+        });
+
+        group.add(&keep_row);
+        group.add(&trim_row);
+
+        group
+    }
+
+    fn build_autosplit_preferences() -> PreferencesGroup {
+        let group = PreferencesGroup::builder()
+            .title("Auto Splitter")
+            .description("Script splitting automatically by reading the game's memory")
+            .build();
+
+        let (path, enabled) = {
+            let cfg = TuxSplitContext::get_instance().config();
+            (
+                cfg.general.auto_splitter.clone(),
+                cfg.general.auto_splitter_enabled,
+            )
+        };
+
+        let enabled_row = SwitchRow::builder()
+            .title("Enable Auto Splitter")
+            .active(enabled)
+            .build();
+        let script_row = ActionRow::builder()
+            .title("Script")
+            .subtitle(script_subtitle(path.as_deref()))
+            .build();
+        let choose_button = gtk4::Button::builder()
+            .label("Choose…")
+            .valign(gtk4::Align::Center)
+            .build();
+        script_row.add_suffix(&choose_button);
+
+        // Load errors stay visible until the next successful load
+        let error_label = Label::builder()
+            .css_classes(["error"])
+            .wrap(true)
+            .xalign(0.0)
+            .visible(false)
+            .build();
+
+        let reload_row = ButtonRow::builder()
+            .title("Reload Script")
+            .sensitive(
+                TuxSplitContext::get_instance()
+                    .config()
+                    .active_auto_splitter()
+                    .is_some(),
+            )
+            .build();
+
+        // Replaces the running script, which drops all of its state
+        let reload = {
+            let error_label = error_label.clone();
+            let reload_row = reload_row.clone();
+            move || {
+                let ctx = TuxSplitContext::get_instance();
+                let result = ctx.reload_auto_splitter();
+                match &result {
+                    Ok(()) => error_label.set_visible(false),
+                    Err(err) => {
+                        error_label.set_label(&err.to_string());
+                        error_label.set_visible(true);
+                    }
+                }
+                reload_row.set_sensitive(ctx.config().active_auto_splitter().is_some());
+                result
+            }
+        };
+
+        let reload_binding = reload.clone();
+        enabled_row.connect_active_notify(move |r| {
+            if let Ok(mut cfg) = TuxSplitContext::get_instance().config_mut() {
+                cfg.general.auto_splitter_enabled = r.is_active();
+            }
+            let _ = reload_binding();
+        });
+
+        let reload_binding = reload.clone();
+        reload_row.connect_activated(move |_| {
+            let toast = match reload_binding() {
+                Ok(()) => "Auto splitter reloaded".to_owned(),
+                Err(err) => err.to_string(),
+            };
+            TuxSplitContext::get_instance().show_toast(&toast);
+        });
+
+        let script_row_binding = script_row.clone();
+        choose_button.connect_clicked(move |button| {
+            let parent = button.root().and_downcast::<gtk4::Window>();
+            let file_chooser = FileChooserDialog::new(
+                Some("Choose Auto Splitter"),
+                parent.as_ref(),
+                gtk4::FileChooserAction::Open,
+                &[
+                    ("Open", gtk4::ResponseType::Ok),
+                    ("Cancel", gtk4::ResponseType::Cancel),
+                ],
+            );
+            let filter = FileFilter::new();
+            filter.set_name(Some("Auto Splitters"));
+            filter.add_pattern("*.wasm");
+            file_chooser.add_filter(&filter);
+            open_in_last_directory(&file_chooser, &TuxSplitContext::get_instance().config());
+
+            let script_row = script_row_binding.clone();
+            let reload = reload.clone();
+            file_chooser.connect_response(move |dialog, response| {
+                if response == gtk4::ResponseType::Ok
+                    && let Some(path) = dialog.file().and_then(|file| file.path())
+                {
+                    if let Ok(mut cfg) = TuxSplitContext::get_instance().config_mut() {
+                        cfg.remember_directory(&path);
+                        cfg.general.auto_splitter = Some(path.clone());
+                    }
+                    script_row.set_subtitle(&script_subtitle(Some(&path)));
+                    let _ = reload();
+                }
+                dialog.destroy();
+            });
+
+            file_chooser.set_modal(true);
+            file_chooser.present();
+        });
+
+        group.add(&enabled_row);
+        group.add(&script_row);
+        group.add(&reload_row);
+        group.add(&error_label);
+
+        group
     }
 
     fn build_segment_editor_page(&self) -> PreferencesPage {
@@ -280,8 +658,7 @@ impl SplitEditor {
             .description("Edit your run segments")
             .build();
 
-        let editor_ctx = EditorContext::new();
-        let segment_editor = SegmentsEditor::new(editor_ctx);
+        let segment_editor = SegmentsEditor::new(self.editor_ctx.clone());
         group.add(segment_editor.container());
 
         page.add(&group);
@@ -289,3 +666,214 @@ impl SplitEditor {
         page
     }
 }
+
+/// Keeps only the history of the `keep` most recent attempts in every segment and
+/// returns how many entries were dropped. Entries with non-positive ids (not tied to
+/// an attempt, e.g. imported golds) are kept. Best segment times are not touched.
+fn trim_segment_history(run: &mut Run, keep: usize) -> usize {
+    let mut removed = 0;
+    for segment in run.segments_mut() {
+        let history = segment.segment_history_mut();
+        let mut ids: Vec<i32> = history
+            .iter()
+            .map(|&(id, _)| id)
+            .filter(|&id| id > 0)
+            .collect();
+        if ids.len() <= keep {
+            continue;
+        }
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        let oldest_kept = if keep == 0 { i32::MAX } else { ids[keep - 1] };
+
+        let before = history.iter().count();
+        history.retain(|&(id, _)| id <= 0 || id >= oldest_kept);
+        removed += before - history.iter().count();
+    }
+    removed
+}
+
+fn script_subtitle(path: Option<&Path>) -> String {
+    path.map_or_else(|| "None".to_owned(), |path| path.display().to_string())
+}
+
+/// Lists every comparison of the run (Personal Best, custom and generated ones).
+fn comparison_model(run: &Run) -> StringList {
+    let names: Vec<&str> = run.comparisons().collect();
+    StringList::new(&names)
+}
+
+fn comparison_position(run: &Run, comparison: &str) -> u32 {
+    run.comparisons()
+        .position(|name| name == comparison)
+        .unwrap_or(0) as u32
+}
+
+#[cfg(test)]
+mod history_trim_tests {
+    use super::trim_segment_history;
+    use livesplit_core::{Run, Segment, Time, TimeSpan};
+
+    fn time_rt(seconds: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)))
+    }
+
+    fn run_with_history(attempts: i32) -> Run {
+        let mut run = Run::new();
+        for name in ["A", "B"] {
+            let mut segment = Segment::new(name);
+            for id in 1..=attempts {
+                segment
+                    .segment_history_mut()
+                    .insert(id, time_rt(f64::from(id)));
+            }
+            // Not tied to an attempt
+            segment.segment_history_mut().insert(0, time_rt(1.0));
+            run.push_segment(segment);
+        }
+        run
+    }
+
+    #[test]
+    fn trims_each_segment_to_most_recent_attempts() {
+        let mut run = run_with_history(10);
+        let removed = trim_segment_history(&mut run, 3);
+        assert_eq!(removed, 14);
+
+        for segment in run.segments() {
+            let mut ids: Vec<i32> = segment
+                .segment_history()
+                .iter()
+                .map(|&(id, _)| id)
+                .filter(|&id| id > 0)
+                .collect();
+            ids.sort_unstable();
+            assert!(ids.len() <= 3);
+            assert_eq!(ids, vec![8, 9, 10]);
+            assert!(segment.segment_history().get(0).is_some());
+        }
+    }
+
+    #[test]
+    fn short_history_is_untouched() {
+        let mut run = run_with_history(2);
+        assert_eq!(trim_segment_history(&mut run, 5), 0);
+        assert_eq!(run.segment(0).segment_history().iter().count(), 3);
+    }
+}
+
+#[cfg(test)]
+mod comparison_row_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    #[gtk4::test]
+    fn comparison_model_lists_all_run_comparisons() {
+        gtk_test_init();
+
+        let mut run = Run::new();
+        run.push_segment(livesplit_core::Segment::new("S1"));
+        run.add_custom_comparison("Friend")
+            .expect("custom comparison");
+
+        let model = comparison_model(&run);
+        let listed: Vec<String> = (0..model.n_items())
+            .filter_map(|i| model.string(i))
+            .map(|s| s.to_string())
+            .collect();
+        let expected: Vec<String> = run.comparisons().map(ToOwned::to_owned).collect();
+
+        assert_eq!(listed, expected);
+        assert!(listed.iter().any(|c| c == "Personal Best"));
+        assert!(listed.iter().any(|c| c == "Friend"));
+    }
+
+    #[test]
+    fn comparison_position_falls_back_to_first() {
+        let mut run = Run::new();
+        run.push_segment(livesplit_core::Segment::new("S1"));
+
+        assert_eq!(comparison_position(&run, "Personal Best"), 0);
+        assert_eq!(comparison_position(&run, "Does not exist"), 0);
+        let best_segments = run
+            .comparisons()
+            .position(|c| c == "Best Segments")
+            .expect("generated comparison");
+        assert_eq!(
+            comparison_position(&run, "Best Segments"),
+            best_segments as u32
+        );
+    }
+}
+
+#[cfg(test)]
+mod dirty_tracking_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    fn run_named(name: &str) -> Run {
+        let mut run = Run::new();
+        run.push_segment(livesplit_core::Segment::new(name));
+        run
+    }
+
+    #[gtk4::test]
+    fn edits_mark_dirty_until_saved() {
+        gtk_test_init();
+        let ctx = TuxSplitContext::get_instance();
+        ctx.set_run(run_named("Before"));
+        let editor = SplitEditor::new();
+        assert!(!editor.dirty.get());
+
+        editor.editor_ctx.commit_run(run_named("Edited"));
+        assert!(editor.dirty.get());
+
+        SplitEditor::keep_edits(&editor.run_snapshot, &editor.dirty);
+        assert!(!editor.dirty.get());
+        assert_eq!(
+            editor.run_snapshot.read().unwrap().segment(0).name(),
+            "Edited"
+        );
+    }
+
+    #[gtk4::test]
+    fn discarding_restores_the_snapshot_and_clears_dirty() {
+        gtk_test_init();
+        let ctx = TuxSplitContext::get_instance();
+        ctx.set_run(run_named("Before"));
+        let editor = SplitEditor::new();
+
+        editor.editor_ctx.commit_run(run_named("Edited"));
+        SplitEditor::discard_edits(&editor.run_snapshot, &editor.dirty);
+
+        assert!(!editor.dirty.get());
+        assert_eq!(ctx.get_run().segment(0).name(), "Before");
+    }
+
+    #[gtk4::test]
+    fn run_changes_outside_the_editor_are_not_edits() {
+        gtk_test_init();
+        let ctx = TuxSplitContext::get_instance();
+        ctx.set_run(run_named("Before"));
+        let editor = SplitEditor::new();
+
+        // E.g. a finished attempt or loading other splits
+        ctx.set_run(run_named("Attempted"));
+        assert!(!editor.dirty.get());
+    }
+}
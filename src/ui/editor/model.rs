@@ -1,10 +1,11 @@
 use gtk4::gio::ListStore;
 use gtk4::prelude::*;
-use livesplit_core::{Segment, Timer, TimingMethod};
+use livesplit_core::{Segment, TimeSpan, Timer, TimingMethod};
 use time::Duration as TimeDuration;
 
 use crate::formatters::time::TimeFormat;
 use crate::ui::editor::row::SegmentRow;
+use crate::utils::notes::segment_note;
 
 /// `SegmentsModel` owns the `ListStore` of `SegmentRow` and provides methods
 /// to build and refresh it from a Timer and a chosen `TimingMethod`.
@@ -42,12 +43,15 @@ impl SegmentsModel {
 
         let mut formatter = TimeFormat::new(true, true, true, true, 3, false);
         let segments = timer.run().segments();
+        let out_of_order = split_order_issues(segments, timing_method);
 
         for (index, segment) in segments.iter().enumerate() {
             let (name, split_time, segment_time, best) =
                 compute_row_values(timing_method, &mut formatter, segments, index, segment);
 
             let row = SegmentRow::new(index as u32, name, split_time, segment_time, best);
+            row.set_split_out_of_order(out_of_order[index]);
+            row.set_note(segment_note(timer.run(), index).unwrap_or_default());
             self.store.append(&row);
         }
     }
@@ -66,6 +70,7 @@ impl SegmentsModel {
         }
 
         let mut formatter = TimeFormat::new(true, true, true, true, 3, false);
+        let out_of_order = split_order_issues(segments, timing_method);
 
         for (index, item) in self.store.iter::<SegmentRow>().enumerate() {
             if let Ok(row) = item
@@ -76,14 +81,37 @@ impl SegmentsModel {
                     compute_row_values(timing_method, &mut formatter, segments, index, segment);
 
                 row.set_name(name);
+                // Before the text, so the entry's validation sees the current state
+                row.set_split_out_of_order(out_of_order[index]);
                 row.set_split_time(split_time);
                 row.set_segment_time(segment_time);
                 row.set_best(best);
+                row.set_note(segment_note(timer.run(), index).unwrap_or_default());
             }
         }
     }
 }
 
+/// For every segment, whether its Personal Best split time is earlier than one of the
+/// splits before it. Segments without a split time are never flagged.
+pub fn split_order_issues(segments: &[Segment], timing_method: TimingMethod) -> Vec<bool> {
+    let mut latest: Option<TimeSpan> = None;
+    segments
+        .iter()
+        .map(|segment| {
+            let Some(split) = segment.comparison_timing_method("Personal Best", timing_method)
+            else {
+                return false;
+            };
+            let out_of_order = latest.is_some_and(|latest| split < latest);
+            if latest.is_none_or(|latest| split > latest) {
+                latest = Some(split);
+            }
+            out_of_order
+        })
+        .collect()
+}
+
 /// Computes the display values for a single row, mirroring the logic used by the editor table.
 ///
 /// - name: segment name
@@ -165,3 +193,52 @@ fn compute_row_values(
     let best_formatted = time_parser.format_duration(&best_delta);
     (name, split_time, segment_time, best_formatted)
 }
+
+#[cfg(test)]
+mod split_order_tests {
+    use super::split_order_issues;
+    use livesplit_core::{Segment, Time, TimeSpan, TimingMethod};
+
+    fn segments(splits: &[Option<f64>]) -> Vec<Segment> {
+        splits
+            .iter()
+            .map(|split| {
+                let mut segment = Segment::new("S");
+                segment.set_personal_best_split_time(
+                    Time::new().with_real_time(split.map(TimeSpan::from_seconds)),
+                );
+                segment
+            })
+            .collect()
+    }
+
+    #[test]
+    fn increasing_splits_are_fine() {
+        let segs = segments(&[Some(10.0), Some(20.0), Some(20.0), Some(30.0)]);
+        assert_eq!(
+            split_order_issues(&segs, TimingMethod::RealTime),
+            vec![false; 4]
+        );
+    }
+
+    #[test]
+    fn splits_earlier_than_any_previous_one_are_flagged() {
+        let segs = segments(&[
+            Some(10.0),
+            Some(30.0),
+            Some(20.0),
+            None,
+            Some(25.0),
+            Some(40.0),
+        ]);
+        assert_eq!(
+            split_order_issues(&segs, TimingMethod::RealTime),
+            vec![false, false, true, false, true, false]
+        );
+        // Other timing method has no times at all
+        assert_eq!(
+            split_order_issues(&segs, TimingMethod::GameTime),
+            vec![false; 6]
+        );
+    }
+}
@@ -24,6 +24,11 @@ mod imp {
         pub segment_time: RefCell<String>,
         #[property(get, set)]
         pub best: RefCell<String>,
+        #[property(get, set)]
+        pub note: RefCell<String>,
+        // Split time is earlier than a previous split (advisory, saving still works)
+        #[property(get, set)]
+        pub split_out_of_order: RefCell<bool>,
     }
 
     #[glib::object_subclass]
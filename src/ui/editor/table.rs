@@ -1,14 +1,25 @@
 use livesplit_core::{Run, TimingMethod};
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::warn;
 
-use gtk4::{Box as GtkBox, ColumnView, ColumnViewColumn, ScrolledWindow, prelude::*};
+use adw::prelude::{AdwDialogExt, AlertDialogExt};
+use gtk4::{Box as GtkBox, ColumnView, ColumnViewColumn, ScrolledWindow, gdk, prelude::*};
 
+use crate::config::EditorCommit;
 use crate::context::TuxSplitContext;
 use crate::formatters::time::parse_hms;
 use crate::ui::editor::context::SegmentMoveDirection;
 use crate::ui::editor::row::SegmentRow;
 use crate::ui::editor::{EditorContext, SegmentsModel};
+use crate::utils::debounce::Debouncer;
+
+/// How long typing has to pause before a time is committed in `EditorCommit::OnChange`.
+const COMMIT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Cell data holding the bound row and its out-of-order handler, dropped on unbind.
+const SPLIT_ORDER_HANDLER_KEY: &str = "split-order-handler";
 
 pub struct SegmentsEditor {
     container: GtkBox,
@@ -83,15 +94,19 @@ impl SegmentsEditor {
     }
 
     fn setup_columns(self: &Rc<SegmentsEditor>) {
+        let handle_column = self.make_drag_handle_column();
         let name_column = self.make_name_column();
         let split_time_column = self.clone().make_split_time_column();
         let segment_time_column = self.clone().make_segment_time_column();
         let best_column = self.clone().make_best_segment_column();
+        let note_column = self.make_note_column();
 
+        self.table.append_column(&handle_column);
         self.table.append_column(&name_column);
         self.table.append_column(&split_time_column);
         self.table.append_column(&segment_time_column);
         self.table.append_column(&best_column);
+        self.table.append_column(&note_column);
         {
             let ctx = self.context.clone();
             let weak_this = std::rc::Rc::downgrade(self);
@@ -127,6 +142,38 @@ impl SegmentsEditor {
         self.segments_model.refresh_from_timer(&timer, method);
     }
 
+    // Rows are reordered by dragging this handle onto another row
+    fn make_drag_handle_column(&self) -> ColumnViewColumn {
+        let col = ColumnViewColumn::builder().build();
+        let factory = gtk4::SignalListItemFactory::new();
+
+        let context = self.context.clone();
+        let model = self.model.clone();
+
+        factory.connect_setup(move |_, list_item| {
+            let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
+            let handle = gtk4::Image::builder()
+                .icon_name("list-drag-handle-symbolic")
+                .tooltip_text("Drag to reorder")
+                .css_classes(["dim-label"])
+                .build();
+            cell.set_child(Some(&handle));
+
+            let drag_source = gtk4::DragSource::new();
+            drag_source.set_actions(gdk::DragAction::MOVE);
+            let cell_weak = cell.downgrade();
+            drag_source.connect_prepare(move |_, _, _| {
+                let index = cell_weak.upgrade().and_then(|cell| cell_row_index(&cell))?;
+                Some(gdk::ContentProvider::for_value(&index.to_value()))
+            });
+            handle.add_controller(drag_source);
+
+            add_reorder_drop_target(cell, &handle, &context, &model);
+        });
+        col.set_factory(Some(&factory));
+        col
+    }
+
     fn make_name_column(&self) -> ColumnViewColumn {
         let col = ColumnViewColumn::builder().title("Segment Name").build();
         let factory = gtk4::SignalListItemFactory::new();
@@ -138,8 +185,15 @@ impl SegmentsEditor {
             let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
             let entry = gtk4::Entry::builder().hexpand(true).build();
             cell.set_child(Some(&entry));
+            add_reorder_drop_target(cell, &entry, &context, &model);
 
-            SegmentsEditor::setup_name_cell_common(cell, &entry, &model, &context);
+            SegmentsEditor::setup_text_cell_common(
+                cell,
+                &entry,
+                &model,
+                &context,
+                EditorContext::set_segment_name,
+            );
         });
         factory.connect_bind(|_, list_item| {
             let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
@@ -158,6 +212,50 @@ impl SegmentsEditor {
         col
     }
 
+    fn make_note_column(&self) -> ColumnViewColumn {
+        let col = ColumnViewColumn::builder()
+            .title("Note")
+            .expand(true)
+            .build();
+        let factory = gtk4::SignalListItemFactory::new();
+
+        let context = self.context.clone();
+        let model = self.model.clone();
+
+        factory.connect_setup(move |_, list_item| {
+            let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
+            let entry = gtk4::Entry::builder()
+                .hexpand(true)
+                .placeholder_text("Shown while running")
+                .build();
+            cell.set_child(Some(&entry));
+            add_reorder_drop_target(cell, &entry, &context, &model);
+
+            SegmentsEditor::setup_text_cell_common(
+                cell,
+                &entry,
+                &model,
+                &context,
+                EditorContext::set_segment_note,
+            );
+        });
+        factory.connect_bind(|_, list_item| {
+            let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
+            let entry = cell.child().unwrap().downcast::<gtk4::Entry>().unwrap();
+
+            if let Some(item) = cell.item()
+                && let Ok(row) = item.downcast::<SegmentRow>()
+            {
+                entry.set_text(&row.note());
+                row.bind_property("note", &entry, "text")
+                    .flags(glib::BindingFlags::SYNC_CREATE)
+                    .build();
+            }
+        });
+        col.set_factory(Some(&factory));
+        col
+    }
+
     fn make_split_time_column(self: Rc<Self>) -> ColumnViewColumn {
         let col = ColumnViewColumn::builder().title("Split Time").build();
         let factory = gtk4::SignalListItemFactory::new();
@@ -168,6 +266,7 @@ impl SegmentsEditor {
             let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
             let entry = gtk4::Entry::builder().hexpand(true).build();
             cell.set_child(Some(&entry));
+            add_reorder_drop_target(cell, &entry, &self_shared.context, &self_shared.model);
 
             SegmentsEditor::setup_time_cell_common(
                 cell,
@@ -185,9 +284,30 @@ impl SegmentsEditor {
                 && let Ok(row) = item.downcast::<SegmentRow>()
             {
                 entry.set_text(&row.split_time());
-                row.bind_property("split-time", &entry, "text")
-                    .flags(glib::BindingFlags::SYNC_CREATE)
-                    .build();
+                bind_time_text(&row, &entry, "split-time");
+
+                SegmentsEditor::apply_split_order_warning(&entry, row.split_out_of_order());
+                // Earlier rows can change this row's state without touching its text
+                let entry_weak = entry.downgrade();
+                let handler = row.connect_split_out_of_order_notify(move |row| {
+                    if let Some(entry) = entry_weak.upgrade() {
+                        SegmentsEditor::apply_split_order_warning(&entry, row.split_out_of_order());
+                    }
+                });
+                // SAFETY: the key is only ever used with this type
+                unsafe {
+                    cell.set_data(SPLIT_ORDER_HANDLER_KEY, (row, handler));
+                }
+            }
+        });
+        factory.connect_unbind(|_, list_item| {
+            let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
+            // SAFETY: the key is only ever used with this type
+            let bound = unsafe {
+                cell.steal_data::<(SegmentRow, glib::SignalHandlerId)>(SPLIT_ORDER_HANDLER_KEY)
+            };
+            if let Some((row, handler)) = bound {
+                row.disconnect(handler);
             }
         });
         col.set_factory(Some(&factory));
@@ -204,6 +324,7 @@ impl SegmentsEditor {
             let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
             let entry = gtk4::Entry::builder().hexpand(true).build();
             cell.set_child(Some(&entry));
+            add_reorder_drop_target(cell, &entry, &self_shared.context, &self_shared.model);
 
             SegmentsEditor::setup_time_cell_common(
                 cell,
@@ -221,9 +342,7 @@ impl SegmentsEditor {
                 && let Ok(row) = item.downcast::<SegmentRow>()
             {
                 entry.set_text(&row.segment_time());
-                row.bind_property("segment-time", &entry, "text")
-                    .flags(glib::BindingFlags::SYNC_CREATE)
-                    .build();
+                bind_time_text(&row, &entry, "segment-time");
             }
         });
         col.set_factory(Some(&factory));
@@ -240,6 +359,7 @@ impl SegmentsEditor {
             let cell = list_item.downcast_ref::<gtk4::ColumnViewCell>().unwrap();
             let entry = gtk4::Entry::builder().hexpand(true).build();
             cell.set_child(Some(&entry));
+            add_reorder_drop_target(cell, &entry, &self_shared.context, &self_shared.model);
 
             SegmentsEditor::setup_time_cell_common(
                 cell,
@@ -257,44 +377,36 @@ impl SegmentsEditor {
                 && let Ok(row) = item.downcast::<SegmentRow>()
             {
                 entry.set_text(&row.best());
-                row.bind_property("best", &entry, "text")
-                    .flags(glib::BindingFlags::SYNC_CREATE)
-                    .build();
+                bind_time_text(&row, &entry, "best");
             }
         });
         col.set_factory(Some(&factory));
         col
     }
 
-    // Set standardized handlers for the name column
-    fn setup_name_cell_common(
+    // Sets standardized handlers for text columns (Name/Note)
+    // - Selects row on focus
+    // - Commits the text on unfocus
+    fn setup_text_cell_common(
         cell: &gtk4::ColumnViewCell,
         entry: &gtk4::Entry,
         model: &gtk4::SingleSelection,
         context: &EditorContext,
+        commit: fn(&EditorContext, usize, String),
     ) {
-        // Apply name on unfocus and select on focus
         let cell_binding = cell.clone();
         let model_binding = model.clone();
         let context_binding = context.clone();
         entry.connect_notify_local(Some("has-focus"), move |e, _| {
             let focused = e.first_child().unwrap().has_focus();
-            if focused {
-                // Select the corresponding SegmentRow
-                if let Some(item) = cell_binding.item()
-                    && let Some(row) = item.downcast_ref::<SegmentRow>()
-                {
-                    let index = row.index() as usize;
+            if let Some(item) = cell_binding.item()
+                && let Some(row) = item.downcast_ref::<SegmentRow>()
+            {
+                let index = row.index() as usize;
+                if focused {
                     model_binding.select_item(index as u32, true);
-                }
-            } else {
-                // Commit name change on unfocus
-                if let Some(item) = cell_binding.item()
-                    && let Some(row) = item.downcast_ref::<SegmentRow>()
-                {
-                    let index = row.index() as usize;
-                    let value = e.text().to_string();
-                    let () = context_binding.set_segment_name(index, value);
+                } else {
+                    commit(&context_binding, index, e.text().to_string());
                 }
             }
         });
@@ -302,7 +414,7 @@ impl SegmentsEditor {
 
     // Sets standardized handlers for time columns (Split/Segment/Best)
     // - Validates on change (adds/removes "error" CSS class)
-    // - Commits on unfocus and refreshes the model
+    // - Commits on unfocus (and debounced on change if configured) and refreshes the model
     // - Selects row on focus
     fn setup_time_cell_common(
         cell: &gtk4::ColumnViewCell,
@@ -311,7 +423,14 @@ impl SegmentsEditor {
         property_name: String,
         commit: fn(&EditorContext, usize, i64),
     ) {
+        let debouncer = Debouncer::new(COMMIT_DEBOUNCE);
+
         // Validation while typing
+        let is_split_column = property_name == "split-time";
+        let cell_weak = cell.downgrade();
+        let debounce_binding = debouncer.clone();
+        let debounce_property = property_name.clone();
+        let debounce_context = editor.context.clone();
         entry.connect_changed(move |e| {
             e.remove_css_class("error");
             let value = e.text().to_string();
@@ -320,6 +439,37 @@ impl SegmentsEditor {
             if dur.is_err() || dur.as_ref().ok().unwrap().is_negative() {
                 e.add_css_class("error");
             }
+
+            // Keep the advisory order warning on split times
+            if is_split_column
+                && let Some(item) = cell_weak.upgrade().and_then(|cell| cell.item())
+                && let Some(row) = item.downcast_ref::<SegmentRow>()
+            {
+                SegmentsEditor::apply_split_order_warning(e, row.split_out_of_order());
+            }
+
+            // Commit shortly after typing stops, without waiting for unfocus
+            let on_change = TuxSplitContext::get_instance()
+                .config()
+                .general
+                .editor_commit
+                == EditorCommit::OnChange;
+            if !on_change {
+                return;
+            }
+            if let Some(item) = cell_weak.upgrade().and_then(|cell| cell.item())
+                && let Some(row) = item.downcast_ref::<SegmentRow>()
+                && let Some(ms) = Self::changed_time_ms(&e.text(), row, &debounce_property)
+            {
+                let index = row.index() as usize;
+                let context = debounce_context.clone();
+                debounce_binding.schedule(move || {
+                    commit(&context, index, ms);
+                });
+            } else {
+                // Invalid or unchanged again
+                debounce_binding.cancel();
+            }
         });
 
         // Apply change on unfocus and refresh model; select row on focus
@@ -337,28 +487,52 @@ impl SegmentsEditor {
                     self_binding.model.select_item(index as u32, true);
                 } else {
                     // Commit value if valid and if different from before
-                    let value = e.text().to_string();
-                    let is_different = match property_name.as_str() {
-                        "split-time" => value != row.split_time(),
-                        "segment-time" => value != row.segment_time(),
-                        "best" => value != row.best(),
-                        _ => false,
-                    };
-                    if let Ok(dur) = parse_hms(&value)
-                        && !dur.is_negative()
-                        && let Some(item) = cell_binding.item()
-                        && let Some(row) = item.downcast_ref::<SegmentRow>()
-                        && is_different
-                    {
+                    debouncer.cancel();
+                    if let Some(ms) = Self::changed_time_ms(&e.text(), row, &property_name) {
                         let index = row.index() as usize;
-                        let ms = dur.whole_milliseconds();
-                        commit(&context_binding, index, ms as i64);
+                        commit(&context_binding, index, ms);
+                    } else {
+                        // Updates were held back while typing, catch up with the row
+                        let current = row.property::<String>(&property_name);
+                        if e.text() != current {
+                            e.set_text(&current);
+                        }
                     }
                 }
             }
         });
     }
 
+    /// The typed time in milliseconds, if it is valid and differs from the row's value.
+    fn changed_time_ms(value: &str, row: &SegmentRow, property_name: &str) -> Option<i64> {
+        let current = match property_name {
+            "split-time" => row.split_time(),
+            "segment-time" => row.segment_time(),
+            "best" => row.best(),
+            _ => return None,
+        };
+        let dur = parse_hms(value).ok()?;
+        // Compared as times, so "1:2" matches an already committed "1:02.000"
+        let is_different = parse_hms(&current).ok() != Some(dur);
+        (is_different && !dur.is_negative()).then(|| dur.whole_milliseconds() as i64)
+    }
+
+    /// Marks a split time entry that is earlier than a previous split. Only advisory,
+    /// the value is still committed and saved.
+    fn apply_split_order_warning(entry: &gtk4::Entry, out_of_order: bool) {
+        if out_of_order {
+            entry.add_css_class("error");
+            entry.set_tooltip_text(Some(
+                "This split is earlier than a previous one, so the segment times don't add up",
+            ));
+        } else {
+            entry.set_tooltip_text(None);
+            if parse_hms(&entry.text()).is_ok_and(|d| !d.is_negative()) {
+                entry.remove_css_class("error");
+            }
+        }
+    }
+
     // Small helpers to bridge into EditorContext
     fn commit_split_time(ctx: &EditorContext, index: usize, ms: i64) {
         ctx.set_split_time_ms(index, ms);
@@ -370,6 +544,60 @@ impl SegmentsEditor {
         ctx.set_best_time_ms(index, ms);
     }
 
+    /// Asks for an offset and adds it to (or subtracts it from) every split time.
+    fn present_shift_dialog(parent: &gtk4::Button, context: &EditorContext) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Shift Split Times")
+            .body("Add or subtract a fixed offset from every split time")
+            .default_response("shift")
+            .close_response("cancel")
+            .build();
+
+        let subtract_toggle = gtk4::ToggleButton::builder().label("Subtract").build();
+        let offset_entry = gtk4::Entry::builder()
+            .placeholder_text("0:05.000")
+            .hexpand(true)
+            .build();
+        offset_entry.connect_changed(|e| {
+            e.remove_css_class("error");
+            if parse_hms(&e.text()).is_err() {
+                e.add_css_class("error");
+            }
+        });
+        let inputs = GtkBox::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        inputs.append(&subtract_toggle);
+        inputs.append(&offset_entry);
+        dialog.set_extra_child(Some(&inputs));
+
+        dialog.add_response("cancel", "Cancel");
+        dialog.add_response("shift", "Shift");
+        dialog.set_response_appearance("shift", adw::ResponseAppearance::Suggested);
+
+        let context = context.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "shift" {
+                return;
+            }
+            match parse_hms(&offset_entry.text()) {
+                Ok(offset) if !offset.is_negative() => {
+                    let ms = offset.whole_milliseconds() as i64;
+                    let sign = if subtract_toggle.is_active() { -1 } else { 1 };
+                    if ms != 0 && !context.shift_all_split_times(sign * ms) {
+                        TuxSplitContext::get_instance().show_toast(
+                            "Split times not shifted, a split would end at zero or below",
+                        );
+                    }
+                }
+                _ => warn!("Invalid time offset: {}", offset_entry.text()),
+            }
+        });
+
+        dialog.present(Some(parent));
+    }
+
     // Builds the editor controls (Move split up/down, Add split above, Remove split)
     fn build_controls(&self) -> gtk4::Box {
         let controls = gtk4::Box::builder()
@@ -480,13 +708,224 @@ impl SegmentsEditor {
             });
         }
 
+        let duplicate_button = gtk4::Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .tooltip_text("Duplicate Segment")
+            .build();
+        {
+            let context = self.context.clone();
+            let model_binding = self.model.clone();
+            duplicate_button.connect_clicked(move |_| {
+                let selected = model_binding.selected(); // We need to capture this before adding
+                context.duplicate_segment(selected as usize);
+                // Select the copy, which sits right below the original
+                model_binding.set_selected(std::cmp::min(
+                    selected + 1,
+                    TuxSplitContext::get_instance().get_run().segments().len() as u32 - 1, // At least one segment will be present
+                ));
+            });
+        }
+
+        let clear_times_button = gtk4::Button::builder()
+            .icon_name("edit-clear-symbolic")
+            .tooltip_text("Clear Segment Times")
+            .build();
+        {
+            let context = self.context.clone();
+            let model_binding = self.model.clone();
+            clear_times_button.connect_clicked(move |_| {
+                let selected = model_binding.selected();
+                context.clear_segment_times(selected as usize);
+                // We restore the selection
+                model_binding.set_selected(selected);
+            });
+        }
+
+        let icon_button = gtk4::Button::builder()
+            .icon_name("image-x-generic-symbolic")
+            .tooltip_text("Set Segment Icon")
+            .build();
+        {
+            let context = self.context.clone();
+            let model_binding = self.model.clone();
+            icon_button.connect_clicked(move |button| {
+                let parent = button.root().and_downcast::<gtk4::Window>();
+                let file_chooser = gtk4::FileChooserDialog::new(
+                    Some("Set Segment Icon"),
+                    parent.as_ref(),
+                    gtk4::FileChooserAction::Open,
+                    &[
+                        ("Open", gtk4::ResponseType::Ok),
+                        ("Cancel", gtk4::ResponseType::Cancel),
+                    ],
+                );
+
+                let image_filter = gtk4::FileFilter::new();
+                image_filter.set_name(Some("Images (*.png, *.jpg, *.jpeg)"));
+                image_filter.add_mime_type("image/png");
+                image_filter.add_mime_type("image/jpeg");
+                file_chooser.add_filter(&image_filter);
+
+                let context = context.clone();
+                let model_binding = model_binding.clone();
+                file_chooser.connect_response(move |dialog, response| {
+                    if response == gtk4::ResponseType::Ok
+                        && let Some(path) = dialog.file().and_then(|f| f.path())
+                    {
+                        let selected = model_binding.selected();
+                        match std::fs::read(&path) {
+                            Ok(bytes) => context.set_segment_icon(selected as usize, &bytes),
+                            Err(err) => warn!("Could not read {}: {err}", path.display()),
+                        }
+                        // We restore the selection
+                        model_binding.set_selected(selected);
+                    }
+                    dialog.destroy();
+                });
+
+                file_chooser.set_modal(true);
+                file_chooser.present();
+            });
+        }
+
+        let shift_button = gtk4::Button::builder()
+            .icon_name("preferences-system-time-symbolic")
+            .tooltip_text("Shift All Split Times")
+            .build();
+        {
+            let context = self.context.clone();
+            shift_button.connect_clicked(move |button| {
+                Self::present_shift_dialog(button, &context);
+            });
+        }
+
+        let history_group = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(0)
+            .homogeneous(true)
+            .valign(gtk4::Align::Fill)
+            .css_classes(["button-group"])
+            .build();
+        {
+            let undo_button = gtk4::Button::builder()
+                .icon_name("edit-undo-symbolic")
+                .tooltip_text("Undo")
+                .sensitive(self.context.can_undo())
+                .build();
+            {
+                let context = self.context.clone();
+                let model_binding = self.model.clone();
+                undo_button.connect_clicked(move |_| {
+                    let selected = model_binding.selected();
+                    context.undo();
+                    model_binding.set_selected(std::cmp::min(
+                        selected,
+                        TuxSplitContext::get_instance().get_run().segments().len() as u32 - 1, // At least one segment will be present
+                    ));
+                });
+            }
+            let redo_button = gtk4::Button::builder()
+                .icon_name("edit-redo-symbolic")
+                .tooltip_text("Redo")
+                .sensitive(self.context.can_redo())
+                .build();
+            {
+                let context = self.context.clone();
+                let model_binding = self.model.clone();
+                redo_button.connect_clicked(move |_| {
+                    let selected = model_binding.selected();
+                    context.redo();
+                    model_binding.set_selected(std::cmp::min(
+                        selected,
+                        TuxSplitContext::get_instance().get_run().segments().len() as u32 - 1, // At least one segment will be present
+                    ));
+                });
+            }
+            {
+                // Keep the buttons in sync with the history after every edit, undo and redo
+                let context_weak = self.context.downgrade();
+                let undo_weak = undo_button.downgrade();
+                let redo_weak = redo_button.downgrade();
+                self.context
+                    .connect_local("run-changed", false, move |_values| {
+                        let context = context_weak.upgrade()?;
+                        if let Some(undo_button) = undo_weak.upgrade() {
+                            undo_button.set_sensitive(context.can_undo());
+                        }
+                        if let Some(redo_button) = redo_weak.upgrade() {
+                            redo_button.set_sensitive(context.can_redo());
+                        }
+                        None
+                    });
+            }
+            history_group.append(&undo_button);
+            history_group.append(&redo_button);
+        }
+
         controls.append(&move_group);
         controls.append(&add_group);
+        controls.append(&duplicate_button);
+        controls.append(&clear_times_button);
+        controls.append(&icon_button);
+        controls.append(&shift_button);
         controls.append(&remove_split_button);
+        controls.append(&history_group);
         controls
     }
 }
 
+/// Shows the row's `property` in `entry`. While the entry is being typed in it keeps
+/// its text, so a debounced commit doesn't rewrite it under the cursor.
+fn bind_time_text(row: &SegmentRow, entry: &gtk4::Entry, property: &str) {
+    let entry_weak = entry.downgrade();
+    row.bind_property(property, entry, "text")
+        .transform_to(move |_, text: String| {
+            let editing = entry_weak.upgrade().is_some_and(|entry| is_editing(&entry));
+            (!editing).then_some(text)
+        })
+        .flags(glib::BindingFlags::SYNC_CREATE)
+        .build();
+}
+
+/// Whether `entry` (its text, that is) has the keyboard focus of its window.
+fn is_editing(entry: &gtk4::Entry) -> bool {
+    entry
+        .root()
+        .and_then(|root| root.focus())
+        .is_some_and(|focus| focus.is_ancestor(entry))
+}
+
+/// Index of the row currently bound to `cell`.
+fn cell_row_index(cell: &gtk4::ColumnViewCell) -> Option<u32> {
+    cell.item()
+        .and_then(|item| item.downcast::<SegmentRow>().ok())
+        .map(|row| row.index())
+}
+
+/// Accepts a dragged row on `widget`, moving it to the row bound to `cell`. Every
+/// cell gets one, so a row can be dropped anywhere on another row.
+fn add_reorder_drop_target(
+    cell: &gtk4::ColumnViewCell,
+    widget: &impl IsA<gtk4::Widget>,
+    context: &EditorContext,
+    model: &gtk4::SingleSelection,
+) {
+    let drop_target = gtk4::DropTarget::new(u32::static_type(), gdk::DragAction::MOVE);
+    let cell_weak = cell.downgrade();
+    let context = context.clone();
+    let model = model.clone();
+    drop_target.connect_drop(move |_, value, _, _| {
+        let to = cell_weak.upgrade().and_then(|cell| cell_row_index(&cell));
+        let (Ok(from), Some(to)) = (value.get::<u32>(), to) else {
+            return false;
+        };
+        context.reorder_segment(from as usize, to as usize);
+        model.set_selected(to);
+        true
+    });
+    widget.add_controller(drop_target);
+}
+
 #[cfg(test)]
 impl SegmentsEditor {
     // Test-only helpers to inspect internal model and context without touching UI widgets.
@@ -567,4 +1006,94 @@ mod tests {
             assert_eq!(items[0].split_time(), "20.000");
         }
     }
+
+    #[gtk4::test]
+    fn debounced_change_commits_once_without_unfocus() {
+        gtk_test_init();
+        let mut run = Run::new();
+        run.push_segment(Segment::new("S1"));
+        crate::context::TuxSplitContext::get_instance().set_run(run);
+
+        let context = EditorContext::new();
+        let commits = Rc::new(std::cell::Cell::new(0));
+        let counter = commits.clone();
+        context.connect_local("run-changed", false, move |_| {
+            counter.set(counter.get() + 1);
+            None
+        });
+
+        // Typing "1" then "12": only the last value gets committed
+        let delay = Duration::from_millis(20);
+        let debouncer = Debouncer::new(delay);
+        for ms in [1_000, 12_000] {
+            let context = context.clone();
+            debouncer.schedule(move || {
+                SegmentsEditor::commit_split_time(&context, 0, ms);
+            });
+        }
+
+        let main_context = glib::MainContext::default();
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        while commits.get() == 0 && std::time::Instant::now() < deadline {
+            main_context.iteration(false);
+        }
+        // Give a wrongly kept first timer the chance to fire too
+        let settle = std::time::Instant::now() + delay * 3;
+        while std::time::Instant::now() < settle {
+            main_context.iteration(false);
+        }
+
+        assert_eq!(commits.get(), 1);
+        let split = crate::context::TuxSplitContext::get_instance()
+            .get_run()
+            .segment(0)
+            .personal_best_split_time()
+            .real_time;
+        assert_eq!(split, Some(TimeSpan::from_milliseconds(12_000.0)));
+    }
+
+    #[gtk4::test]
+    fn focused_time_entry_keeps_the_typed_text() {
+        gtk_test_init();
+        let row = SegmentRow::new(
+            0,
+            "S1".to_owned(),
+            "1:00.000".to_owned(),
+            "1:00.000".to_owned(),
+            "1:00.000".to_owned(),
+        );
+        let entry = gtk4::Entry::new();
+        let window = gtk4::Window::new();
+        window.set_child(Some(&entry));
+        bind_time_text(&row, &entry, "split-time");
+        assert_eq!(entry.text(), "1:00.000");
+
+        assert!(entry.grab_focus());
+        entry.set_text("1:2");
+        // A debounced commit refreshes the row while still typing
+        row.set_split_time("1:02.000".to_owned());
+        assert_eq!(entry.text(), "1:2");
+
+        window.set_focus(None::<&gtk4::Widget>);
+        row.set_split_time("1:03.000".to_owned());
+        assert_eq!(entry.text(), "1:03.000");
+    }
+
+    #[gtk4::test]
+    fn cancelled_change_does_not_commit() {
+        gtk_test_init();
+        let committed = Rc::new(std::cell::Cell::new(false));
+        let flag = committed.clone();
+
+        let debouncer = Debouncer::new(Duration::from_millis(10));
+        debouncer.schedule(move || flag.set(true));
+        debouncer.cancel();
+
+        let main_context = glib::MainContext::default();
+        let settle = std::time::Instant::now() + Duration::from_millis(50);
+        while std::time::Instant::now() < settle {
+            main_context.iteration(false);
+        }
+        assert!(!committed.get());
+    }
 }
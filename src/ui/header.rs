@@ -1,13 +1,25 @@
-use adw::{self, AboutDialog, AlertDialog};
+use adw::{self, AboutDialog};
 use adw::{PreferencesDialog, prelude::*};
 use gtk4::{
-    Align, Box as GtkBox, FileChooserDialog, FileFilter, Label, ListBox, MenuButton,
-    Orientation::Vertical, gio,
+    Align, Box as GtkBox, CallbackAction, EventControllerKey, FileChooserDialog, FileFilter, Label,
+    MenuButton, Orientation::Vertical, Shortcut, ShortcutController, ShortcutTrigger, gio,
 };
+use livesplit_core::hotkey::Hotkey;
+use livesplit_core::{
+    HotkeyConfig, Run, Timer, TimerPhase,
+    run::parser::{TimerKind, composite},
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
 
-use crate::context::TuxSplitContext;
+use crate::config::{Config, LoadWhileRunning};
+use crate::context::{ComparisonStep, TuxSplitContext};
+use crate::formatters::export::RunExport;
 use crate::ui::editor::SplitEditor;
+use crate::ui::hotkeys::{hotkey_from_key, present_hotkeys_dialog};
 use crate::ui::menu::TimerPreferencesDialog;
+use crate::utils::comparisons::{WORLD_RECORD_COMPARISON, import_reference_comparison};
 
 /// `TuxSplitHeader`
 /// A top bar that renders the application title and a hamburger menu.
@@ -25,6 +37,52 @@ impl TuxSplitHeader {
         let menu = TuxSplitMenu::new(parent);
         header.pack_start(menu.button());
 
+        // Ctrl+Shift+O opens the splits file in the default external editor
+        let shortcuts = ShortcutController::new();
+        shortcuts.add_shortcut(Shortcut::new(
+            ShortcutTrigger::parse_string("<Control><Shift>o"),
+            Some(CallbackAction::new(|_, _| {
+                open_splits_externally();
+                glib::Propagation::Stop
+            })),
+        ));
+        // The comparison hotkeys also work while the window is focused; the global
+        // hotkeys do that themselves when active. Keys are read from the config on every
+        // press, so rebinding them applies right away.
+        let comparison_keys = EventControllerKey::new();
+        comparison_keys.connect_key_pressed(|_, key, _, state| {
+            let ctx = TuxSplitContext::get_instance();
+            let step = {
+                let cfg = ctx.config();
+                if cfg.hotkeys_active() {
+                    return glib::Propagation::Proceed;
+                }
+                comparison_step(&cfg.hotkeys, hotkey_from_key(key, state))
+            };
+            let Some(step) = step else {
+                return glib::Propagation::Proceed;
+            };
+            ctx.cycle_comparison(step);
+            glib::Propagation::Stop
+        });
+        parent.add_controller(comparison_keys);
+        // Ctrl+S saves the splits, unless a text field has the focus
+        shortcuts.add_shortcut(Shortcut::new(
+            ShortcutTrigger::parse_string("<Control>s"),
+            Some(CallbackAction::new(|widget, _| {
+                let editing = widget
+                    .root()
+                    .and_then(|root| root.focus())
+                    .is_some_and(|focus| focus.is::<gtk4::Editable>());
+                if editing {
+                    return glib::Propagation::Proceed;
+                }
+                save_splits();
+                glib::Propagation::Stop
+            })),
+        ));
+        parent.add_controller(shortcuts);
+
         Self { header, menu }
     }
 
@@ -50,6 +108,22 @@ impl TuxSplitMenu {
         splits_section.append(Some("Load Splits"), Some("app.load-splits"));
         splits_section.append(Some("Save Splits"), Some("app.save-splits"));
         splits_section.append(Some("Edit Splits"), Some("app.edit-splits"));
+        splits_section.append(Some("Import World Record"), Some("app.import-reference"));
+        splits_section.append(Some("Export CSV"), Some("app.export-csv"));
+        splits_section.append(Some("Export JSON"), Some("app.export-json"));
+        splits_section.append(
+            Some("Open Splits File Externally"),
+            Some("app.open-splits-externally"),
+        );
+
+        let reset_section = gio::Menu::new();
+        reset_section.append(Some("Prime Timer"), Some("app.prime-timer"));
+        reset_section.append(Some("Reset (Save Golds/PB)"), Some("app.reset-save"));
+        reset_section.append(Some("Reset (Discard)"), Some("app.reset-discard"));
+
+        let comparison_section = gio::Menu::new();
+        comparison_section.append(Some("Previous Comparison"), Some("app.previous-comparison"));
+        comparison_section.append(Some("Next Comparison"), Some("app.next-comparison"));
 
         let settings_section = gio::Menu::new();
         settings_section.append(Some("Settings"), Some("app.settings"));
@@ -59,6 +133,8 @@ impl TuxSplitMenu {
         about_section.append(Some("About"), Some("app.about"));
 
         menu.append_section(None, &splits_section);
+        menu.append_section(None, &reset_section);
+        menu.append_section(None, &comparison_section);
         menu.append_section(None, &settings_section);
         menu.append_section(None, &about_section);
         button.set_menu_model(Some(&menu));
@@ -68,6 +144,21 @@ impl TuxSplitMenu {
         group.add_action(&Self::get_load_action(parent));
         group.add_action(&Self::get_save_action());
         group.add_action(&Self::get_edit_action());
+        group.add_action(&Self::get_import_reference_action(parent));
+        group.add_action(&Self::get_export_action(parent, ExportFormat::Csv));
+        group.add_action(&Self::get_export_action(parent, ExportFormat::Json));
+        group.add_action(&Self::get_open_externally_action());
+        group.add_action(&Self::get_prime_action());
+        group.add_action(&Self::get_reset_action("reset-save", true));
+        group.add_action(&Self::get_reset_action("reset-discard", false));
+        group.add_action(&Self::get_comparison_action(
+            "previous-comparison",
+            ComparisonStep::Previous,
+        ));
+        group.add_action(&Self::get_comparison_action(
+            "next-comparison",
+            ComparisonStep::Next,
+        ));
         group.add_action(&Self::get_settings_action(parent));
         group.add_action(&Self::get_keybinds_action(parent));
         group.add_action(&Self::get_about_action(parent));
@@ -82,14 +173,7 @@ impl TuxSplitMenu {
 
     fn get_save_action() -> gio::SimpleAction {
         let action = gio::SimpleAction::new("save-splits", None);
-        action.connect_activate(move |_, _| {
-            let ctx = TuxSplitContext::get_instance();
-            if let Ok(c) = ctx.config_mut() {
-                let shared_timer = ctx.timer();
-                let t = shared_timer.read().unwrap();
-                c.save_splits(&t);
-            }
-        });
+        action.connect_activate(move |_, _| save_splits());
         action
     }
 
@@ -105,6 +189,35 @@ impl TuxSplitMenu {
         action
     }
 
+    fn get_prime_action() -> gio::SimpleAction {
+        let action = gio::SimpleAction::new("prime-timer", None);
+        action.connect_activate(move |_, _| TuxSplitContext::get_instance().prime());
+        action
+    }
+
+    /// Resets the attempt, `update_splits` deciding whether its golds and PB are kept.
+    fn get_reset_action(name: &str, update_splits: bool) -> gio::SimpleAction {
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| {
+            TuxSplitContext::get_instance().reset(update_splits);
+        });
+        action
+    }
+
+    fn get_comparison_action(name: &str, step: ComparisonStep) -> gio::SimpleAction {
+        let action = gio::SimpleAction::new(name, None);
+        action.connect_activate(move |_, _| {
+            TuxSplitContext::get_instance().cycle_comparison(step);
+        });
+        action
+    }
+
+    fn get_open_externally_action() -> gio::SimpleAction {
+        let action = gio::SimpleAction::new("open-splits-externally", None);
+        action.connect_activate(move |_, _| open_splits_externally());
+        action
+    }
+
     fn get_load_action(parent: &adw::ApplicationWindow) -> gio::SimpleAction {
         let parent_binding = parent.clone();
         let action = gio::SimpleAction::new("load-splits", None);
@@ -119,26 +232,30 @@ impl TuxSplitMenu {
                 ],
             );
 
-            let lss_filter = FileFilter::new();
-            let all_filter = FileFilter::new();
-            lss_filter.set_name(Some("LiveSplit Splits (*.lss)"));
-            all_filter.set_name(Some("All Files"));
-            lss_filter.add_pattern("*.lss");
-            all_filter.add_pattern("*");
-            file_chooser.add_filter(&lss_filter);
-            file_chooser.add_filter(&all_filter);
+            for filter in splits_filters() {
+                file_chooser.add_filter(&filter);
+            }
+
+            open_in_last_directory(&file_chooser, &TuxSplitContext::get_instance().config());
 
+            let parent = parent_binding.clone();
             file_chooser.connect_response(move |dialog, response| {
                 if response == gtk4::ResponseType::Ok
                     && let Some(file) = dialog.file()
                     && let Some(path) = file.path()
                 {
                     let ctx = TuxSplitContext::get_instance();
-                    if let Ok(mut c) = ctx.config_mut() {
-                        c.set_splits_path(path);
-                        if let Some(run) = c.parse_run() {
-                            drop(c); // Set run needs write access to config
-                            ctx.set_run(run);
+                    let phase = ctx.timer().read().unwrap().current_phase();
+                    let behavior = ctx.config().general.load_while_running;
+                    match load_decision(phase, behavior) {
+                        LoadDecision::Load => load_splits(&parent, &path),
+                        LoadDecision::ResetFirst => {
+                            ctx.reset(false);
+                            load_splits(&parent, &path);
+                        }
+                        LoadDecision::Confirm => present_discard_attempt(&parent, path),
+                        LoadDecision::Refuse => {
+                            warn!("Not loading splits while an attempt is running");
                         }
                     }
                 }
@@ -151,38 +268,124 @@ impl TuxSplitMenu {
         action
     }
 
-    fn get_keybinds_action(parent: &adw::ApplicationWindow) -> gio::SimpleAction {
-        let parent_for_keybinds = parent.clone();
-        let action = gio::SimpleAction::new("keybindings", None);
+    fn get_import_reference_action(parent: &adw::ApplicationWindow) -> gio::SimpleAction {
+        let parent_binding = parent.clone();
+        let action = gio::SimpleAction::new("import-reference", None);
         action.connect_activate(move |_, _| {
-            let dialog = AlertDialog::builder()
-                .heading("Keybindings")
-                .body("Current keybinds are not modifiable yet.")
-                .default_response("ok")
-                .build();
+            let file_chooser = FileChooserDialog::new(
+                Some("Import World Record"),
+                Some(&parent_binding),
+                gtk4::FileChooserAction::Open,
+                &[
+                    ("Import", gtk4::ResponseType::Ok),
+                    ("Cancel", gtk4::ResponseType::Cancel),
+                ],
+            );
 
-            let keybinds_list = ListBox::new();
-            keybinds_list.add_css_class("boxed-list");
-            let keybinds = vec![
-                ("Start / Split", "Numpad 1"),
-                ("Skip Split", "Numpad 2"),
-                ("Reset", "Numpad 3"),
-                ("Previous Comparison", "Numpad 4"),
-                ("Pause", "Numpad 5"),
-                ("Next Comparison", "Numpad 6"),
-                ("Undo", "Numpad 8"),
-            ];
-            for (action, key) in keybinds {
-                let key_label = Label::new(Some(key));
-                let row = adw::ActionRow::builder().title(action).build();
-                row.add_suffix(&key_label);
-                keybinds_list.append(&row);
+            for filter in splits_filters() {
+                file_chooser.add_filter(&filter);
             }
 
-            dialog.set_extra_child(Some(&keybinds_list));
+            open_in_last_directory(&file_chooser, &TuxSplitContext::get_instance().config());
+
+            file_chooser.connect_response(move |dialog, response| {
+                if response == gtk4::ResponseType::Ok
+                    && let Some(file) = dialog.file()
+                    && let Some(path) = file.path()
+                {
+                    let ctx = TuxSplitContext::get_instance();
+                    if let Ok(mut c) = ctx.config_mut() {
+                        c.remember_directory(&path);
+                    }
+                    let phase = ctx.timer().read().unwrap().current_phase();
+                    if load_decision(phase, LoadWhileRunning::Refuse) == LoadDecision::Refuse {
+                        warn!("Not importing a comparison while an attempt is running");
+                    } else if let Some(reference) = parse_reference_run(&path)
+                        && let Some(run) = import_reference_comparison(
+                            ctx.get_run(),
+                            &reference,
+                            WORLD_RECORD_COMPARISON,
+                        )
+                    {
+                        ctx.set_run(run);
+                    } else {
+                        warn!("Could not import {} as a comparison", path.display());
+                    }
+                }
+                dialog.destroy();
+            });
+
+            file_chooser.set_modal(true);
+            file_chooser.present();
+        });
+        action
+    }
+
+    fn get_export_action(
+        parent: &adw::ApplicationWindow,
+        format: ExportFormat,
+    ) -> gio::SimpleAction {
+        let parent_binding = parent.clone();
+        let action = gio::SimpleAction::new(format.action_name(), None);
+        action.connect_activate(move |_, _| {
+            let file_chooser = FileChooserDialog::new(
+                Some(format.title()),
+                Some(&parent_binding),
+                gtk4::FileChooserAction::Save,
+                &[
+                    ("Export", gtk4::ResponseType::Ok),
+                    ("Cancel", gtk4::ResponseType::Cancel),
+                ],
+            );
+
+            let (filter_name, pattern) = format.filter();
+            let filter = FileFilter::new();
+            filter.set_name(Some(filter_name));
+            filter.add_pattern(pattern);
+            file_chooser.add_filter(&filter);
+            file_chooser.set_current_name(format.file_name());
+
+            open_in_last_directory(&file_chooser, &TuxSplitContext::get_instance().config());
+
+            file_chooser.connect_response(move |dialog, response| {
+                if response == gtk4::ResponseType::Ok
+                    && let Some(file) = dialog.file()
+                    && let Some(path) = file.path()
+                {
+                    let ctx = TuxSplitContext::get_instance();
+                    let contents = {
+                        let shared_timer = ctx.timer();
+                        let timer = shared_timer.read().unwrap();
+                        format.contents(&ctx.config(), &timer)
+                    };
+                    if let Ok(mut c) = ctx.config_mut() {
+                        c.remember_directory(&path);
+                    }
+                    let result = contents.and_then(|contents| {
+                        fs::write(&path, contents).map_err(|err| err.to_string())
+                    });
+                    match result {
+                        Ok(()) => ctx.show_toast(&format!("Exported splits to {}", path.display())),
+                        Err(err) => {
+                            warn!("Could not export splits to {}: {err}", path.display());
+                            ctx.show_toast(&format!("Could not export splits: {err}"));
+                        }
+                    }
+                }
+                dialog.destroy();
+            });
+
+            file_chooser.set_modal(true);
+            file_chooser.present();
+        });
+        action
+    }
 
-            dialog.add_response("ok", "Okay");
-            dialog.present(Some(&parent_for_keybinds));
+    fn get_keybinds_action(parent: &adw::ApplicationWindow) -> gio::SimpleAction {
+        let parent_for_keybinds = parent.clone();
+        let action = gio::SimpleAction::new("keybindings", None);
+        action.connect_activate(move |_, _| {
+            present_hotkeys_dialog(&parent_for_keybinds);
         });
         action
     }
@@ -217,6 +420,259 @@ impl TuxSplitMenu {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    fn action_name(self) -> &'static str {
+        match self {
+            Self::Csv => "export-csv",
+            Self::Json => "export-json",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Csv => "Export CSV",
+            Self::Json => "Export JSON",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            Self::Csv => "splits.csv",
+            Self::Json => "splits.json",
+        }
+    }
+
+    /// Name and pattern of the chooser's file filter.
+    fn filter(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Csv => ("CSV Files (*.csv)", "*.csv"),
+            Self::Json => ("JSON Files (*.json)", "*.json"),
+        }
+    }
+
+    fn contents(self, config: &Config, timer: &Timer) -> Result<String, String> {
+        match self {
+            Self::Csv => Ok(config.export_csv(timer.run(), timer.current_timing_method())),
+            Self::Json => RunExport::from_run(timer.run())
+                .to_json()
+                .map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Opens the chooser in the folder a file was last picked from.
+pub(crate) fn open_in_last_directory(file_chooser: &FileChooserDialog, config: &Config) {
+    if let Some(dir) = config.chooser_directory()
+        && let Err(err) = file_chooser.set_current_folder(Some(&gio::File::for_path(dir)))
+    {
+        warn!(
+            "Could not open the file chooser in {}: {err}",
+            dir.display()
+        );
+    }
+}
+
+/// Splits formats offered by the load chooser. livesplit-core detects the format from
+/// the contents, so the filters only narrow down the file list.
+const SPLITS_FORMATS: [(&str, &str); 4] = [
+    ("LiveSplit (*.lss)", "*.lss"),
+    ("WSplit (*.wsplit)", "*.wsplit"),
+    ("Urn (*.json)", "*.json"),
+    ("SplitterZ (*.txt)", "*.txt"),
+];
+
+/// A filter matching all supported splits formats, then one per format and a catch-all.
+fn splits_filters() -> Vec<FileFilter> {
+    let splits_filter = FileFilter::new();
+    splits_filter.set_name(Some("Splits"));
+    let mut filters = vec![splits_filter.clone()];
+    for (name, pattern) in SPLITS_FORMATS {
+        splits_filter.add_pattern(pattern);
+        let filter = FileFilter::new();
+        filter.set_name(Some(name));
+        filter.add_pattern(pattern);
+        filters.push(filter);
+    }
+    let all_filter = FileFilter::new();
+    all_filter.set_name(Some("All Files"));
+    all_filter.add_pattern("*");
+    filters.push(all_filter);
+    filters
+}
+
+/// Where saving writes a run loaded from `loaded`. Saving always writes LiveSplit
+/// splits, so runs imported from other formats go to a `.lss` file next to the
+/// original instead of overwriting it.
+fn splits_save_path(loaded: &Path, kind: &TimerKind) -> PathBuf {
+    if matches!(kind, TimerKind::LiveSplit) {
+        return loaded.to_path_buf();
+    }
+    let stem = loaded
+        .file_stem()
+        .map_or_else(|| "splits".into(), |stem| stem.to_string_lossy());
+    let mut candidate = loaded.with_file_name(format!("{stem}.lss"));
+    let mut n = 1;
+    while candidate.exists() {
+        candidate = loaded.with_file_name(format!("{stem}-{n}.lss"));
+        n += 1;
+    }
+    candidate
+}
+
+/// Reads the splits at `path` and makes them the current run.
+fn load_splits(parent: &adw::ApplicationWindow, path: &Path) {
+    let ctx = TuxSplitContext::get_instance();
+    let Ok(mut c) = ctx.config_mut() else {
+        return;
+    };
+    c.remember_directory(path);
+    match c.read_run(path) {
+        Ok((run, kind)) => {
+            let splits_path = splits_save_path(path, &kind);
+            c.set_splits_path(splits_path.clone());
+            drop(c); // Set run needs write access to config
+            ctx.set_run(run);
+            if splits_path != path {
+                ctx.show_toast(&format!(
+                    "Imported {kind} splits, saving writes {}",
+                    splits_path.display()
+                ));
+            }
+        }
+        Err(err) => {
+            drop(c);
+            warn!("Could not load {}: {err}", path.display());
+            present_load_error(parent, path, &err);
+        }
+    }
+}
+
+/// Asks before a load discards the running attempt.
+fn present_discard_attempt(parent: &adw::ApplicationWindow, path: PathBuf) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Discard Running Attempt?")
+        .body("Loading other splits resets the current attempt without saving it")
+        .default_response("cancel")
+        .close_response("cancel")
+        .build();
+    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("discard", "Discard and Load");
+    dialog.set_response_appearance("discard", adw::ResponseAppearance::Destructive);
+
+    let parent_binding = parent.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response == "discard" {
+            TuxSplitContext::get_instance().reset(false);
+            load_splits(&parent_binding, &path);
+        }
+    });
+    dialog.present(Some(parent));
+}
+
+fn present_load_error(parent: &adw::ApplicationWindow, path: &Path, err: &str) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Could Not Load Splits")
+        .body(format!("{} could not be read: {err}", path.display()))
+        .default_response("ok")
+        .close_response("ok")
+        .build();
+    dialog.add_response("ok", "OK");
+    dialog.present(Some(parent));
+}
+
+fn parse_reference_run(path: &Path) -> Option<Run> {
+    let file = fs::read(path).ok()?;
+    Some(composite::parse(&file, Some(path)).ok()?.run)
+}
+
+/// The comparison step bound to `hotkey` in `hotkeys`.
+fn comparison_step(hotkeys: &HotkeyConfig, hotkey: Option<Hotkey>) -> Option<ComparisonStep> {
+    let hotkey = hotkey?;
+    if hotkeys.previous_comparison == Some(hotkey) {
+        Some(ComparisonStep::Previous)
+    } else if hotkeys.next_comparison == Some(hotkey) {
+        Some(ComparisonStep::Next)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum LoadDecision {
+    Load,
+    ResetFirst,
+    Confirm,
+    Refuse,
+}
+
+/// Replacing the run mid-attempt would leave a half-started run behind, so only
+/// an idle or finished timer loads directly.
+fn load_decision(phase: TimerPhase, behavior: LoadWhileRunning) -> LoadDecision {
+    match phase {
+        TimerPhase::NotRunning | TimerPhase::Ended => LoadDecision::Load,
+        TimerPhase::Running | TimerPhase::Paused => match behavior {
+            LoadWhileRunning::Confirm => LoadDecision::Confirm,
+            LoadWhileRunning::Reset => LoadDecision::ResetFirst,
+            LoadWhileRunning::Refuse => LoadDecision::Refuse,
+        },
+    }
+}
+
+/// Opens the configured splits file with the desktop's default handler (usually a
+/// text/XML editor). Does nothing when no splits file is set.
+fn open_splits_externally() {
+    let uri = {
+        let ctx = TuxSplitContext::get_instance();
+        let cfg = ctx.config();
+        splits_file_uri(cfg.general.splits.as_deref())
+    };
+    let Some(uri) = uri else {
+        warn!("No splits file set, nothing to open");
+        return;
+    };
+    if let Err(err) = gio::AppInfo::launch_default_for_uri(&uri, None::<&gio::AppLaunchContext>) {
+        warn!("Could not open {uri}: {err}");
+    }
+}
+
+/// Saves the run to the splits file and reports the outcome in a toast.
+fn save_splits() {
+    let ctx = TuxSplitContext::get_instance();
+    let result = {
+        let cfg = ctx.config();
+        let shared_timer = ctx.timer();
+        let timer = shared_timer.read().unwrap();
+        cfg.save_splits(&timer)
+    };
+    if let Err(err) = &result {
+        warn!("Could not save splits: {err}");
+    }
+    ctx.show_toast(&save_toast_message(&result));
+}
+
+fn save_toast_message(result: &Result<Option<PathBuf>, std::io::Error>) -> String {
+    match result {
+        Ok(Some(path)) => format!("Saved splits to {}", path.display()),
+        Ok(None) => "No splits file to save to, load splits first".to_owned(),
+        Err(err) => format!("Could not save splits: {err}"),
+    }
+}
+
+/// URI to hand to the default application for the given splits path.
+fn splits_file_uri(path: Option<&Path>) -> Option<String> {
+    let path = path?;
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    Some(gio::File::for_path(path).uri().to_string())
+}
+
 fn temporary_keybinds_disable(widget: &PreferencesDialog) {
     // Disable and enable keybinds while settings are open
     let ctx = TuxSplitContext::get_instance();
@@ -254,3 +710,231 @@ fn simple_title_header(title: &str) -> adw::HeaderBar {
 
     header
 }
+
+#[cfg(test)]
+mod save_toast_tests {
+    use super::save_toast_message;
+    use std::path::PathBuf;
+
+    #[test]
+    fn success_mentions_the_path() {
+        let result = Ok(Some(PathBuf::from("/tmp/run.lss")));
+        assert_eq!(save_toast_message(&result), "Saved splits to /tmp/run.lss");
+    }
+
+    #[test]
+    fn missing_splits_file_is_explained() {
+        assert_eq!(
+            save_toast_message(&Ok(None)),
+            "No splits file to save to, load splits first"
+        );
+    }
+
+    #[test]
+    fn failure_shows_the_error() {
+        let result = Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "permission denied",
+        ));
+        assert_eq!(
+            save_toast_message(&result),
+            "Could not save splits: permission denied"
+        );
+    }
+}
+
+#[cfg(test)]
+mod splits_file_uri_tests {
+    use super::splits_file_uri;
+    use std::path::Path;
+
+    #[test]
+    fn no_path_means_nothing_to_open() {
+        assert_eq!(splits_file_uri(None), None);
+        assert_eq!(splits_file_uri(Some(Path::new(""))), None);
+    }
+
+    #[test]
+    fn absolute_path_becomes_file_uri() {
+        assert_eq!(
+            splits_file_uri(Some(Path::new("/tmp/splits/run.lss"))).as_deref(),
+            Some("file:///tmp/splits/run.lss")
+        );
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            splits_file_uri(Some(Path::new("/tmp/my run#1.lss"))).as_deref(),
+            Some("file:///tmp/my%20run%231.lss")
+        );
+    }
+}
+
+#[cfg(test)]
+mod last_directory_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    #[gtk4::test]
+    fn chooser_opens_in_the_remembered_folder() {
+        gtk_test_init();
+        let dir = std::env::temp_dir();
+        let mut config = Config::default();
+        config.remember_directory(&dir.join("run.lss"));
+
+        let file_chooser = FileChooserDialog::new(
+            Some("Load Splits"),
+            None::<&gtk4::Window>,
+            gtk4::FileChooserAction::Open,
+            &[("Open", gtk4::ResponseType::Ok)],
+        );
+        open_in_last_directory(&file_chooser, &config);
+        assert_eq!(
+            file_chooser
+                .current_folder()
+                .and_then(|folder| folder.path()),
+            Some(dir)
+        );
+        file_chooser.destroy();
+    }
+}
+
+#[cfg(test)]
+mod splits_save_path_tests {
+    use super::splits_save_path;
+    use livesplit_core::run::parser::TimerKind;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn livesplit_files_are_saved_in_place() {
+        let path = Path::new("/tmp/any%.lss");
+        assert_eq!(splits_save_path(path, &TimerKind::LiveSplit), path);
+    }
+
+    #[test]
+    fn other_formats_get_a_sibling_lss_file() {
+        let dir = std::env::temp_dir().join("tuxsplit-save-path-test");
+        fs::create_dir_all(&dir).expect("dir");
+        let imported = dir.join("run.wsplit");
+        let _ = fs::remove_file(dir.join("run.lss"));
+        let _ = fs::remove_file(dir.join("run-1.lss"));
+
+        assert_eq!(
+            splits_save_path(&imported, &TimerKind::WSplit),
+            dir.join("run.lss")
+        );
+
+        // An existing .lss is never overwritten
+        fs::write(dir.join("run.lss"), "").expect("write");
+        assert_eq!(
+            splits_save_path(&imported, &TimerKind::Urn),
+            dir.join("run-1.lss")
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
+
+#[cfg(test)]
+mod load_decision_tests {
+    use super::{LoadDecision, load_decision};
+    use crate::config::LoadWhileRunning;
+    use livesplit_core::TimerPhase;
+
+    #[test]
+    fn idle_or_ended_timer_loads_directly() {
+        for behavior in [
+            LoadWhileRunning::Confirm,
+            LoadWhileRunning::Reset,
+            LoadWhileRunning::Refuse,
+        ] {
+            assert_eq!(
+                load_decision(TimerPhase::NotRunning, behavior),
+                LoadDecision::Load
+            );
+            assert_eq!(
+                load_decision(TimerPhase::Ended, behavior),
+                LoadDecision::Load
+            );
+        }
+    }
+
+    #[test]
+    fn running_or_paused_asks_first_by_default() {
+        let behavior = LoadWhileRunning::default();
+        assert_eq!(
+            load_decision(TimerPhase::Running, behavior),
+            LoadDecision::Confirm
+        );
+        assert_eq!(
+            load_decision(TimerPhase::Paused, behavior),
+            LoadDecision::Confirm
+        );
+    }
+
+    #[test]
+    fn running_or_paused_resets_first_when_configured() {
+        assert_eq!(
+            load_decision(TimerPhase::Running, LoadWhileRunning::Reset),
+            LoadDecision::ResetFirst
+        );
+        assert_eq!(
+            load_decision(TimerPhase::Paused, LoadWhileRunning::Reset),
+            LoadDecision::ResetFirst
+        );
+    }
+
+    #[test]
+    fn running_or_paused_refuses_when_configured() {
+        assert_eq!(
+            load_decision(TimerPhase::Running, LoadWhileRunning::Refuse),
+            LoadDecision::Refuse
+        );
+        assert_eq!(
+            load_decision(TimerPhase::Paused, LoadWhileRunning::Refuse),
+            LoadDecision::Refuse
+        );
+    }
+}
+
+#[cfg(test)]
+mod comparison_step_tests {
+    use super::comparison_step;
+    use crate::context::ComparisonStep;
+    use livesplit_core::HotkeyConfig;
+    use livesplit_core::hotkey::{Hotkey, KeyCode};
+
+    #[test]
+    fn configured_comparison_keys_pick_the_step() {
+        let previous = Hotkey::from(KeyCode::KeyP);
+        let next = Hotkey::from(KeyCode::KeyN);
+        let hotkeys = HotkeyConfig {
+            previous_comparison: Some(previous),
+            next_comparison: Some(next),
+            ..HotkeyConfig::default()
+        };
+
+        assert_eq!(
+            comparison_step(&hotkeys, Some(previous)),
+            Some(ComparisonStep::Previous)
+        );
+        assert_eq!(
+            comparison_step(&hotkeys, Some(next)),
+            Some(ComparisonStep::Next)
+        );
+        assert_eq!(
+            comparison_step(&hotkeys, Some(Hotkey::from(KeyCode::Numpad4))),
+            None
+        );
+        assert_eq!(comparison_step(&hotkeys, None), None);
+    }
+}
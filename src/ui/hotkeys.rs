@@ -0,0 +1,552 @@
+//! Editable global hotkeys: which actions can be bound, translating GDK key presses
+//! into livesplit-core hotkeys and the dialog to rebind them.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog};
+use gtk4::{
+    Align, Box as GtkBox, Button, CallbackAction, EventControllerKey, Grid, Label, ListBox,
+    Orientation, Overlay, Shortcut, ShortcutController, ShortcutTrigger, gdk,
+};
+use livesplit_core::HotkeyConfig;
+use livesplit_core::hotkey::{Hotkey, KeyCode, Modifiers};
+use tracing::warn;
+
+use crate::config::Config;
+use crate::context::TuxSplitContext;
+
+/// Everything the keybindings dialog binds: the global hotkeys and the reset that
+/// discards the attempt's golds and PB, which only the window listens to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Keybinds {
+    pub hotkeys: HotkeyConfig,
+    pub discard_reset: Option<Hotkey>,
+}
+
+impl Keybinds {
+    pub fn of(config: &Config) -> Self {
+        Self {
+            hotkeys: config.hotkeys,
+            discard_reset: config.general.discard_reset_hotkey,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    Split,
+    Skip,
+    Reset,
+    DiscardReset,
+    PreviousComparison,
+    Pause,
+    NextComparison,
+    Undo,
+}
+
+impl HotkeyAction {
+    pub const ALL: [Self; 8] = [
+        Self::Split,
+        Self::Skip,
+        Self::Reset,
+        Self::DiscardReset,
+        Self::PreviousComparison,
+        Self::Pause,
+        Self::NextComparison,
+        Self::Undo,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Split => "Start / Split",
+            Self::Skip => "Skip Split",
+            Self::Reset => "Reset (Save Golds/PB)",
+            Self::DiscardReset => "Reset (Discard)",
+            Self::PreviousComparison => "Previous Comparison",
+            Self::Pause => "Pause",
+            Self::NextComparison => "Next Comparison",
+            Self::Undo => "Undo",
+        }
+    }
+
+    pub const fn get(self, keybinds: &Keybinds) -> Option<Hotkey> {
+        let config = &keybinds.hotkeys;
+        match self {
+            Self::Split => config.split,
+            Self::Skip => config.skip,
+            Self::Reset => config.reset,
+            Self::DiscardReset => keybinds.discard_reset,
+            Self::PreviousComparison => config.previous_comparison,
+            Self::Pause => config.pause,
+            Self::NextComparison => config.next_comparison,
+            Self::Undo => config.undo,
+        }
+    }
+
+    pub fn set(self, keybinds: &mut Keybinds, hotkey: Option<Hotkey>) {
+        let config = &mut keybinds.hotkeys;
+        let slot = match self {
+            Self::Split => &mut config.split,
+            Self::Skip => &mut config.skip,
+            Self::Reset => &mut config.reset,
+            Self::DiscardReset => &mut keybinds.discard_reset,
+            Self::PreviousComparison => &mut config.previous_comparison,
+            Self::Pause => &mut config.pause,
+            Self::NextComparison => &mut config.next_comparison,
+            Self::Undo => &mut config.undo,
+        };
+        *slot = hotkey;
+    }
+}
+
+/// The other action already bound to `hotkey`, if any.
+pub fn hotkey_conflict(
+    config: &Keybinds,
+    action: HotkeyAction,
+    hotkey: Hotkey,
+) -> Option<HotkeyAction> {
+    HotkeyAction::ALL
+        .into_iter()
+        .find(|&other| other != action && other.get(config) == Some(hotkey))
+}
+
+/// Translates a GDK key press into a hotkey. `None` for keys the global hotkeys
+/// can't listen to (and for bare modifier keys).
+pub fn hotkey_from_key(key: gdk::Key, state: gdk::ModifierType) -> Option<Hotkey> {
+    let key_code = key_code_from_name(&key.to_lower().name()?)?;
+
+    let mut modifiers = Modifiers::empty();
+    for (mask, modifier) in [
+        (gdk::ModifierType::SHIFT_MASK, Modifiers::SHIFT),
+        (gdk::ModifierType::CONTROL_MASK, Modifiers::CONTROL),
+        (gdk::ModifierType::ALT_MASK, Modifiers::ALT),
+        (gdk::ModifierType::SUPER_MASK, Modifiers::META),
+    ] {
+        if state.contains(mask) {
+            modifiers.insert(modifier);
+        }
+    }
+
+    Some(Hotkey {
+        key_code,
+        modifiers,
+    })
+}
+
+/// Maps a GDK key name (e.g. `a`, `KP_3`, `F5`) to the W3C key code livesplit-core uses.
+fn key_code_from_name(name: &str) -> Option<KeyCode> {
+    let code = match name {
+        "space" => "Space".to_owned(),
+        "Return" => "Enter".to_owned(),
+        "BackSpace" => "Backspace".to_owned(),
+        "Tab" | "Home" | "End" | "Insert" | "Delete" | "Escape" => name.to_owned(),
+        "Page_Up" => "PageUp".to_owned(),
+        "Page_Down" => "PageDown".to_owned(),
+        "Up" | "Down" | "Left" | "Right" => format!("Arrow{name}"),
+        "minus" => "Minus".to_owned(),
+        "equal" => "Equal".to_owned(),
+        "comma" => "Comma".to_owned(),
+        "period" => "Period".to_owned(),
+        "slash" => "Slash".to_owned(),
+        "semicolon" => "Semicolon".to_owned(),
+        "apostrophe" => "Quote".to_owned(),
+        "grave" => "Backquote".to_owned(),
+        "backslash" => "Backslash".to_owned(),
+        "bracketleft" => "BracketLeft".to_owned(),
+        "bracketright" => "BracketRight".to_owned(),
+        "KP_Add" => "NumpadAdd".to_owned(),
+        "KP_Subtract" => "NumpadSubtract".to_owned(),
+        "KP_Multiply" => "NumpadMultiply".to_owned(),
+        "KP_Divide" => "NumpadDivide".to_owned(),
+        "KP_Decimal" | "KP_Delete" => "NumpadDecimal".to_owned(),
+        "KP_Enter" => "NumpadEnter".to_owned(),
+        // Numpad digits while Num Lock is off
+        "KP_Insert" => "Numpad0".to_owned(),
+        "KP_End" => "Numpad1".to_owned(),
+        "KP_Down" => "Numpad2".to_owned(),
+        "KP_Page_Down" | "KP_Next" => "Numpad3".to_owned(),
+        "KP_Left" => "Numpad4".to_owned(),
+        "KP_Begin" => "Numpad5".to_owned(),
+        "KP_Right" => "Numpad6".to_owned(),
+        "KP_Home" => "Numpad7".to_owned(),
+        "KP_Up" => "Numpad8".to_owned(),
+        "KP_Page_Up" | "KP_Prior" => "Numpad9".to_owned(),
+        _ => {
+            if let Some(digit) = name.strip_prefix("KP_")
+                && digit.len() == 1
+                && digit.chars().all(|c| c.is_ascii_digit())
+            {
+                format!("Numpad{digit}")
+            } else if name.len() == 1 && name.chars().all(|c| c.is_ascii_lowercase()) {
+                format!("Key{}", name.to_ascii_uppercase())
+            } else if name.len() == 1 && name.chars().all(|c| c.is_ascii_digit()) {
+                format!("Digit{name}")
+            } else if let Some(number) = name.strip_prefix('F')
+                && number.parse::<u8>().is_ok_and(|n| (1..=24).contains(&n))
+            {
+                name.to_owned()
+            } else {
+                return None;
+            }
+        }
+    };
+    code.parse().ok()
+}
+
+/// Whether the window handles `hotkey` as one of the reset keys. That is only the case
+/// with hold-to-reset, the global hotkeys take care of them otherwise (see
+/// `Config::system_hotkeys` and `Config::system_discard_reset`).
+pub fn window_reset(config: &Config, hotkey: Option<Hotkey>) -> bool {
+    config.hold_to_reset().is_some()
+        && hotkey.is_some_and(|hotkey| {
+            config.hotkeys.reset == Some(hotkey)
+                || config.general.discard_reset_hotkey == Some(hotkey)
+        })
+}
+
+/// Handles the reset keys with hold-to-reset, which the global hotkeys can't: the reset
+/// happens once the key was held for the configured time, releasing it earlier cancels.
+pub fn install_reset_keys(window: &impl IsA<gtk4::Widget>) {
+    let keys = EventControllerKey::new();
+    // Hardware keycode held down and the pending reset
+    let pending: Rc<RefCell<Option<(u32, glib::SourceId)>>> = Rc::new(RefCell::new(None));
+
+    {
+        let pending = pending.clone();
+        keys.connect_key_pressed(move |_, key, keycode, state| {
+            let ctx = TuxSplitContext::get_instance();
+            let hotkey = hotkey_from_key(key, state);
+            let (hold, update_splits) = {
+                let cfg = ctx.config();
+                (
+                    cfg.hold_to_reset().filter(|_| window_reset(&cfg, hotkey)),
+                    cfg.general.discard_reset_hotkey != hotkey,
+                )
+            };
+            let Some(hold) = hold else {
+                return glib::Propagation::Proceed;
+            };
+            // Key repeat while held
+            if pending.borrow().is_some() {
+                return glib::Propagation::Stop;
+            }
+
+            let pending_binding = pending.clone();
+            let source = glib::timeout_add_local_once(hold, move || {
+                pending_binding.borrow_mut().take();
+                TuxSplitContext::get_instance().reset(update_splits);
+            });
+            pending.replace(Some((keycode, source)));
+            glib::Propagation::Stop
+        });
+    }
+    keys.connect_key_released(move |_, _, keycode, _| {
+        let released = pending
+            .borrow()
+            .as_ref()
+            .is_some_and(|(held, _)| *held == keycode);
+        if released && let Some((_, source)) = pending.borrow_mut().take() {
+            source.remove();
+        }
+    });
+
+    window.add_controller(keys);
+}
+
+fn hotkey_label(hotkey: Option<Hotkey>) -> String {
+    hotkey.map_or_else(|| "Disabled".to_owned(), |hotkey| hotkey.to_string())
+}
+
+/// Window shortcuts registered by the header, the split list (and the overview
+/// itself), as shown in the keybinding overview.
+pub const APP_SHORTCUTS: [(&str, &str); 4] = [
+    ("Save Splits", "Ctrl+S"),
+    ("Open Splits File Externally", "Ctrl+Shift+O"),
+    ("Search Splits", "Ctrl+F"),
+    ("Show Keybindings", "F1"),
+];
+
+/// (action, key) rows of the keybinding overview: the global hotkeys as listed in the
+/// keybindings dialog, followed by the window shortcuts.
+pub fn cheat_sheet_entries(hotkeys: &Keybinds) -> Vec<(&'static str, String)> {
+    HotkeyAction::ALL
+        .into_iter()
+        .map(|action| (action.label(), hotkey_label(action.get(hotkeys))))
+        .chain(
+            APP_SHORTCUTS
+                .into_iter()
+                .map(|(action, key)| (action, key.to_owned())),
+        )
+        .collect()
+}
+
+/// Adds the keybinding overview on top of `overlay`, shown and hidden with F1 (Escape
+/// hides it too). It is filled from the current bindings every time it opens.
+pub fn install_cheat_sheet(window: &impl IsA<gtk4::Widget>, overlay: &Overlay) {
+    let sheet = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(12)
+        .halign(Align::Center)
+        .valign(Align::Center)
+        .css_classes(["osd", "card", "cheat-sheet"])
+        .visible(false)
+        .build();
+    let heading = Label::builder()
+        .label("Keybindings")
+        .css_classes(["title-4"])
+        .build();
+    let grid = Grid::builder().row_spacing(6).column_spacing(24).build();
+    sheet.append(&heading);
+    sheet.append(&grid);
+    overlay.add_overlay(&sheet);
+
+    let shortcuts = ShortcutController::new();
+    let sheet_weak = sheet.downgrade();
+    let grid_weak = grid.downgrade();
+    shortcuts.add_shortcut(Shortcut::new(
+        ShortcutTrigger::parse_string("F1"),
+        Some(CallbackAction::new(move |_, _| {
+            let (Some(sheet), Some(grid)) = (sheet_weak.upgrade(), grid_weak.upgrade()) else {
+                return glib::Propagation::Proceed;
+            };
+            if !sheet.is_visible() {
+                fill_cheat_sheet(
+                    &grid,
+                    &Keybinds::of(&TuxSplitContext::get_instance().config()),
+                );
+            }
+            sheet.set_visible(!sheet.is_visible());
+            glib::Propagation::Stop
+        })),
+    ));
+    let sheet_weak = sheet.downgrade();
+    shortcuts.add_shortcut(Shortcut::new(
+        ShortcutTrigger::parse_string("Escape"),
+        Some(CallbackAction::new(move |_, _| {
+            match sheet_weak.upgrade() {
+                Some(sheet) if sheet.is_visible() => {
+                    sheet.set_visible(false);
+                    glib::Propagation::Stop
+                }
+                _ => glib::Propagation::Proceed,
+            }
+        })),
+    ));
+    window.add_controller(shortcuts);
+}
+
+fn fill_cheat_sheet(grid: &Grid, hotkeys: &Keybinds) {
+    while let Some(child) = grid.first_child() {
+        grid.remove(&child);
+    }
+    for (row, (action, key)) in (0..).zip(cheat_sheet_entries(hotkeys)) {
+        let action = Label::builder().label(action).halign(Align::Start).build();
+        let key = Label::builder()
+            .label(key)
+            .halign(Align::End)
+            .css_classes(["dim-label", "monospace"])
+            .build();
+        grid.attach(&action, 0, row, 1, 1);
+        grid.attach(&key, 1, row, 1, 1);
+    }
+}
+
+/// Lists every action with its hotkey. Clicking a hotkey waits for the next key press
+/// and binds it right away; Escape cancels and Backspace disables the action.
+pub fn present_hotkeys_dialog(parent: &impl IsA<gtk4::Widget>) {
+    let dialog = AlertDialog::builder()
+        .heading("Keybindings")
+        .body("Click a keybinding and press the new key. Escape cancels, Backspace disables it.")
+        .default_response("ok")
+        .build();
+
+    // Captured keys must not trigger the timer while the dialog is open
+    let ctx = TuxSplitContext::get_instance();
+    ctx.disable_hotkeys();
+    dialog.connect_closed(|_| TuxSplitContext::get_instance().enable_hotkeys());
+
+    let list = ListBox::new();
+    list.add_css_class("boxed-list");
+    let hotkeys = Keybinds::of(&ctx.config());
+    for action in HotkeyAction::ALL {
+        let button = Button::builder()
+            .label(hotkey_label(action.get(&hotkeys)))
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
+            .build();
+        let row = ActionRow::builder().title(action.label()).build();
+        row.add_suffix(&button);
+        list.append(&row);
+
+        let capturing = Rc::new(Cell::new(false));
+        {
+            let capturing = capturing.clone();
+            button.connect_clicked(move |b| {
+                capturing.set(true);
+                b.set_label("Press a key…");
+            });
+        }
+
+        let keys = EventControllerKey::new();
+        let button_weak = button.downgrade();
+        let dialog_weak = dialog.downgrade();
+        keys.connect_key_pressed(move |_, key, _, state| {
+            if !capturing.get() {
+                return glib::Propagation::Proceed;
+            }
+            let (Some(button), Some(dialog)) = (button_weak.upgrade(), dialog_weak.upgrade())
+            else {
+                return glib::Propagation::Proceed;
+            };
+            let ctx = TuxSplitContext::get_instance();
+            let mut hotkeys = Keybinds::of(&ctx.config());
+
+            let binding = match key {
+                gdk::Key::Escape => {
+                    capturing.set(false);
+                    button.set_label(&hotkey_label(action.get(&hotkeys)));
+                    return glib::Propagation::Stop;
+                }
+                gdk::Key::BackSpace => None,
+                _ => {
+                    let Some(hotkey) = hotkey_from_key(key, state) else {
+                        // Modifier on its own or an unsupported key, keep waiting
+                        return glib::Propagation::Stop;
+                    };
+                    if let Some(other) = hotkey_conflict(&hotkeys, action, hotkey) {
+                        dialog.set_body(&format!(
+                            "{hotkey} is already used for \"{}\".",
+                            other.label()
+                        ));
+                        return glib::Propagation::Stop;
+                    }
+                    Some(hotkey)
+                }
+            };
+
+            capturing.set(false);
+            action.set(&mut hotkeys, binding);
+            let result = if action == HotkeyAction::DiscardReset {
+                ctx.set_discard_reset_hotkey(binding)
+            } else {
+                ctx.set_hotkeys(hotkeys.hotkeys)
+            };
+            if let Err(err) = result {
+                warn!("Could not rebind {}: {err}", action.label());
+                dialog.set_body(&format!("Could not rebind \"{}\": {err}", action.label()));
+            }
+            button.set_label(&hotkey_label(action.get(&Keybinds::of(&ctx.config()))));
+            glib::Propagation::Stop
+        });
+        button.add_controller(keys);
+    }
+
+    dialog.set_extra_child(Some(&list));
+    dialog.add_response("ok", "Done");
+    dialog.present(Some(parent));
+}
+
+#[cfg(test)]
+mod hotkeys_tests {
+    use super::*;
+
+    fn numpad(n: u8) -> Hotkey {
+        Hotkey {
+            key_code: format!("Numpad{n}").parse().unwrap(),
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    #[test]
+    fn actions_round_trip_through_the_config() {
+        let mut config = Keybinds::default();
+        for (i, action) in HotkeyAction::ALL.into_iter().enumerate() {
+            action.set(&mut config, Some(numpad(i as u8)));
+        }
+        for (i, action) in HotkeyAction::ALL.into_iter().enumerate() {
+            assert_eq!(action.get(&config), Some(numpad(i as u8)));
+        }
+
+        HotkeyAction::Reset.set(&mut config, None);
+        assert_eq!(HotkeyAction::Reset.get(&config), None);
+        // The discarding reset isn't part of the hotkey system's config
+        assert_eq!(config.discard_reset, Some(numpad(3)));
+    }
+
+    #[test]
+    fn window_handles_only_held_resets() {
+        let mut config = Config::default();
+        config.hotkeys.reset = Some(numpad(3));
+        config.general.discard_reset_hotkey = Some(numpad(9));
+        // Both resets are up to the global hotkeys
+        assert!(!window_reset(&config, Some(numpad(3))));
+        assert!(!window_reset(&config, Some(numpad(9))));
+        assert_eq!(config.system_hotkeys().reset, Some(numpad(3)));
+        assert_eq!(config.system_discard_reset(), Some(numpad(9)));
+
+        config.general.hold_to_reset_ms = Some(500);
+        assert!(window_reset(&config, Some(numpad(3))));
+        assert!(window_reset(&config, Some(numpad(9))));
+        assert!(!window_reset(&config, Some(numpad(1))));
+        assert!(!window_reset(&config, None));
+        assert_eq!(config.system_hotkeys().reset, None);
+        assert_eq!(config.system_discard_reset(), None);
+    }
+
+    #[test]
+    fn duplicate_bindings_are_detected() {
+        let mut config = Keybinds::default();
+        HotkeyAction::Split.set(&mut config, Some(numpad(1)));
+        HotkeyAction::Reset.set(&mut config, Some(numpad(3)));
+
+        assert_eq!(
+            hotkey_conflict(&config, HotkeyAction::Reset, numpad(1)),
+            Some(HotkeyAction::Split)
+        );
+        // Rebinding an action to its own key is fine
+        assert_eq!(
+            hotkey_conflict(&config, HotkeyAction::Reset, numpad(3)),
+            None
+        );
+        assert_eq!(
+            hotkey_conflict(&config, HotkeyAction::Reset, numpad(9)),
+            None
+        );
+    }
+
+    #[test]
+    fn key_presses_map_to_hotkeys() {
+        let none = gdk::ModifierType::empty();
+        assert_eq!(hotkey_from_key(gdk::Key::KP_3, none), Some(numpad(3)));
+        // Num Lock off still means the numpad key
+        assert_eq!(
+            hotkey_from_key(gdk::Key::KP_Page_Down, none),
+            Some(numpad(3))
+        );
+
+        let shift_a = hotkey_from_key(gdk::Key::A, gdk::ModifierType::SHIFT_MASK).unwrap();
+        assert_eq!(shift_a.key_code, "KeyA".parse().unwrap());
+        assert_eq!(shift_a.modifiers, Modifiers::SHIFT);
+
+        let f5 = hotkey_from_key(gdk::Key::F5, gdk::ModifierType::CONTROL_MASK).unwrap();
+        assert_eq!(f5.key_code, "F5".parse().unwrap());
+        assert_eq!(f5.modifiers, Modifiers::CONTROL);
+
+        assert_eq!(hotkey_from_key(gdk::Key::Shift_L, none), None);
+    }
+
+    #[test]
+    fn cheat_sheet_lists_hotkeys_then_window_shortcuts() {
+        let mut config = Keybinds::default();
+        HotkeyAction::Split.set(&mut config, Some(numpad(1)));
+        HotkeyAction::Undo.set(&mut config, None);
+
+        let entries = cheat_sheet_entries(&config);
+        assert_eq!(entries.len(), HotkeyAction::ALL.len() + APP_SHORTCUTS.len());
+        assert_eq!(entries[0], ("Start / Split", numpad(1).to_string()));
+        assert!(entries.contains(&("Undo", "Disabled".to_owned())));
+        assert_eq!(entries.last(), Some(&("Show Keybindings", "F1".to_owned())));
+    }
+}
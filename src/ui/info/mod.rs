@@ -1,9 +1,11 @@
-use crate::config::Config;
+use crate::config::{BestPossibleWhenEnded, Config};
 use crate::utils::comparisons::{
-    best_comparison_values, best_segment_duration, classify_split_label,
-    current_attempt_running_duration, format_signed, previous_comparison_values,
-    previous_split_combined_gold_and_prev_comparison, real_time_sob, segment_best_time,
-    segment_comparison_time, segment_split_time,
+    BEST_SEGMENTS_COMPARISON, average_segment_duration, best_comparison_values,
+    best_segment_duration, classify_split_label, current_attempt_running_duration,
+    current_or_first_segment, format_signed, pace_class, pace_target_time,
+    previous_comparison_values, previous_split_combined_gold_and_prev_comparison,
+    projected_goal_delta, real_time_sob, segment_best_time, segment_comparison_time,
+    segment_split_time, segment_time_in_comparison, sum_of_best, total_possible_time_save,
 };
 
 use gtk4::{CenterBox, Label, Orientation::Horizontal, prelude::WidgetExt};
@@ -19,9 +21,14 @@ pub enum AdditionalInfoKind {
     CurrentPace,
     TotalPlaytime,
     PbChance,
+    SumOfBest,
+    SegmentProgress,
+    AttemptCount,
+    AverageSegment,
+    GoalTime,
 }
 
-pub static ALL_ADDITIONAL_INFOS: [AdditionalInfoKind; 7] = [
+pub static ALL_ADDITIONAL_INFOS: [AdditionalInfoKind; 12] = [
     AdditionalInfoKind::PrevSegmentDiff,
     AdditionalInfoKind::PrevSegmentBest,
     AdditionalInfoKind::BestPossibleTime,
@@ -29,6 +36,11 @@ pub static ALL_ADDITIONAL_INFOS: [AdditionalInfoKind; 7] = [
     AdditionalInfoKind::CurrentPace,
     AdditionalInfoKind::TotalPlaytime,
     AdditionalInfoKind::PbChance,
+    AdditionalInfoKind::SumOfBest,
+    AdditionalInfoKind::SegmentProgress,
+    AdditionalInfoKind::AttemptCount,
+    AdditionalInfoKind::AverageSegment,
+    AdditionalInfoKind::GoalTime,
 ];
 
 pub trait AdditionalInfo {
@@ -52,10 +64,33 @@ pub struct PrevSegmentBestInfo {
 pub struct BestPossibleTimeInfo {
     container: CenterBox,
     value: Label,
+    // Last value computed while running, for the frozen ended behavior
+    last_running: Option<time::Duration>,
+}
+
+pub struct SumOfBestInfo {
+    container: CenterBox,
+    value: Label,
+}
+
+pub struct SegmentProgressInfo {
+    container: CenterBox,
+    value: Label,
+}
+
+pub struct AttemptCountInfo {
+    container: CenterBox,
+    value: Label,
+}
+
+pub struct AverageSegmentInfo {
+    container: CenterBox,
+    value: Label,
 }
 
 pub struct PossibleTimeSaveInfo {
     container: CenterBox,
+    heading: Label,
     value: Label,
 }
 
@@ -64,6 +99,11 @@ pub struct CurrentPaceInfo {
     value: Label,
 }
 
+pub struct GoalTimeInfo {
+    container: CenterBox,
+    value: Label,
+}
+
 pub struct PbChanceInfo {
     container: CenterBox,
     value: Label,
@@ -179,42 +219,24 @@ impl AdditionalInfo for PrevSegmentBestInfo {
             index -= 1; // Previous segment index
 
             let segment = &timer.run().segments()[index];
-
-            let segment_best_time = segment_best_time(segment, timer);
-            let (_, previous_split_time) = previous_comparison_values(timer, index);
-            let (previous_best_duration, previous_best_time) = best_comparison_values(timer, index);
-            let segment_best_duration = segment_best_time
-                .checked_sub(previous_best_duration)
-                .unwrap_or_default()
-                .abs();
-
-            let split_time = segment_split_time(segment, timer);
-
-            if split_time == time::Duration::ZERO {
-                self.value.set_label("");
-            } else {
-                let diff = split_time
-                    .checked_sub(previous_split_time)
-                    .unwrap_or_default()
-                    .checked_sub(segment_best_duration)
-                    .unwrap_or_default();
-
-                if segment_best_time != time::Duration::ZERO {
-                    self.value.set_label(format_signed(diff, config).as_str());
-
-                    let gold_duration = best_segment_duration(segment, timer);
-                    let split_duration = split_time
-                        .checked_sub(previous_best_time)
-                        .unwrap_or_default();
-
-                    self.value.add_css_class(classify_split_label(
-                        segment_best_duration,
-                        split_duration,
-                        diff,
-                        gold_duration,
-                        false,
-                    ));
-                }
+            let comparison = config
+                .general
+                .prev_segment_best_comparison
+                .as_deref()
+                .unwrap_or(BEST_SEGMENTS_COMPARISON);
+
+            if let Some(values) = prev_segment_best_values(timer, index, comparison) {
+                self.value
+                    .set_label(format_signed(values.diff, config).as_str());
+
+                let gold_duration = best_segment_duration(segment, timer);
+                self.value.add_css_class(classify_split_label(
+                    values.reference_duration,
+                    values.split_duration,
+                    values.diff,
+                    gold_duration,
+                    false,
+                ));
             }
         } else {
             self.value.set_label("");
@@ -239,7 +261,11 @@ impl AdditionalInfo for BestPossibleTimeInfo {
         container.set_start_widget(Some(&label));
         container.set_end_widget(Some(&value));
 
-        let mut res = Self { container, value };
+        let mut res = Self {
+            container,
+            value,
+            last_running: None,
+        };
 
         res.update(timer, config); // Initialize with default timer state
 
@@ -248,27 +274,11 @@ impl AdditionalInfo for BestPossibleTimeInfo {
 
     fn update(&mut self, timer: &Timer, config: &Config) {
         if timer.current_phase().is_not_running() {
+            self.last_running = None;
             self.value.set_label("");
         } else if timer.current_phase().is_running() || timer.current_phase().is_paused() {
-            let segment = timer.current_split().unwrap_or(timer.run().segment(0));
-
-            let segment_best_duration = segment_best_time(segment, timer);
-
-            // Diff to SOB
-            let diff = current_attempt_running_duration(timer)
-                .checked_sub(segment_best_duration)
-                .unwrap_or_default();
-
-            // We will be adding only diff time to the best possible time when we are behind
-            let live_addition = if diff.is_positive() {
-                diff
-            } else {
-                time::Duration::ZERO
-            };
-
-            let best_possible_time = real_time_sob(timer)
-                .checked_add(live_addition)
-                .unwrap_or_default();
+            let best_possible_time = running_best_possible_time(timer);
+            self.last_running = Some(best_possible_time);
             if best_possible_time == time::Duration::ZERO {
                 self.value.set_label("");
             } else {
@@ -281,13 +291,13 @@ impl AdditionalInfo for BestPossibleTimeInfo {
                 );
             }
         } else if timer.current_phase().is_ended() {
-            self.value.set_label(
-                config
-                    .format
-                    .segment
-                    .format_duration(&current_attempt_running_duration(timer))
-                    .as_str(),
+            let ended = ended_best_possible_time(
+                timer,
+                config.general.best_possible_when_ended,
+                self.last_running,
             );
+            self.value
+                .set_label(config.format.segment.format_duration(&ended).as_str());
         }
     }
 
@@ -300,16 +310,20 @@ impl AdditionalInfo for PossibleTimeSaveInfo {
     fn new(timer: &Timer, config: &Config) -> Self {
         let container = CenterBox::builder().orientation(Horizontal).build();
 
-        let label = Label::builder()
+        let heading = Label::builder()
             .label("Possible Time Save:")
             .css_classes(["heading"])
             .build();
         let value = Label::builder().label("").css_classes(["timer"]).build();
 
-        container.set_start_widget(Some(&label));
+        container.set_start_widget(Some(&heading));
         container.set_end_widget(Some(&value));
 
-        let mut res = Self { container, value };
+        let mut res = Self {
+            container,
+            heading,
+            value,
+        };
 
         res.update(timer, config); // Initialize with default timer state
 
@@ -317,17 +331,34 @@ impl AdditionalInfo for PossibleTimeSaveInfo {
     }
 
     fn update(&mut self, timer: &Timer, config: &Config) {
+        let total = config.general.possible_time_save_total;
+        let heading = if total {
+            "Possible Time Save (Total):"
+        } else {
+            "Possible Time Save:"
+        };
+        if self.heading.label().as_str() != heading {
+            self.heading.set_label(heading);
+        }
+
         if timer.current_phase().is_not_running() {
             self.value.set_label("");
+        } else if total && (timer.current_phase().is_running() || timer.current_phase().is_paused())
+        {
+            let index = timer.current_split_index().unwrap_or(0);
+            let save = total_possible_time_save(timer, index);
+            self.value
+                .set_label(config.format.comparison.format_duration(&save).as_str());
         } else if timer.current_phase().is_running() || timer.current_phase().is_paused() {
             let index = timer.current_split_index().unwrap_or(0);
 
+            let Some(segment) = current_or_first_segment(timer) else {
+                self.value.set_label("");
+                return;
+            };
             let (_, combined_gold, previous_comparion_time) =
                 previous_split_combined_gold_and_prev_comparison(timer, index);
-            let current_comparison_time = segment_comparison_time(
-                timer.current_split().unwrap_or(timer.run().segment(0)),
-                timer,
-            );
+            let current_comparison_time = segment_comparison_time(segment, timer);
 
             // Diff from gold to comp. This is the possible time save
             let gold_diff = current_comparison_time
@@ -375,14 +406,25 @@ impl AdditionalInfo for CurrentPaceInfo {
 
     fn update(&mut self, timer: &Timer, config: &Config) {
         if timer.current_phase().is_not_running() {
+            self.value.set_css_classes(&["timer"]);
             self.value.set_label("");
         } else {
             let timer_snaptshot = timer.snapshot();
             let pace = current_pace::calculate(&timer_snaptshot, timer.current_comparison())
                 .0
                 .unwrap_or_default();
-            let pace = config.format.timer.format_time_span(&pace);
-            self.value.set_label(&pace);
+            let target = pace_target_time(
+                config.general.pace_target,
+                timer.run(),
+                timer.current_timing_method(),
+                config.general.goal_time,
+            );
+            match pace_class(pace, target) {
+                Some(class) => self.value.set_css_classes(&["timer", class]),
+                None => self.value.set_css_classes(&["timer"]),
+            }
+            self.value
+                .set_label(&config.format.timer.format_time_span(&pace));
         }
     }
 
@@ -448,11 +490,454 @@ impl AdditionalInfo for TotalPlaytimeInfo {
 
     fn update(&mut self, timer: &Timer, config: &Config) {
         let playtime = total_playtime::calculate(timer);
+        // Playtime easily exceeds a day, so always render the day count
+        let mut format = config.format.comparison.clone();
+        format.show_days = true;
+        self.value.set_label(&format.format_time_span(&playtime));
+    }
+
+    fn container(&self) -> &CenterBox {
+        &self.container
+    }
+}
+
+impl AdditionalInfo for SumOfBestInfo {
+    fn new(timer: &Timer, config: &Config) -> Self {
+        let container = CenterBox::builder().orientation(Horizontal).build();
+
+        let label = Label::builder()
+            .label("Sum of Best:")
+            .css_classes(["heading"])
+            .build();
+        let value = Label::builder().label("").css_classes(["timer"]).build();
+
+        container.set_start_widget(Some(&label));
+        container.set_end_widget(Some(&value));
+
+        let mut res = Self { container, value };
+
+        res.update(timer, config); // Initialize with default timer state
+
+        res
+    }
+
+    fn update(&mut self, timer: &Timer, config: &Config) {
+        // Static value of the saved run, so it only moves when golds are saved
+        let sob = sum_of_best(timer.run(), timer.current_timing_method());
         self.value
-            .set_label(&config.format.comparison.format_time_span(&playtime));
+            .set_label(&config.format.segment.format_duration_opt(sob));
     }
 
     fn container(&self) -> &CenterBox {
         &self.container
     }
 }
+
+impl AdditionalInfo for SegmentProgressInfo {
+    fn new(timer: &Timer, config: &Config) -> Self {
+        let container = CenterBox::builder().orientation(Horizontal).build();
+
+        let label = Label::builder()
+            .label("Splits:")
+            .css_classes(["heading"])
+            .build();
+        let value = Label::builder().label("").css_classes(["timer"]).build();
+
+        container.set_start_widget(Some(&label));
+        container.set_end_widget(Some(&value));
+
+        let mut res = Self { container, value };
+
+        res.update(timer, config); // Initialize with default timer state
+
+        res
+    }
+
+    fn update(&mut self, timer: &Timer, _config: &Config) {
+        self.value.set_label(&segment_progress_text(timer));
+    }
+
+    fn container(&self) -> &CenterBox {
+        &self.container
+    }
+}
+
+impl AdditionalInfo for AttemptCountInfo {
+    fn new(timer: &Timer, config: &Config) -> Self {
+        let container = CenterBox::builder().orientation(Horizontal).build();
+
+        let label = Label::builder()
+            .label("Attempts:")
+            .css_classes(["heading"])
+            .build();
+        let value = Label::builder().label("").css_classes(["timer"]).build();
+
+        container.set_start_widget(Some(&label));
+        container.set_end_widget(Some(&value));
+
+        let mut res = Self { container, value };
+
+        res.update(timer, config); // Initialize with default timer state
+
+        res
+    }
+
+    fn update(&mut self, timer: &Timer, _config: &Config) {
+        // Starting a run bumps the count, so it moves as soon as the attempt begins
+        self.value.set_label(&attempt_count_text(timer));
+    }
+
+    fn container(&self) -> &CenterBox {
+        &self.container
+    }
+}
+
+impl AdditionalInfo for AverageSegmentInfo {
+    fn new(timer: &Timer, config: &Config) -> Self {
+        let container = CenterBox::builder().orientation(Horizontal).build();
+
+        let label = Label::builder()
+            .label("Average Segment:")
+            .css_classes(["heading"])
+            .build();
+        let value = Label::builder().label("").css_classes(["timer"]).build();
+
+        container.set_start_widget(Some(&label));
+        container.set_end_widget(Some(&value));
+
+        let mut res = Self { container, value };
+
+        res.update(timer, config); // Initialize with default timer state
+
+        res
+    }
+
+    fn update(&mut self, timer: &Timer, config: &Config) {
+        let run = timer.run();
+        if run.is_empty() {
+            self.value.set_label("--");
+            return;
+        }
+        // Before the run starts this previews the first segment, after it ends the last one
+        let index = timer.current_split_index().unwrap_or(0).min(run.len() - 1);
+        let average = average_segment_duration(run, index, timer.current_timing_method());
+        self.value
+            .set_label(&config.format.segment.format_duration_opt(average));
+    }
+
+    fn container(&self) -> &CenterBox {
+        &self.container
+    }
+}
+
+impl AdditionalInfo for GoalTimeInfo {
+    fn new(timer: &Timer, config: &Config) -> Self {
+        let container = CenterBox::builder().orientation(Horizontal).build();
+
+        let label = Label::builder()
+            .label("Goal:")
+            .css_classes(["heading"])
+            .build();
+        let value = Label::builder().label("").css_classes(["timer"]).build();
+
+        container.set_start_widget(Some(&label));
+        container.set_end_widget(Some(&value));
+
+        let mut res = Self { container, value };
+
+        res.update(timer, config); // Initialize with default timer state
+
+        res
+    }
+
+    /// Shows the goal itself until the attempt starts, then how far the current pace
+    /// projects the finish from it.
+    fn update(&mut self, timer: &Timer, config: &Config) {
+        self.value.set_css_classes(&["timer"]);
+        let Some(goal) = config.general.goal_time else {
+            self.value.set_label("--");
+            return;
+        };
+        if timer.current_phase().is_not_running() {
+            self.value
+                .set_label(&config.format.timer.format_time_span(&goal));
+            return;
+        }
+        let pace = current_pace::calculate(&timer.snapshot(), timer.current_comparison()).0;
+        let Some(pace) = pace else {
+            self.value.set_label("--");
+            return;
+        };
+        match pace_class(pace, Some(goal)) {
+            Some(class) => self.value.set_css_classes(&["timer", class]),
+            None => self.value.set_css_classes(&["timer"]),
+        }
+        self.value
+            .set_label(&format_signed(projected_goal_delta(pace, goal), config));
+    }
+
+    fn container(&self) -> &CenterBox {
+        &self.container
+    }
+}
+
+struct PrevSegmentBestValues {
+    /// Segment time minus the reference segment time.
+    diff: time::Duration,
+    /// Segment time of the reference comparison.
+    reference_duration: time::Duration,
+    split_duration: time::Duration,
+}
+
+/// Compares the segment at `index` against its segment time in `comparison` ("Best
+/// Segments" by default). `None` when the segment was skipped or the comparison has
+/// no time for it.
+fn prev_segment_best_values(
+    timer: &Timer,
+    index: usize,
+    comparison: &str,
+) -> Option<PrevSegmentBestValues> {
+    let segment = &timer.run().segments()[index];
+
+    let reference_time = segment_time_in_comparison(segment, timer, comparison);
+    let (_, previous_split_time) = previous_comparison_values(timer, index);
+    let (previous_reference_duration, previous_reference_time) =
+        best_comparison_values(timer, index, comparison);
+    let reference_duration = reference_time
+        .checked_sub(previous_reference_duration)
+        .unwrap_or_default()
+        .abs();
+
+    let split_time = segment_split_time(segment, timer);
+    if split_time == time::Duration::ZERO || reference_time == time::Duration::ZERO {
+        return None;
+    }
+
+    let diff = split_time
+        .checked_sub(previous_split_time)
+        .unwrap_or_default()
+        .checked_sub(reference_duration)
+        .unwrap_or_default();
+    let split_duration = split_time
+        .checked_sub(previous_reference_time)
+        .unwrap_or_default();
+
+    Some(PrevSegmentBestValues {
+        diff,
+        reference_duration,
+        split_duration,
+    })
+}
+
+fn running_best_possible_time(timer: &Timer) -> time::Duration {
+    let segment_best_duration = current_or_first_segment(timer)
+        .map_or(time::Duration::ZERO, |s| segment_best_time(s, timer));
+
+    // Diff to SOB
+    let diff = current_attempt_running_duration(timer)
+        .checked_sub(segment_best_duration)
+        .unwrap_or_default();
+
+    // We will be adding only diff time to the best possible time when we are behind
+    let live_addition = if diff.is_positive() {
+        diff
+    } else {
+        time::Duration::ZERO
+    };
+
+    real_time_sob(timer)
+        .checked_add(live_addition)
+        .unwrap_or_default()
+}
+
+/// Value shown after the attempt ended. Frozen falls back to the final time when
+/// nothing was computed while running (e.g. the info was just created).
+fn ended_best_possible_time(
+    timer: &Timer,
+    behavior: BestPossibleWhenEnded,
+    last_running: Option<time::Duration>,
+) -> time::Duration {
+    match (behavior, last_running) {
+        (BestPossibleWhenEnded::Frozen, Some(frozen)) => frozen,
+        _ => current_attempt_running_duration(timer),
+    }
+}
+
+fn attempt_count_text(timer: &Timer) -> String {
+    timer.run().attempt_count().to_string()
+}
+
+/// "completed / total" splits. Once the run ended every split is completed, and
+/// before it starts none are.
+fn segment_progress_text(timer: &Timer) -> String {
+    let total = timer.run().len();
+    let completed = if timer.current_phase().is_ended() {
+        total
+    } else {
+        timer.current_split_index().unwrap_or(0).min(total)
+    };
+    format!("{completed} / {total}")
+}
+
+#[cfg(test)]
+mod attempt_count_tests {
+    use super::attempt_count_text;
+    use livesplit_core::{Run, Segment, Timer};
+
+    #[test]
+    fn fresh_run_has_no_attempts() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split"));
+        let timer = Timer::new(run).expect("timer");
+
+        assert_eq!(attempt_count_text(&timer), "0");
+    }
+
+    #[test]
+    fn starting_an_attempt_increments_count() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("Split"));
+        run.set_attempt_count(41);
+        let mut timer = Timer::new(run).expect("timer");
+
+        timer.start();
+        assert_eq!(attempt_count_text(&timer), "42");
+    }
+}
+
+#[cfg(test)]
+mod segment_progress_tests {
+    use super::segment_progress_text;
+    use livesplit_core::{Run, Segment, Timer};
+
+    fn make_timer(segments: usize) -> Timer {
+        let mut run = Run::new();
+        for i in 0..segments {
+            run.push_segment(Segment::new(format!("Split {i}")));
+        }
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn not_running_shows_zero_completed() {
+        let timer = make_timer(12);
+        assert_eq!(segment_progress_text(&timer), "0 / 12");
+    }
+
+    #[test]
+    fn mid_run_counts_completed_splits() {
+        let mut timer = make_timer(12);
+        timer.start();
+        for _ in 0..3 {
+            timer.split();
+        }
+        assert_eq!(segment_progress_text(&timer), "3 / 12");
+    }
+
+    #[test]
+    fn ended_run_shows_total() {
+        let mut timer = make_timer(2);
+        timer.start();
+        timer.split();
+        timer.split();
+        assert!(timer.current_phase().is_ended());
+        assert_eq!(segment_progress_text(&timer), "2 / 2");
+    }
+}
+
+#[cfg(test)]
+mod best_possible_ended_tests {
+    use super::ended_best_possible_time;
+    use crate::config::BestPossibleWhenEnded;
+    use crate::utils::comparisons::current_attempt_running_duration;
+    use livesplit_core::{Run, Segment, Timer};
+
+    fn ended_timer() -> Timer {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        run.push_segment(Segment::new("B"));
+        let mut timer = Timer::new(run).expect("timer");
+        timer.start();
+        timer.split();
+        timer.split();
+        assert!(timer.current_phase().is_ended());
+        timer
+    }
+
+    #[test]
+    fn final_time_shows_the_attempt_duration() {
+        let timer = ended_timer();
+        let frozen = Some(time::Duration::seconds(90));
+
+        assert_eq!(
+            ended_best_possible_time(&timer, BestPossibleWhenEnded::FinalTime, frozen),
+            current_attempt_running_duration(&timer)
+        );
+    }
+
+    #[test]
+    fn frozen_keeps_the_last_running_value() {
+        let timer = ended_timer();
+        let frozen = time::Duration::seconds(90);
+
+        assert_eq!(
+            ended_best_possible_time(&timer, BestPossibleWhenEnded::Frozen, Some(frozen)),
+            frozen
+        );
+        // Nothing computed while running
+        assert_eq!(
+            ended_best_possible_time(&timer, BestPossibleWhenEnded::Frozen, None),
+            current_attempt_running_duration(&timer)
+        );
+    }
+}
+
+#[cfg(test)]
+mod prev_segment_best_tests {
+    use super::prev_segment_best_values;
+    use crate::utils::comparisons::BEST_SEGMENTS_COMPARISON;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, Timer, TimingMethod};
+
+    /// First segment split at 12s game time, best segment 10s, "Target" comparison 8s.
+    fn timer_after_first_split() -> Timer {
+        let game = |s: f64| Time::new().with_game_time(Some(TimeSpan::from_seconds(s)));
+        let mut run = Run::new();
+        run.custom_comparisons_mut().push("Target".to_owned());
+        let mut first = Segment::new("A");
+        *first.best_segment_time_mut() = game(10.0);
+        *first.comparison_mut("Target") = game(8.0);
+        run.push_segment(first);
+        run.push_segment(Segment::new("B"));
+        run.regenerate_comparisons();
+
+        let mut timer = Timer::new(run).expect("timer");
+        timer.set_current_timing_method(TimingMethod::GameTime);
+        timer.start();
+        timer.initialize_game_time();
+        timer.pause_game_time();
+        timer.set_game_time(TimeSpan::from_seconds(12.0));
+        timer.split();
+        timer
+    }
+
+    #[test]
+    fn defaults_to_best_segments() {
+        let timer = timer_after_first_split();
+        let values = prev_segment_best_values(&timer, 0, BEST_SEGMENTS_COMPARISON).expect("values");
+        assert_eq!(values.diff, time::Duration::seconds(2));
+    }
+
+    #[test]
+    fn configured_comparison_is_used() {
+        let timer = timer_after_first_split();
+        let values = prev_segment_best_values(&timer, 0, "Target").expect("values");
+        assert_eq!(values.diff, time::Duration::seconds(4));
+        assert_eq!(values.reference_duration, time::Duration::seconds(8));
+    }
+
+    #[test]
+    fn comparison_without_times_shows_nothing() {
+        let timer = timer_after_first_split();
+        assert!(prev_segment_best_values(&timer, 0, "Missing").is_none());
+    }
+}
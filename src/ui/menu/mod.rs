@@ -1,9 +1,22 @@
 use adw::{
-    ComboRow, ExpanderRow, PreferencesDialog, PreferencesGroup, PreferencesPage, SpinRow,
-    SwitchRow, prelude::*,
+    ActionRow, ComboRow, EntryRow, ExpanderRow, PreferencesDialog, PreferencesGroup,
+    PreferencesPage, SpinRow, SwitchRow, prelude::*,
 };
 use gtk4::{self as gtk, StringList};
-use livesplit_core::TimingMethod;
+use livesplit_core::{TimeSpan, TimingMethod};
+
+use crate::config::{
+    BestPossibleWhenEnded, DEFAULT_ICON_CACHE_SIZE, EditorCommit, IdleDisplay, InfoPosition,
+    MAX_ICON_SIZE, MIN_ICON_SIZE, PaceTarget, SplitDisplay, TimerOrientation, goal_time_text,
+};
+use crate::formatters::TimeFormatPreset;
+use crate::formatters::time::parse_hms;
+use crate::ui::styles::{
+    DELTA_COLOR_CLASSES, MAX_FONT_SCALE, MAX_TIMER_SCALE, MIN_FONT_SCALE, MIN_TIMER_SCALE,
+    MIN_WINDOW_OPACITY, apply_delta_colors, apply_text_scales, apply_window_appearance,
+    window_opacity,
+};
+use crate::ui::timer::icons::icon_size;
 
 #[derive(Clone, Copy)]
 enum FormatTarget {
@@ -56,6 +69,187 @@ impl TimerPreferencesDialog {
         let timing_group = PreferencesGroup::builder().title("Timing").build();
         let timing_row = self.build_timing_method_row();
         timing_group.add(&timing_row);
+
+        let click_to_split_row = SwitchRow::builder()
+            .title("Split on Click")
+            .subtitle("Clicking the timer starts or splits; Ctrl/Shift + click resets")
+            .build();
+        click_to_split_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .click_to_split,
+        );
+        click_to_split_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.click_to_split = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&click_to_split_row);
+
+        let pause_on_unfocus_row = SwitchRow::builder()
+            .title("Pause When Unfocused")
+            .subtitle("Pauses a running attempt while the window is in the background")
+            .build();
+        pause_on_unfocus_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .pause_on_unfocus,
+        );
+        pause_on_unfocus_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.pause_on_unfocus = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&pause_on_unfocus_row);
+
+        let pause_on_suspend_row = SwitchRow::builder()
+            .title("Pause After Suspend")
+            .subtitle("Pauses a running attempt when the system went to sleep during it")
+            .build();
+        pause_on_suspend_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .pause_on_suspend,
+        );
+        pause_on_suspend_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.pause_on_suspend = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&pause_on_suspend_row);
+
+        let start_paused_row = SwitchRow::builder()
+            .title("Start Paused")
+            .subtitle("Starting primes the timer at its offset, the next split starts counting")
+            .build();
+        start_paused_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .start_paused,
+        );
+        start_paused_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.start_paused = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&start_paused_row);
+
+        let default_run_prompt_row = SwitchRow::builder()
+            .title("Prompt to Load Splits")
+            .subtitle("Shows a prompt instead of the placeholder timer while no splits are loaded")
+            .build();
+        default_run_prompt_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .default_run_prompt,
+        );
+        default_run_prompt_row.connect_active_notify(|r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.general.default_run_prompt = r.is_active();
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+        timing_group.add(&default_run_prompt_row);
+
+        let announce_pb_row = SwitchRow::builder()
+            .title("Announce Personal Bests")
+            .subtitle("Posts new Personal Bests to the Twitch chat set in the config")
+            .build();
+        announce_pb_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .announce_pb,
+        );
+        announce_pb_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.announce_pb = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&announce_pb_row);
+
+        let announce_reset_row = SwitchRow::builder()
+            .title("Announce Resets")
+            .subtitle("Also posts to the Twitch chat when an attempt is reset")
+            .build();
+        announce_reset_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .announce_reset,
+        );
+        announce_reset_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.announce_reset = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&announce_reset_row);
+
+        let commit_on_change_row = SwitchRow::builder()
+            .title("Apply Edits While Typing")
+            .subtitle("The split editor applies valid times shortly after typing stops")
+            .build();
+        commit_on_change_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .editor_commit
+                == EditorCommit::OnChange,
+        );
+        commit_on_change_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.editor_commit = if r.is_active() {
+                    EditorCommit::OnChange
+                } else {
+                    EditorCommit::OnUnfocus
+                };
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        timing_group.add(&commit_on_change_row);
+
+        let hold_to_reset_row = SpinRow::with_range(0.0, 5000.0, 100.0);
+        hold_to_reset_row.set_title("Hold to Reset (ms)");
+        hold_to_reset_row.set_subtitle(
+            "How long the reset keys have to be held while the window has focus, 0 resets instantly",
+        );
+        hold_to_reset_row.set_value(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .general
+                .hold_to_reset_ms
+                .unwrap_or(0) as f64,
+        );
+        hold_to_reset_row.connect_value_notify(|r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let ms = r.value().round() as u64;
+            let hotkeys = {
+                let Ok(mut cfg) = ctx.config_mut() else {
+                    return;
+                };
+                cfg.general.hold_to_reset_ms = (ms > 0).then_some(ms);
+                cfg.hotkeys
+            };
+            // Hand the reset keys to (or take them from) the global hotkeys
+            if let Err(err) = ctx.set_hotkeys(hotkeys) {
+                tracing::warn!("Could not apply hold to reset: {err}");
+            }
+        });
+        timing_group.add(&hold_to_reset_row);
         page.add(&timing_group);
 
         // Additional Info Visibility Group
@@ -80,6 +274,7 @@ impl TimerPreferencesDialog {
                             cfg.general.additional_info.$field = r.is_active();
                             drop(cfg);
                             crate::context::TuxSplitContext::get_instance().emit_run_changed();
+                            crate::context::TuxSplitContext::get_instance().schedule_config_save();
                         }
                     });
                     additional_info_group.add(&$row_var);
@@ -128,6 +323,70 @@ impl TimerPreferencesDialog {
                 "Toggle visibility of the probability of achieving a Personal Best",
                 show_pb_chance
             );
+            add_switch!(
+                sum_of_best_row,
+                "Show Sum of Best",
+                "Toggle visibility of the sum of best segments of the loaded run",
+                show_sum_of_best
+            );
+            add_switch!(
+                segment_progress_row,
+                "Show Split Progress",
+                "Toggle visibility of the completed / total splits counter",
+                show_segment_progress
+            );
+            add_switch!(
+                attempt_count_row,
+                "Show Attempt Count",
+                "Toggle visibility of the number of attempts of the loaded run",
+                show_attempt_count
+            );
+            add_switch!(
+                average_segment_row,
+                "Show Average Segment",
+                "Toggle visibility of the average time of the current segment across attempts",
+                show_average_segment
+            );
+            add_switch!(
+                goal_time_info_row,
+                "Show Goal",
+                "Toggle visibility of the projected finish relative to the goal time",
+                show_goal_time
+            );
+
+            let time_save_total_row = SwitchRow::builder()
+                .title("Total Possible Time Save")
+                .subtitle("Sum the possible time save over all remaining segments")
+                .active(cfg.general.possible_time_save_total)
+                .build();
+            time_save_total_row.connect_active_notify(|r| {
+                if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                    cfg.general.possible_time_save_total = r.is_active();
+                    crate::context::TuxSplitContext::get_instance().schedule_config_save();
+                }
+            });
+            additional_info_group.add(&time_save_total_row);
+
+            let freeze_best_possible_row = SwitchRow::builder()
+                .title("Freeze Best Possible Time")
+                .subtitle(
+                    "After a run ends, keep the last best possible time instead of the final time",
+                )
+                .active(cfg.general.best_possible_when_ended == BestPossibleWhenEnded::Frozen)
+                .build();
+            freeze_best_possible_row.connect_active_notify(|r| {
+                if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                    cfg.general.best_possible_when_ended = if r.is_active() {
+                        BestPossibleWhenEnded::Frozen
+                    } else {
+                        BestPossibleWhenEnded::FinalTime
+                    };
+                    crate::context::TuxSplitContext::get_instance().schedule_config_save();
+                }
+            });
+            additional_info_group.add(&freeze_best_possible_row);
+            additional_info_group.add(&Self::build_pace_target_row());
+            additional_info_group.add(&Self::build_goal_time_row());
         }
 
         page.add(&additional_info_group);
@@ -159,6 +418,7 @@ impl TimerPreferencesDialog {
             let value = r.value().round().clamp(0.0, 1000.0) as usize;
             if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
                 cfg.style.segments_scroll_follow_from = Some(value);
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
             }
         });
 
@@ -171,6 +431,7 @@ impl TimerPreferencesDialog {
             let value = r.value().round().clamp(1.0, 1000.0) as usize;
             if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
                 cfg.style.max_segments_displayed = Some(value);
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
             }
 
             // Adjust follow_from if necessary
@@ -197,18 +458,631 @@ impl TimerPreferencesDialog {
             if let Ok(mut cfg) = ctx.config_mut() {
                 cfg.style.show_icons = Some(active);
                 drop(cfg);
-                ctx.emit_by_name::<()>("run-changed", &[]);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Icon cache size
+        let initial_icon_cache_size = crate::context::TuxSplitContext::get_instance()
+            .config()
+            .style
+            .icon_cache_size
+            .unwrap_or(DEFAULT_ICON_CACHE_SIZE);
+        let icon_cache_row = SpinRow::with_range(1.0, 1000.0, 1.0);
+        icon_cache_row.set_title("Icon Cache Size");
+        icon_cache_row.set_subtitle("Decoded segment icons kept in memory");
+        icon_cache_row.set_value(initial_icon_cache_size as f64);
+        icon_cache_row.connect_value_notify(|r| {
+            let value = r.value().round().clamp(1.0, 1000.0) as usize;
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                // The cache picks up the new limit the next time rows are built
+                cfg.style.icon_cache_size = Some(value);
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Icon size
+        let initial_icon_size = icon_size(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .icon_size,
+        );
+        let icon_size_row = SpinRow::with_range(MIN_ICON_SIZE as f64, MAX_ICON_SIZE as f64, 1.0);
+        icon_size_row.set_title("Icon Size");
+        icon_size_row.set_subtitle("Pixel size of the icons next to segment names");
+        icon_size_row.set_value(initial_icon_size as f64);
+        icon_size_row.connect_value_notify(move |r| {
+            let value = (r.value().round() as i32).clamp(MIN_ICON_SIZE, MAX_ICON_SIZE);
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.icon_size = Some(value);
+                drop(cfg);
+                // Rebuild the rows with the new icon size and height
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Scale delta colors
+        let scale_deltas_row = SwitchRow::builder()
+            .title("Scale Delta Colors")
+            .subtitle("Deepen the green/red of deltas the more time was gained or lost")
+            .build();
+        let initial_scale_deltas = {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let c = ctx.config();
+            c.style.scale_delta_colors.unwrap_or(false)
+        };
+        scale_deltas_row.set_active(initial_scale_deltas);
+        scale_deltas_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.scale_delta_colors = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Live delta
+        let live_delta_row = SwitchRow::builder()
+            .title("Always Show Live Delta")
+            .subtitle("Show the current segment's delta even while ahead")
+            .build();
+        live_delta_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .always_show_live_delta
+                .unwrap_or(false),
+        );
+        live_delta_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.always_show_live_delta = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Compact layout
+        let compact_row = SwitchRow::builder()
+            .title("Compact Layout")
+            .subtitle("Only show the splits and the running timer")
+            .build();
+        compact_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .compact
+                .unwrap_or(false),
+        );
+        compact_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.compact = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Compact rows
+        let compact_rows_row = SwitchRow::builder()
+            .title("Compact Rows")
+            .subtitle("Less padding around segments, so more of them fit")
+            .build();
+        compact_rows_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .compact_rows
+                .unwrap_or(false),
+        );
+        compact_rows_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.compact_rows = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Subsplit groups
+        let subsplits_row = SwitchRow::builder()
+            .title("Subsplits")
+            .subtitle("Group segments named \"-Part\" up to \"{Group}Last\" under collapsible rows")
+            .build();
+        subsplits_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .subsplits
+                .unwrap_or(false),
+        );
+        subsplits_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.subsplits = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        let animations_row = SwitchRow::builder()
+            .title("Gold Split Flash")
+            .subtitle("Briefly highlight a segment that just became a new gold")
+            .build();
+        animations_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .animations
+                .unwrap_or(false),
+        );
+        animations_row.connect_active_notify(move |r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let active = r.is_active();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.animations = Some(active);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+
+        // Run progress bar
+        let progress_bar_row = SwitchRow::builder()
+            .title("Show Progress Bar")
+            .subtitle("A bar under the splits filling up as the run progresses")
+            .build();
+        progress_bar_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .show_progress_bar
+                .unwrap_or(false),
+        );
+        progress_bar_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.style.show_progress_bar = Some(r.is_active());
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+
+        // Secondary timer
+        let secondary_timer_row = SwitchRow::builder()
+            .title("Show Secondary Timer")
+            .subtitle("Also show the timer of the other timing method")
+            .build();
+        secondary_timer_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .show_secondary_timer
+                .unwrap_or(false),
+        );
+        secondary_timer_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.style.show_secondary_timer = Some(r.is_active());
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+
+        let color_timer_row = SwitchRow::builder()
+            .title("Color Running Timer")
+            .subtitle("Green or red with the live delta of the current segment")
+            .build();
+        color_timer_row.set_active(
+            crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .color_main_timer
+                .unwrap_or(false),
+        );
+        color_timer_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.style.color_main_timer = Some(r.is_active());
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+
+        // Timer scale
+        let initial_timer_scale = crate::context::TuxSplitContext::get_instance()
+            .config()
+            .style
+            .timer_scale
+            .unwrap_or(1.0);
+        let timer_scale_row = SpinRow::with_range(MIN_TIMER_SCALE, MAX_TIMER_SCALE, 0.1);
+        timer_scale_row.set_title("Timer Scale");
+        timer_scale_row.set_subtitle("Size multiplier of the main timer font");
+        timer_scale_row.set_digits(1);
+        timer_scale_row.set_value(initial_timer_scale);
+        timer_scale_row.connect_value_notify(move |r| {
+            let value = r.value().clamp(MIN_TIMER_SCALE, MAX_TIMER_SCALE);
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.timer_scale = Some(value);
+                apply_text_scales(value, cfg.style.font_scale.unwrap_or(1.0));
+                ctx.schedule_config_save();
+            }
+        });
+
+        // App-wide font scale
+        let initial_font_scale = crate::context::TuxSplitContext::get_instance()
+            .config()
+            .style
+            .font_scale
+            .unwrap_or(1.0);
+        let font_scale_row = SpinRow::with_range(MIN_FONT_SCALE, MAX_FONT_SCALE, 0.05);
+        font_scale_row.set_title("Text Scale");
+        font_scale_row.set_subtitle("Size multiplier of all text, the main timer included");
+        font_scale_row.set_digits(2);
+        font_scale_row.set_value(initial_font_scale);
+        font_scale_row.connect_value_notify(move |r| {
+            let value = r.value().clamp(MIN_FONT_SCALE, MAX_FONT_SCALE);
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.font_scale = Some(value);
+                apply_text_scales(cfg.style.timer_scale.unwrap_or(1.0), value);
+                drop(cfg);
+                // Segment rows got taller or shorter, rebuild to measure them again
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
             }
         });
 
         segments_group.add(&max_segments_row);
         segments_group.add(&follow_from_row);
         segments_group.add(&show_icons_row);
+        segments_group.add(&icon_size_row);
+        segments_group.add(&icon_cache_row);
+        segments_group.add(&scale_deltas_row);
+        segments_group.add(&live_delta_row);
+        segments_group.add(&Self::build_split_format_row());
+        segments_group.add(&Self::build_idle_display_row());
+        segments_group.add(&Self::build_info_position_row());
+        segments_group.add(&Self::build_orientation_row());
+        segments_group.add(&compact_row);
+        segments_group.add(&compact_rows_row);
+        segments_group.add(&subsplits_row);
+        segments_group.add(&animations_row);
+        segments_group.add(&secondary_timer_row);
+        segments_group.add(&color_timer_row);
+        segments_group.add(&progress_bar_row);
+        segments_group.add(&timer_scale_row);
+        segments_group.add(&font_scale_row);
 
         page.add(&segments_group);
+        page.add(&Self::build_window_group());
+        page.add(&Self::build_delta_colors_group());
         page
     }
 
+    fn build_delta_colors_group() -> PreferencesGroup {
+        let group = PreferencesGroup::builder()
+            .title("Delta Colors")
+            .description("Overridden colors aren't deepened by Scale Delta Colors")
+            .build();
+
+        for (class, default_color) in DELTA_COLOR_CLASSES {
+            let title = match class {
+                "greensplit" => "Ahead, Gaining Time",
+                "lostgreensplit" => "Ahead, Losing Time",
+                "redsplit" => "Behind, Losing Time",
+                "gainedredsplit" => "Behind, Gaining Time",
+                _ => "Best Segment",
+            };
+            let color = crate::context::TuxSplitContext::get_instance()
+                .config()
+                .style
+                .colors
+                .get(class)
+                .cloned()
+                .unwrap_or_else(|| default_color.to_owned());
+
+            let button = gtk::ColorDialogButton::new(Some(
+                gtk::ColorDialog::builder().with_alpha(false).build(),
+            ));
+            button.set_valign(gtk::Align::Center);
+            if let Ok(rgba) = gtk::gdk::RGBA::parse(color.as_str()) {
+                button.set_rgba(&rgba);
+            }
+            button.connect_rgba_notify(move |b| {
+                let ctx = crate::context::TuxSplitContext::get_instance();
+                if let Ok(mut cfg) = ctx.config_mut() {
+                    cfg.style
+                        .colors
+                        .insert(class.to_owned(), rgba_to_hex(&b.rgba()));
+                    apply_delta_colors(&cfg.style.colors);
+                    ctx.schedule_config_save();
+                }
+            });
+
+            let reset = gtk::Button::builder()
+                .icon_name("edit-undo-symbolic")
+                .tooltip_text("Use the default color")
+                .valign(gtk::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            let button_binding = button.downgrade();
+            reset.connect_clicked(move |_| {
+                if let Some(button) = button_binding.upgrade()
+                    && let Ok(rgba) = gtk::gdk::RGBA::parse(default_color)
+                {
+                    button.set_rgba(&rgba);
+                }
+                let ctx = crate::context::TuxSplitContext::get_instance();
+                if let Ok(mut cfg) = ctx.config_mut() {
+                    cfg.style.colors.remove(class);
+                    apply_delta_colors(&cfg.style.colors);
+                    ctx.schedule_config_save();
+                }
+            });
+
+            let row = ActionRow::builder().title(title).subtitle(class).build();
+            row.add_suffix(&button);
+            row.add_suffix(&reset);
+            group.add(&row);
+        }
+        group
+    }
+
+    fn build_window_group() -> PreferencesGroup {
+        let window_group = PreferencesGroup::builder().title("Window").build();
+        let (initial_opacity, initial_transparent) = {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            let c = ctx.config();
+            (
+                window_opacity(c.window.opacity),
+                c.window.transparent_background,
+            )
+        };
+
+        let opacity_row = SpinRow::with_range(MIN_WINDOW_OPACITY, 1.0, 0.05);
+        opacity_row.set_title("Window Opacity");
+        opacity_row.set_subtitle("Opacity of the whole timer window");
+        opacity_row.set_digits(2);
+        opacity_row.set_value(initial_opacity);
+        opacity_row.connect_value_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.window.opacity = Some(window_opacity(Some(r.value())));
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+            Self::apply_window_appearance();
+        });
+
+        let transparent_row = SwitchRow::builder()
+            .title("Transparent Background")
+            .subtitle("Don't paint a window background, for overlays")
+            .build();
+        transparent_row.set_active(initial_transparent);
+        transparent_row.connect_active_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.window.transparent_background = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+            Self::apply_window_appearance();
+        });
+
+        window_group.add(&opacity_row);
+        window_group.add(&transparent_row);
+        window_group
+    }
+
+    /// Re-applies the window options to the main timer window.
+    fn apply_window_appearance() {
+        let ctx = crate::context::TuxSplitContext::get_instance();
+        let cfg = ctx.config();
+        for window in gtk::Window::list_toplevels() {
+            if window.is::<adw::ApplicationWindow>() {
+                apply_window_appearance(
+                    &window,
+                    cfg.window.opacity,
+                    cfg.window.transparent_background,
+                );
+            }
+        }
+    }
+
+    fn build_idle_display_row() -> ComboRow {
+        let options = StringList::new(&["First segment", "Whole run"]);
+        let initial = match crate::context::TuxSplitContext::get_instance()
+            .config()
+            .general
+            .idle_display
+        {
+            IdleDisplay::FirstSegment => 0,
+            IdleDisplay::WholeRun => 1,
+        };
+        let row = ComboRow::builder()
+            .title("Footer When Idle")
+            .subtitle("Values shown before a run starts, when no segment is selected")
+            .model(&options)
+            .selected(initial)
+            .build();
+        row.connect_selected_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.idle_display = if r.selected() == 1 {
+                    IdleDisplay::WholeRun
+                } else {
+                    IdleDisplay::FirstSegment
+                };
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        row
+    }
+
+    fn build_split_format_row() -> ComboRow {
+        let options = StringList::new(&["Delta", "Split Time", "Split Time and Delta"]);
+        let initial = match crate::context::TuxSplitContext::get_instance()
+            .config()
+            .general
+            .split_format
+        {
+            SplitDisplay::Delta => 0,
+            SplitDisplay::AbsoluteTime => 1,
+            SplitDisplay::Both => 2,
+        };
+        let row = ComboRow::builder()
+            .title("Passed Splits")
+            .subtitle("What the rows of finished segments show")
+            .model(&options)
+            .selected(initial)
+            .build();
+        row.connect_selected_notify(|r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.general.split_format = match r.selected() {
+                    0 => SplitDisplay::Delta,
+                    1 => SplitDisplay::AbsoluteTime,
+                    _ => SplitDisplay::Both,
+                };
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+        row
+    }
+
+    fn build_pace_target_row() -> ComboRow {
+        let options = StringList::new(&["Personal Best", "Goal Time", "World Record"]);
+        let initial = match crate::context::TuxSplitContext::get_instance()
+            .config()
+            .general
+            .pace_target
+        {
+            PaceTarget::PersonalBest => 0,
+            PaceTarget::GoalTime => 1,
+            PaceTarget::WorldRecord => 2,
+        };
+        let row = ComboRow::builder()
+            .title("Current Pace Color")
+            .subtitle("Green when the projected final time beats this target")
+            .model(&options)
+            .selected(initial)
+            .build();
+        row.connect_selected_notify(|r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.pace_target = match r.selected() {
+                    1 => PaceTarget::GoalTime,
+                    2 => PaceTarget::WorldRecord,
+                    _ => PaceTarget::PersonalBest,
+                };
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        row
+    }
+
+    fn build_goal_time_row() -> EntryRow {
+        let goal = crate::context::TuxSplitContext::get_instance()
+            .config()
+            .general
+            .goal_time;
+        let row = EntryRow::builder()
+            .title("Goal Time")
+            .text(goal.map(goal_time_text).unwrap_or_default())
+            .build();
+        row.connect_text_notify(|entry| {
+            let text = entry.text();
+            let goal = if text.trim().is_empty() {
+                None
+            } else if let Ok(goal) = parse_hms(text.trim()) {
+                Some(TimeSpan::from_seconds(goal.as_seconds_f64()))
+            } else {
+                entry.set_title("Goal Time (e.g. 1:30:00.000 or 1h30m)");
+                entry.add_css_class("error");
+                return;
+            };
+            entry.set_title("Goal Time");
+            entry.remove_css_class("error");
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                cfg.general.goal_time = goal;
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+        row
+    }
+
+    fn build_info_position_row() -> ComboRow {
+        let options = StringList::new(&["Above splits", "Above footer", "Below footer"]);
+        let initial = match crate::context::TuxSplitContext::get_instance()
+            .config()
+            .style
+            .info_position
+            .unwrap_or_default()
+        {
+            InfoPosition::AboveSplits => 0,
+            InfoPosition::AboveFooter => 1,
+            InfoPosition::BelowFooter => 2,
+        };
+        let row = ComboRow::builder()
+            .title("Additional Info Position")
+            .model(&options)
+            .selected(initial)
+            .build();
+        row.connect_selected_notify(|r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.info_position = Some(match r.selected() {
+                    0 => InfoPosition::AboveSplits,
+                    2 => InfoPosition::BelowFooter,
+                    _ => InfoPosition::AboveFooter,
+                });
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+        row
+    }
+
+    fn build_orientation_row() -> ComboRow {
+        let options = StringList::new(&["Vertical", "Horizontal"]);
+        let initial = match crate::context::TuxSplitContext::get_instance()
+            .config()
+            .style
+            .orientation
+            .unwrap_or_default()
+        {
+            TimerOrientation::Vertical => 0,
+            TimerOrientation::Horizontal => 1,
+        };
+        let row = ComboRow::builder()
+            .title("Layout Orientation")
+            .subtitle("Horizontal places the sections side by side")
+            .model(&options)
+            .selected(initial)
+            .build();
+        row.connect_selected_notify(|r| {
+            let ctx = crate::context::TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                cfg.style.orientation = Some(match r.selected() {
+                    1 => TimerOrientation::Horizontal,
+                    _ => TimerOrientation::Vertical,
+                });
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+        row
+    }
+
     fn build_format_page(&self) -> PreferencesPage {
         let page = PreferencesPage::builder()
             .title("Format")
@@ -279,6 +1153,7 @@ impl TimerPreferencesDialog {
 
             if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
                 cfg.general.timing_method = Some(method);
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
             }
 
             if let Ok(mut t) = crate::context::TuxSplitContext::get_instance()
@@ -298,7 +1173,7 @@ impl TimerPreferencesDialog {
         subtitle: &str,
         target: FormatTarget,
     ) -> ExpanderRow {
-        let (initial_mode_index, initial_decimals) = {
+        let (initial_mode_index, initial_decimals, initial_separator, initial_signed) = {
             let ctx = crate::context::TuxSplitContext::get_instance();
             let cfg = ctx.config();
             let tf = match target {
@@ -307,12 +1182,21 @@ impl TimerPreferencesDialog {
                 FormatTarget::Segment => &cfg.format.segment,
                 FormatTarget::Comparison => &cfg.format.comparison,
             };
-            let mode = if tf.show_decimals {
-                u32::from(tf.dynamic)
+            let preset = tf.preset().unwrap_or(if tf.show_decimals {
+                if tf.dynamic {
+                    TimeFormatPreset::SmartDecimals
+                } else {
+                    TimeFormatPreset::ShowDecimals
+                }
             } else {
-                2
-            };
-            (mode, tf.decimal_places)
+                TimeFormatPreset::NoDecimals
+            });
+            let mode = TimeFormatPreset::ALL
+                .iter()
+                .position(|&p| p == preset)
+                .unwrap_or(0) as u32;
+            let separator = u32::from(tf.decimal_separator() == ',');
+            (mode, tf.decimal_places(), separator, tf.always_signed)
         };
 
         let expander = ExpanderRow::builder()
@@ -320,10 +1204,11 @@ impl TimerPreferencesDialog {
             .subtitle(subtitle)
             .build();
 
-        let mode_model = StringList::new(&["Show decimals", "Smart decimals", "No decimals"]);
+        let mode_labels: Vec<&str> = TimeFormatPreset::ALL.iter().map(|p| p.label()).collect();
+        let mode_model = StringList::new(&mode_labels);
         let mode_row = ComboRow::builder()
             .title("Mode")
-            .subtitle("Select decimal visibility strategy")
+            .subtitle("Which time units and decimals are shown")
             .build();
         mode_row.set_model(Some(&mode_model));
         mode_row.set_selected(initial_mode_index);
@@ -332,35 +1217,42 @@ impl TimerPreferencesDialog {
         decimals_row.set_title("Decimal places");
         decimals_row.set_value(f64::from(initial_decimals));
 
+        let separator_model = StringList::new(&["Period (1:23.45)", "Comma (1:23,45)"]);
+        let separator_row = ComboRow::builder()
+            .title("Decimal separator")
+            .subtitle("Character placed before the fractional digits")
+            .build();
+        separator_row.set_model(Some(&separator_model));
+        separator_row.set_selected(initial_separator);
+
+        let signed_row = SwitchRow::builder()
+            .title("Always show sign")
+            .subtitle("Prefix positive times with \"+\"")
+            .active(initial_signed)
+            .build();
+
+        let decimals_binding = decimals_row.clone();
         mode_row.connect_selected_notify(move |r| {
-            let idx = r.selected();
-            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+            let Some(&preset) = TimeFormatPreset::ALL.get(r.selected() as usize) else {
+                return;
+            };
+            let decimal_places = {
+                let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut()
+                else {
+                    return;
+                };
                 let tf = match target {
                     FormatTarget::Timer => &mut cfg.format.timer,
                     FormatTarget::Split => &mut cfg.format.split,
                     FormatTarget::Segment => &mut cfg.format.segment,
                     FormatTarget::Comparison => &mut cfg.format.comparison,
                 };
-                match idx {
-                    0 => {
-                        // Show decimals
-                        tf.dynamic = false;
-                        tf.show_decimals = true;
-                    }
-                    1 => {
-                        // Smart decimals
-                        tf.dynamic = true;
-                        tf.show_decimals = true;
-                    }
-                    2 => {
-                        // No decimals
-                        tf.dynamic = false;
-                        tf.show_decimals = false;
-                    }
-                    _ => {}
-                }
-                tf.set_decimal_places(tf.decimal_places);
-            }
+                tf.apply_preset(preset);
+                tf.decimal_places()
+            };
+            crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            // Reflect the preset's decimal count once the config is released
+            decimals_binding.set_value(f64::from(decimal_places));
         });
 
         decimals_row.connect_value_notify(move |row| {
@@ -373,12 +1265,53 @@ impl TimerPreferencesDialog {
                     FormatTarget::Comparison => &mut cfg.format.comparison,
                 };
                 tf.set_decimal_places(val);
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+
+        separator_row.connect_selected_notify(move |r| {
+            let separator = if r.selected() == 1 { ',' } else { '.' };
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                let tf = match target {
+                    FormatTarget::Timer => &mut cfg.format.timer,
+                    FormatTarget::Split => &mut cfg.format.split,
+                    FormatTarget::Segment => &mut cfg.format.segment,
+                    FormatTarget::Comparison => &mut cfg.format.comparison,
+                };
+                tf.set_decimal_separator(separator);
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
+            }
+        });
+
+        signed_row.connect_active_notify(move |r| {
+            if let Ok(mut cfg) = crate::context::TuxSplitContext::get_instance().config_mut() {
+                let tf = match target {
+                    FormatTarget::Timer => &mut cfg.format.timer,
+                    FormatTarget::Split => &mut cfg.format.split,
+                    FormatTarget::Segment => &mut cfg.format.segment,
+                    FormatTarget::Comparison => &mut cfg.format.comparison,
+                };
+                tf.always_signed = r.is_active();
+                crate::context::TuxSplitContext::get_instance().schedule_config_save();
             }
         });
 
         expander.add_row(&mode_row);
         expander.add_row(&decimals_row);
+        expander.add_row(&separator_row);
+        expander.add_row(&signed_row);
 
         expander
     }
 }
+
+/// `#rrggbb` for a color picked in a `ColorDialogButton`.
+fn rgba_to_hex(rgba: &gtk::gdk::RGBA) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(rgba.red()),
+        channel(rgba.green()),
+        channel(rgba.blue())
+    )
+}
@@ -1,7 +1,10 @@
 pub mod editor;
 pub mod header;
+pub mod hotkeys;
 pub mod info;
 pub mod menu;
+pub mod sounds;
+pub mod styles;
 pub mod timer;
 
 pub use header::TuxSplitHeader;
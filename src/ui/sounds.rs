@@ -0,0 +1,193 @@
+//! Short sound effects on splits, gold segments, new Personal Bests and resets,
+//! configured in `style.sounds`.
+
+use std::cell::RefCell;
+use std::path::Path;
+
+use gtk4::MediaFile;
+use gtk4::prelude::*;
+use livesplit_core::{Timer, TimerPhase};
+use tracing::warn;
+
+use crate::config::SoundConfig;
+use crate::utils::comparisons::{
+    is_new_personal_best, previous_split_combined_gold_and_prev_comparison, segment_split_time,
+};
+
+thread_local! {
+    /// Streams still playing, dropping one would cut it off.
+    static PLAYING: RefCell<Vec<MediaFile>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    Split,
+    Gold,
+    PersonalBest,
+    Reset,
+}
+
+/// The sound for what happened since the refresh loop last saw the timer in
+/// `previous_phase` at `previous_index`, if anything worth a sound happened.
+pub fn sound_event(
+    previous_phase: TimerPhase,
+    previous_index: Option<usize>,
+    timer: &Timer,
+) -> Option<SoundEvent> {
+    let index = timer.current_split_index();
+    match (previous_phase, timer.current_phase()) {
+        // Resetting a finished run is routine, not a reset
+        (TimerPhase::Running | TimerPhase::Paused, TimerPhase::NotRunning) => {
+            Some(SoundEvent::Reset)
+        }
+        (TimerPhase::Running, TimerPhase::Ended) if is_new_personal_best(timer) => {
+            Some(SoundEvent::PersonalBest)
+        }
+        (TimerPhase::Running, TimerPhase::Ended) => {
+            split_sound(timer, timer.run().len().saturating_sub(1))
+        }
+        (TimerPhase::Running | TimerPhase::Paused, TimerPhase::Running | TimerPhase::Paused)
+            if index > previous_index =>
+        {
+            split_sound(timer, index?.checked_sub(1)?)
+        }
+        _ => None,
+    }
+}
+
+/// Gold when the segment beat its best segment time, nothing for a skipped segment.
+fn split_sound(timer: &Timer, index: usize) -> Option<SoundEvent> {
+    let split = segment_split_time(timer.run().segments().get(index)?, timer);
+    if split == time::Duration::ZERO {
+        return None;
+    }
+    let (previous_split, combined_gold, _) =
+        previous_split_combined_gold_and_prev_comparison(timer, index);
+    if combined_gold == time::Duration::ZERO || split - previous_split < combined_gold {
+        Some(SoundEvent::Gold)
+    } else {
+        Some(SoundEvent::Split)
+    }
+}
+
+/// Starts playing the sound of `event`. GTK decodes and plays it on its own threads.
+pub fn play(sounds: &SoundConfig, event: SoundEvent) {
+    let Some(path) = sound_file(sounds, event) else {
+        return;
+    };
+    if !path.is_file() {
+        warn!("Sound file {} not found, skipping it", path.display());
+        return;
+    }
+    let media = MediaFile::for_filename(path);
+    media.connect_error_notify(|media| {
+        if let Some(err) = media.error() {
+            warn!("Could not play a sound: {err}");
+        }
+    });
+    media.play();
+    PLAYING.with(|playing| {
+        let mut playing = playing.borrow_mut();
+        playing.retain(|media| !media.is_ended() && media.error().is_none());
+        playing.push(media);
+    });
+}
+
+/// The file played for `event`, `None` while sounds are off.
+pub fn sound_file(sounds: &SoundConfig, event: SoundEvent) -> Option<&Path> {
+    if !sounds.enabled {
+        return None;
+    }
+    match event {
+        SoundEvent::Split => sounds.split.as_deref(),
+        SoundEvent::Gold => sounds.gold.as_deref(),
+        SoundEvent::PersonalBest => sounds.personal_best.as_deref(),
+        SoundEvent::Reset => sounds.reset.as_deref(),
+    }
+}
+
+#[cfg(test)]
+mod sound_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment, Time, TimeSpan};
+    use std::path::PathBuf;
+
+    fn seconds(s: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(s)))
+    }
+
+    /// Two segments, optionally with best segment times. The offset makes splitting right
+    /// away take about 10 seconds.
+    fn timer(best_segment: Option<f64>) -> Timer {
+        let mut run = Run::new();
+        for name in ["First", "Second"] {
+            let mut segment = Segment::new(name);
+            if let Some(best) = best_segment {
+                segment.set_best_segment_time(seconds(best));
+                segment.set_personal_best_split_time(seconds(1.0));
+            }
+            run.push_segment(segment);
+        }
+        run.set_offset(TimeSpan::from_seconds(10.0));
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn splits_are_gold_only_when_beating_the_best_segment() {
+        let mut slow = timer(Some(5.0));
+        slow.start();
+        slow.split();
+        assert_eq!(
+            sound_event(TimerPhase::Running, Some(0), &slow),
+            Some(SoundEvent::Split)
+        );
+
+        let mut first = timer(None);
+        first.start();
+        first.split();
+        assert_eq!(
+            sound_event(TimerPhase::Running, Some(0), &first),
+            Some(SoundEvent::Gold)
+        );
+
+        // Skipped segments have no time to play anything for
+        let mut skipped = timer(None);
+        skipped.start();
+        skipped.skip_split();
+        assert_eq!(sound_event(TimerPhase::Running, Some(0), &skipped), None);
+    }
+
+    #[test]
+    fn finishing_and_resetting() {
+        let mut timer = timer(None);
+        timer.start();
+        timer.split();
+        timer.split();
+        assert_eq!(
+            sound_event(TimerPhase::Running, Some(1), &timer),
+            Some(SoundEvent::PersonalBest)
+        );
+
+        timer.reset(false);
+        assert_eq!(
+            sound_event(TimerPhase::Running, Some(1), &timer),
+            Some(SoundEvent::Reset)
+        );
+        assert_eq!(sound_event(TimerPhase::Ended, Some(2), &timer), None);
+    }
+
+    #[test]
+    fn nothing_plays_while_disabled() {
+        let mut sounds = SoundConfig {
+            split: Some(PathBuf::from("split.ogg")),
+            ..SoundConfig::default()
+        };
+        assert_eq!(sound_file(&sounds, SoundEvent::Split), None);
+        sounds.enabled = true;
+        assert_eq!(
+            sound_file(&sounds, SoundEvent::Split),
+            Some(Path::new("split.ogg"))
+        );
+        assert_eq!(sound_file(&sounds, SoundEvent::Reset), None);
+    }
+}
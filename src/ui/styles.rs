@@ -0,0 +1,311 @@
+//! Stylesheets generated at runtime from the config, layered on top of the
+//! bundled `tuxsplit.css`.
+
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+
+use gtk4::prelude::*;
+use gtk4::{CssProvider, gdk::Display};
+use tracing::warn;
+
+/// Font sizes of `.bigtimer` / `.smalltimer` in `tuxsplit.css`.
+pub const BIG_TIMER_FONT_SIZE: f64 = 36.0;
+pub const SMALL_TIMER_FONT_SIZE: f64 = 18.0;
+
+pub const MIN_TIMER_SCALE: f64 = 0.5;
+pub const MAX_TIMER_SCALE: f64 = 4.0;
+
+pub const MIN_FONT_SCALE: f64 = 0.75;
+pub const MAX_FONT_SCALE: f64 = 3.0;
+
+/// Lowest window opacity, so the window can't vanish completely.
+pub const MIN_WINDOW_OPACITY: f64 = 0.2;
+
+const TRANSPARENT_BACKGROUND_CLASS: &str = "transparent-background";
+
+/// Delta classes whose color can be overridden, with their color in `tuxsplit.css`.
+pub const DELTA_COLOR_CLASSES: [(&str, &str); 5] = [
+    ("greensplit", "#33d17a"),
+    ("lostgreensplit", "#26a269"),
+    ("redsplit", "#ed333b"),
+    ("gainedredsplit", "#f66151"),
+    ("goldsplit", "#e5a50a"),
+];
+
+const DELTA_INTENSITIES: [&str; 3] = [
+    "delta-intensity-low",
+    "delta-intensity-medium",
+    "delta-intensity-high",
+];
+
+thread_local! {
+    static TIMER_SCALE_PROVIDER: OnceCell<CssProvider> = const { OnceCell::new() };
+    static FONT_SCALE_PROVIDER: OnceCell<CssProvider> = const { OnceCell::new() };
+    static DELTA_COLORS_PROVIDER: OnceCell<CssProvider> = const { OnceCell::new() };
+}
+
+/// Out of range (or non-finite) scales are clamped to something readable.
+fn clamp_scale(scale: f64, min: f64, max: f64) -> f64 {
+    if scale.is_finite() {
+        scale.clamp(min, max)
+    } else {
+        1.0
+    }
+}
+
+/// CSS for the main timer font sizes, scaled by both the timer scale and the font
+/// scale of the whole app (the timer sizes are in px, so the latter doesn't reach them).
+pub fn timer_font_css(timer_scale: f64, font_scale: f64) -> String {
+    let scale = clamp_scale(timer_scale, MIN_TIMER_SCALE, MAX_TIMER_SCALE)
+        * clamp_scale(font_scale, MIN_FONT_SCALE, MAX_FONT_SCALE);
+    format!(
+        ".bigtimer {{ font-size: {:.0}px; }}\n.smalltimer {{ font-size: {:.0}px; }}\n",
+        BIG_TIMER_FONT_SIZE * scale,
+        SMALL_TIMER_FONT_SIZE * scale,
+    )
+}
+
+/// CSS scaling the text of every window relative to the system font. Popovers inherit
+/// the size of their window, so selecting them too would scale their text twice.
+/// Sizes given in px, like the main timer, are scaled by `timer_font_css` instead.
+pub fn font_scale_css(scale: f64) -> String {
+    let scale = clamp_scale(scale, MIN_FONT_SCALE, MAX_FONT_SCALE);
+    format!("window {{ font-size: {:.0}%; }}\n", scale * 100.0)
+}
+
+/// Whether `color` is a `#rgb`, `#rrggbb` or `#rrggbbaa` hex color.
+pub fn is_hex_color(color: &str) -> bool {
+    color.strip_prefix('#').is_some_and(|digits| {
+        matches!(digits.len(), 3 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// CSS overriding the colors of the delta classes. Unknown classes and invalid colors
+/// are skipped with a warning. An overridden class also replaces its intensity shades.
+pub fn delta_colors_css(colors: &BTreeMap<String, String>) -> String {
+    let mut css = String::new();
+    for (class, color) in colors {
+        if !DELTA_COLOR_CLASSES.iter().any(|(known, _)| known == class) {
+            warn!("Ignoring color for unknown class {class}");
+            continue;
+        }
+        if !is_hex_color(color) {
+            warn!("Ignoring invalid color {color} for {class}");
+            continue;
+        }
+        let selectors: Vec<String> = std::iter::once(format!(".{class}"))
+            .chain(
+                DELTA_INTENSITIES
+                    .iter()
+                    .map(|intensity| format!(".{class}.{intensity}")),
+            )
+            .collect();
+        css.push_str(&format!("{} {{ color: {color}; }}\n", selectors.join(", ")));
+    }
+    css
+}
+
+/// Installs (once) and reloads the timer and app-wide font scale stylesheets for the
+/// default display.
+pub fn apply_text_scales(timer_scale: f64, font_scale: f64) {
+    install(
+        &TIMER_SCALE_PROVIDER,
+        &timer_font_css(timer_scale, font_scale),
+    );
+    install(&FONT_SCALE_PROVIDER, &font_scale_css(font_scale));
+}
+
+/// The configured window opacity clamped to a visible range, opaque when unset.
+pub fn window_opacity(opacity: Option<f64>) -> f64 {
+    match opacity {
+        Some(opacity) if opacity.is_finite() => opacity.clamp(MIN_WINDOW_OPACITY, 1.0),
+        _ => 1.0,
+    }
+}
+
+/// Applies the window opacity and toggles the transparent background class.
+pub fn apply_window_appearance(
+    window: &impl IsA<gtk4::Widget>,
+    opacity: Option<f64>,
+    transparent_background: bool,
+) {
+    window.set_opacity(window_opacity(opacity));
+    if transparent_background {
+        window.add_css_class(TRANSPARENT_BACKGROUND_CLASS);
+    } else {
+        window.remove_css_class(TRANSPARENT_BACKGROUND_CLASS);
+    }
+}
+
+/// Installs (once) and reloads the delta color overrides for the default display.
+pub fn apply_delta_colors(colors: &BTreeMap<String, String>) {
+    install(&DELTA_COLORS_PROVIDER, &delta_colors_css(colors));
+}
+
+fn install(provider: &'static std::thread::LocalKey<OnceCell<CssProvider>>, css: &str) {
+    let Some(display) = Display::default() else {
+        return;
+    };
+    provider.with(|cell| {
+        let provider = cell.get_or_init(|| {
+            let provider = CssProvider::new();
+            // Above the bundled stylesheet so the generated sizes win
+            gtk4::style_context_add_provider_for_display(
+                &display,
+                &provider,
+                gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+            );
+            provider
+        });
+        provider.load_from_string(css);
+    });
+}
+
+#[cfg(test)]
+mod timer_font_css_tests {
+    use super::*;
+
+    #[test]
+    fn default_scale_matches_bundled_sizes() {
+        assert_eq!(
+            timer_font_css(1.0, 1.0),
+            ".bigtimer { font-size: 36px; }\n.smalltimer { font-size: 18px; }\n"
+        );
+    }
+
+    #[test]
+    fn scale_multiplies_both_sizes() {
+        let css = timer_font_css(1.5, 1.0);
+        assert!(css.contains(".bigtimer { font-size: 54px; }"), "{css}");
+        assert!(css.contains(".smalltimer { font-size: 27px; }"), "{css}");
+    }
+
+    #[test]
+    fn font_scale_multiplies_timer_scale() {
+        assert_eq!(timer_font_css(1.0, 2.0), timer_font_css(2.0, 1.0));
+        let css = timer_font_css(1.5, 2.0);
+        assert!(css.contains(".bigtimer { font-size: 108px; }"), "{css}");
+        // Each factor is clamped on its own
+        assert_eq!(
+            timer_font_css(100.0, 100.0),
+            timer_font_css(MAX_TIMER_SCALE, MAX_FONT_SCALE)
+        );
+    }
+
+    #[test]
+    fn scale_is_clamped() {
+        assert_eq!(
+            timer_font_css(0.0, 1.0),
+            timer_font_css(MIN_TIMER_SCALE, 1.0)
+        );
+        assert_eq!(
+            timer_font_css(100.0, 1.0),
+            timer_font_css(MAX_TIMER_SCALE, 1.0)
+        );
+        assert_eq!(timer_font_css(f64::NAN, 1.0), timer_font_css(1.0, 1.0));
+    }
+}
+
+#[cfg(test)]
+mod font_scale_css_tests {
+    use super::*;
+
+    #[test]
+    fn default_scale_keeps_system_size() {
+        assert_eq!(font_scale_css(1.0), "window { font-size: 100%; }\n");
+    }
+
+    #[test]
+    fn scale_is_a_percentage() {
+        assert!(font_scale_css(1.25).contains("font-size: 125%;"));
+    }
+
+    #[test]
+    fn scale_is_clamped() {
+        assert_eq!(font_scale_css(0.1), font_scale_css(MIN_FONT_SCALE));
+        assert_eq!(font_scale_css(10.0), font_scale_css(MAX_FONT_SCALE));
+        assert_eq!(font_scale_css(f64::INFINITY), font_scale_css(1.0));
+    }
+}
+
+#[cfg(test)]
+mod window_appearance_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    #[test]
+    fn opacity_defaults_to_opaque_and_is_clamped() {
+        assert_eq!(window_opacity(None), 1.0);
+        assert_eq!(window_opacity(Some(0.5)), 0.5);
+        assert_eq!(window_opacity(Some(0.0)), MIN_WINDOW_OPACITY);
+        assert_eq!(window_opacity(Some(3.0)), 1.0);
+        assert_eq!(window_opacity(Some(f64::NAN)), 1.0);
+    }
+
+    #[gtk4::test]
+    fn transparent_background_toggles_class() {
+        gtk_test_init();
+
+        let window = gtk4::Window::new();
+        apply_window_appearance(&window, Some(0.6), true);
+        assert!(window.has_css_class(TRANSPARENT_BACKGROUND_CLASS));
+        assert!((window.opacity() - 0.6).abs() < 1e-9);
+
+        apply_window_appearance(&window, None, false);
+        assert!(!window.has_css_class(TRANSPARENT_BACKGROUND_CLASS));
+        assert_eq!(window.opacity(), 1.0);
+    }
+}
+
+#[cfg(test)]
+mod delta_colors_css_tests {
+    use super::*;
+
+    fn colors(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(class, color)| ((*class).to_owned(), (*color).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn hex_colors_are_validated() {
+        for valid in ["#fff", "#33d17a", "#33D17A80"] {
+            assert!(is_hex_color(valid), "{valid}");
+        }
+        for invalid in ["33d17a", "#33d17", "#gggggg", "red", ""] {
+            assert!(!is_hex_color(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn overrides_class_and_its_intensities() {
+        let css = delta_colors_css(&colors(&[("greensplit", "#00ff00")]));
+        assert_eq!(
+            css,
+            ".greensplit, .greensplit.delta-intensity-low, .greensplit.delta-intensity-medium, \
+             .greensplit.delta-intensity-high { color: #00ff00; }\n"
+        );
+    }
+
+    #[test]
+    fn skips_invalid_colors_and_unknown_classes() {
+        let css = delta_colors_css(&colors(&[
+            ("goldsplit", "#ffd700"),
+            ("redsplit", "not a color"),
+            ("bluesplit", "#0000ff"),
+        ]));
+        assert!(css.contains(".goldsplit"), "{css}");
+        assert!(!css.contains("redsplit"), "{css}");
+        assert!(!css.contains("bluesplit"), "{css}");
+        assert!(delta_colors_css(&BTreeMap::new()).is_empty());
+    }
+}
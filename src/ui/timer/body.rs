@@ -1,27 +1,57 @@
-use crate::config::Config;
+use crate::config::{
+    Config, DEFAULT_COMPARISON_MIN_WIDTH, DEFAULT_DELTA_MIN_WIDTH, DEFAULT_ICON_CACHE_SIZE,
+    SplitDisplay, TimerOrientation,
+};
+use crate::context::TuxSplitContext;
+use crate::ui::timer::icons::{icon_hash, icon_size, segment_icon_texture};
+use crate::ui::timer::notes::NoteDisplay;
+use crate::ui::timer::progress::RunProgress;
+use crate::ui::timer::subsplits::{
+    SegmentGroup, display_name, group_duration, hidden_segments, is_collapsed, timer_groups,
+};
 use crate::utils::comparisons::{
-    classify_split_label, current_attempt_running_duration, format_signed,
-    previous_split_combined_gold_and_prev_comparison, segment_comparison_time, segment_split_time,
+    classify_split_label, current_attempt_running_duration, delta_intensity_class,
+    display_duration, format_display_time, format_signed, is_gold_segment, mark_derived,
+    previous_split_combined_gold_and_prev_comparison,
 };
 
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
 use adw::ActionRow;
 use adw::prelude::ActionRowExt;
 use glib::Propagation;
 use gtk4::ffi::GTK_ICON_LOOKUP_FORCE_REGULAR;
 use gtk4::{
-    Align, Box as GtkBox, EventControllerKey, Label, ListBox, Orientation, ScrolledWindow,
-    SelectionMode, gdk,
+    Align, Box as GtkBox, Button, CallbackAction, EventControllerKey, Label, ListBox, Orientation,
+    PolicyType, PropagationPhase, ScrolledWindow, SearchBar, SearchEntry, SelectionMode, Shortcut,
+    ShortcutController, ShortcutScope, ShortcutTrigger, gdk,
 };
 use gtk4::{CenterBox, prelude::*};
 
 use livesplit_core::{Timer, TimerPhase};
 
+/// Horizontal and vertical scrollbar policies of the splits scroller. Side by side with
+/// the other sections the list can get narrow, so it may also scroll horizontally.
+fn scroller_policy(orientation: TimerOrientation) -> (PolicyType, PolicyType) {
+    match orientation {
+        TimerOrientation::Vertical => (PolicyType::Never, PolicyType::Automatic),
+        TimerOrientation::Horizontal => (PolicyType::Automatic, PolicyType::Automatic),
+    }
+}
+
+/// Segment rows with less padding, see `tuxsplit.css`.
+const COMPACT_ROW_CLASS: &str = "compact-row";
+
 /// The body of the Timer UI:
 ///
-/// It owns a vertical container and a `SegmentList` that renders the splits.
+/// It owns a vertical container and a `SegmentList` that renders the splits, followed
+/// by the optional run progress bar and the note of the running segment.
 pub struct TimerBody {
     container: GtkBox,
     segment_list: SegmentList,
+    progress: RunProgress,
+    notes: NoteDisplay,
 }
 
 impl TimerBody {
@@ -33,10 +63,16 @@ impl TimerBody {
 
         let segment_list = SegmentList::new(timer, config);
         container.append(segment_list.container());
+        let progress = RunProgress::new(timer, config);
+        container.append(progress.container());
+        let notes = NoteDisplay::new(timer);
+        container.append(notes.container());
 
         Self {
             container,
             segment_list,
+            progress,
+            notes,
         }
     }
 
@@ -52,8 +88,35 @@ impl TimerBody {
         self.segment_list.last_segment_list()
     }
 
+    pub fn set_orientation(&self, orientation: TimerOrientation) {
+        self.segment_list.set_orientation(orientation);
+    }
+
+    /// Filters the splits of `this` whenever the search text changes.
+    pub fn connect_search_changed(this: &Rc<RefCell<Self>>) {
+        let body_weak = Rc::downgrade(this);
+        let search_entry = this.borrow().segment_list.search_entry.clone();
+        search_entry.connect_search_changed(move |_| {
+            let body_weak = body_weak.clone();
+            // Clearing the search happens while the list updates, filter after that
+            glib::idle_add_local_once(move || {
+                let Some(body) = body_weak.upgrade() else {
+                    return;
+                };
+                let ctx = TuxSplitContext::get_instance();
+                let shared = ctx.timer();
+                let timer = shared.read().unwrap();
+                body.borrow_mut()
+                    .segment_list
+                    .apply_search(&timer, &ctx.config());
+            });
+        });
+    }
+
     pub fn refresh(&mut self, timer: &Timer, config: &Config, force_rebuild: bool) {
         self.segment_list.update(timer, config, force_rebuild);
+        self.progress.refresh(timer, config);
+        self.notes.refresh(timer);
     }
 }
 
@@ -64,10 +127,146 @@ pub struct SegmentList {
     list: ListBox,
     last_segment_list: ListBox,
     rows: Vec<SegmentRow>,
+    // Subsplit groups and their headers, in the same order
+    groups: Vec<SegmentGroup>,
+    group_headers: Vec<GroupHeader>,
+    // Rows hidden inside collapsed groups or by the search
+    hidden: Vec<bool>,
+    search_bar: SearchBar,
+    search_entry: SearchEntry,
+    // Scroll position from before the search, restored once it is cleared
+    search_scroll: Option<f64>,
+    // What the current rows were built from
+    layout: RowLayout,
+    // What the groups and search were last applied for
+    groups_shown: GroupsShown,
     last_phase: TimerPhase,
     last_comparison: String,
 }
 
+/// Everything a `SegmentRow` is built from and can't change afterwards. Rows are only
+/// recreated when it differs, other changes refresh them in place.
+#[derive(Debug, Default, PartialEq)]
+struct RowLayout {
+    /// Name and icon hash of every segment, in order.
+    segments: Vec<(String, u64)>,
+    compact_rows: bool,
+    show_icons: bool,
+    icon_size: i32,
+    delta_min_width: i32,
+    comparison_min_width: i32,
+    subsplits: bool,
+}
+
+impl RowLayout {
+    fn of(timer: &Timer, config: &Config) -> Self {
+        let segments = timer
+            .run()
+            .segments()
+            .iter()
+            .map(|segment| (segment.name().to_owned(), icon_hash(segment.icon().data())))
+            .collect();
+        Self {
+            segments,
+            compact_rows: config.style.compact_rows.unwrap_or(false),
+            show_icons: config.style.show_icons.unwrap_or(true),
+            icon_size: icon_size(config.style.icon_size),
+            delta_min_width: config
+                .style
+                .delta_min_width
+                .unwrap_or(DEFAULT_DELTA_MIN_WIDTH),
+            comparison_min_width: config
+                .style
+                .comparison_min_width
+                .unwrap_or(DEFAULT_COMPARISON_MIN_WIDTH),
+            subsplits: config.style.subsplits.unwrap_or(false),
+        }
+    }
+}
+
+/// What decides which rows `apply_groups` shows and how they are titled, other than
+/// the rows themselves. It only has to run again once this changes.
+#[derive(Debug, Default, PartialEq)]
+struct GroupsShown {
+    split_index: Option<usize>,
+    collapsed: Vec<bool>,
+    search: Option<String>,
+}
+
+/// Expander and summed segment time of a subsplit group, shown on the row of its
+/// closing segment.
+struct GroupHeader {
+    toggle: Button,
+    time_label: Label,
+}
+
+impl GroupHeader {
+    fn new(group: &SegmentGroup) -> Self {
+        let toggle = Button::builder()
+            .valign(Align::Center)
+            .css_classes(["flat", "group-toggle"])
+            .build();
+        let name = group.name.clone();
+        toggle.connect_clicked(move |_| {
+            let ctx = TuxSplitContext::get_instance();
+            if let Ok(mut cfg) = ctx.config_mut() {
+                let collapsed = cfg.is_group_collapsed(&name);
+                cfg.set_group_collapsed(&name, !collapsed);
+                drop(cfg);
+                ctx.emit_run_changed();
+                ctx.schedule_config_save();
+            }
+        });
+        let time_label = Label::builder()
+            .valign(Align::Center)
+            .css_classes(["timer", "monospace", "tabular", "group-time"])
+            .build();
+        Self { toggle, time_label }
+    }
+}
+
+/// Whether the segment `name` contains the lowercase search `query`, ignoring the
+/// subsplit markers.
+fn matches_search(name: &str, query: &str) -> bool {
+    display_name(name).to_lowercase().contains(query)
+}
+
+/// Selects the first shown segment and scrolls to the top. Does nothing while the
+/// splits can't be selected (during a run).
+fn select_first_segment(list: &ListBox, scroller: &ScrolledWindow) -> bool {
+    if list.selection_mode() == SelectionMode::None {
+        return false;
+    }
+    // Rows of collapsed subsplit groups are hidden
+    let Some(row) = (0..)
+        .map_while(|index| list.row_at_index(index))
+        .find(|row| row.is_visible())
+    else {
+        return false;
+    };
+    list.grab_focus();
+    list.select_row(Some(&row));
+    let adjustment = scroller.vadjustment();
+    adjustment.set_value(adjustment.lower());
+    true
+}
+
+/// Selects the pinned last segment and scrolls the list to its end.
+fn select_last_segment(list: &ListBox, last_list: &ListBox, scroller: &ScrolledWindow) -> bool {
+    if last_list.selection_mode() == SelectionMode::None {
+        return false;
+    }
+    let Some(row) = last_list.row_at_index(0) else {
+        return false;
+    };
+    list.unselect_all();
+    last_list.grab_focus();
+    last_list.select_row(Some(&row));
+    let adjustment = scroller.vadjustment();
+    adjustment.set_value(adjustment.upper());
+    true
+}
+
 impl SegmentList {
     pub fn new(timer: &Timer, config: &Config) -> Self {
         let container = GtkBox::builder()
@@ -83,7 +282,7 @@ impl SegmentList {
         let scroller = ScrolledWindow::builder()
             .hexpand(true)
             .vexpand(false)
-            .min_content_height(SegmentRow::get_natural_height())
+            .min_content_height(SegmentRow::get_natural_height(config))
             .height_request(height_request)
             .css_classes(["no-background"])
             .kinetic_scrolling(true)
@@ -100,6 +299,34 @@ impl SegmentList {
             .css_classes(["last-split-boxed-list", "no-background"])
             .build();
 
+        // Ctrl+F searches the splits by name, only while no attempt is running
+        let search_entry = SearchEntry::builder()
+            .placeholder_text("Search Splits")
+            .hexpand(true)
+            .build();
+        let search_bar = SearchBar::builder()
+            .child(&search_entry)
+            .show_close_button(true)
+            .build();
+        search_bar.connect_entry(&search_entry);
+        let shortcuts = ShortcutController::new();
+        shortcuts.set_scope(ShortcutScope::Global);
+        let search_bar_weak = search_bar.downgrade();
+        shortcuts.add_shortcut(Shortcut::new(
+            ShortcutTrigger::parse_string("<Control>f"),
+            Some(CallbackAction::new(move |_, _| {
+                match search_bar_weak.upgrade() {
+                    Some(bar) if bar.is_sensitive() => {
+                        bar.set_search_mode(!bar.is_search_mode());
+                        Propagation::Stop
+                    }
+                    _ => Propagation::Proceed,
+                }
+            })),
+        ));
+        container.add_controller(shortcuts);
+
+        container.append(&search_bar);
         container.append(&scroller);
         container.append(&last_segment_list);
         scroller.set_child(Some(&list));
@@ -110,10 +337,19 @@ impl SegmentList {
             list,
             last_segment_list,
             rows: Vec::new(),
+            groups: Vec::new(),
+            group_headers: Vec::new(),
+            hidden: Vec::new(),
+            search_bar,
+            search_entry,
+            search_scroll: None,
+            layout: RowLayout::default(),
+            groups_shown: GroupsShown::default(),
             last_phase: timer.current_phase(),
             last_comparison: timer.current_comparison().to_owned(),
         };
         this.build_rows(timer, config);
+        this.layout = RowLayout::of(timer, config);
         this.list.unselect_all();
         this.enable_multilateral_selection();
         this
@@ -131,6 +367,11 @@ impl SegmentList {
         &self.last_segment_list
     }
 
+    pub fn set_orientation(&self, orientation: TimerOrientation) {
+        let (horizontal, vertical) = scroller_policy(orientation);
+        self.scroller.set_policy(horizontal, vertical);
+    }
+
     pub fn update(&mut self, timer: &Timer, config: &Config, force_rebuild: bool) {
         // Detect structural changes or comparison/splits changes that force a full rebuild.
         let phase = timer.current_phase();
@@ -143,22 +384,29 @@ impl SegmentList {
         let phase_changed = self.last_phase != phase;
 
         let selected_index = self.get_selected_row_index();
+        self.update_search(phase);
+        // Group times change with splits and comparisons, those come with a new split
+        // index or one of the other changes
+        if comp_changed
+            || phase_changed
+            || force_rebuild
+            || self.groups_shown != self.groups_shown(timer, config)
+        {
+            self.apply_groups(timer, config);
+        }
 
         if comp_changed || phase_changed || force_rebuild {
-            self.rebuild_rows(timer, config);
+            let layout = RowLayout::of(timer, config);
+            if layout == self.layout {
+                self.refresh_rows(timer, config);
+            } else {
+                self.rebuild_rows(timer, config, layout, selected_index);
+            }
         } else if phase.is_running() {
             self.update_scroll_position(timer, config);
             self.update_rows_minimal(timer, config);
         }
 
-        if comp_changed
-            && let Some(index) = selected_index
-            && let Some(row) = self.list.row_at_index(index)
-        {
-            self.list.grab_focus();
-            self.list.select_row(Some(&row));
-        }
-
         if phase_changed {
             if phase.is_not_running() {
                 // Go to the beggining of the split list after a reset
@@ -174,9 +422,13 @@ impl SegmentList {
         self.last_phase = phase;
         self.last_comparison = timer.current_comparison().to_string();
 
-        // Update scroller height request
+        // Update scroller height request, rows change height with the font scale
         let height_request = SegmentList::compute_scroller_height(timer, config);
         self.scroller.set_height_request(height_request);
+        let row_height = SegmentRow::get_natural_height(config);
+        if self.scroller.min_content_height() != row_height {
+            self.scroller.set_min_content_height(row_height);
+        }
     }
 
     fn update_scroll_position(&mut self, timer: &Timer, config: &Config) {
@@ -184,10 +436,19 @@ impl SegmentList {
 
         if let Some(cur) = timer.current_split_index() {
             let follow_from = config.style.segments_scroll_follow_from.unwrap_or(7);
-            let y = SegmentRow::get_natural_height() * (cur as i32 + 1 - follow_from as i32);
+            // Rows hidden in collapsed groups take no space
+            let position = cur
+                - self
+                    .hidden
+                    .iter()
+                    .take(cur)
+                    .filter(|&&hidden| hidden)
+                    .count();
+            let y =
+                SegmentRow::get_natural_height(config) * (position as i32 + 1 - follow_from as i32);
 
             if self.list.row_at_index(cur as i32).is_some() {
-                adjustment.set_value(if cur >= follow_from {
+                adjustment.set_value(if position >= follow_from {
                     f64::from(y)
                 } else {
                     0.0
@@ -284,6 +545,31 @@ impl SegmentList {
             Propagation::Proceed
         });
         self.last_segment_list.add_controller(up_ctrl);
+
+        // Home / End jump to the first and last segment from either list. Captured so
+        // the list's own Home / End don't stop at its own ends.
+        for target in [&self.list, &self.last_segment_list] {
+            let list = self.list.clone();
+            let last_list = self.last_segment_list.clone();
+            let scroller = self.scroller.clone();
+            let jump_ctrl = EventControllerKey::new();
+            jump_ctrl.set_propagation_phase(PropagationPhase::Capture);
+            jump_ctrl.connect_key_pressed(move |_, keyval, _, _| {
+                let jumped = match keyval {
+                    gdk::Key::Home | gdk::Key::KP_Home => select_first_segment(&list, &scroller),
+                    gdk::Key::End | gdk::Key::KP_End => {
+                        select_last_segment(&list, &last_list, &scroller)
+                    }
+                    _ => false,
+                };
+                if jumped {
+                    Propagation::Stop
+                } else {
+                    Propagation::Proceed
+                }
+            });
+            target.add_controller(jump_ctrl);
+        }
     }
 
     fn update_selection_policy(&mut self, phase: TimerPhase) {
@@ -313,8 +599,36 @@ impl SegmentList {
         }
     }
 
-    fn rebuild_rows(&mut self, timer: &Timer, config: &Config) {
+    /// Updates every row in place, keeping the selection and scroll position.
+    fn refresh_rows(&mut self, timer: &Timer, config: &Config) {
+        let opt_current_segment_index = timer.current_split_index();
+        for (index, (row, segment)) in self.rows.iter_mut().zip(timer.run().segments()).enumerate()
+        {
+            row.refresh(timer, config, opt_current_segment_index, index, segment);
+        }
+    }
+
+    /// Recreates the rows after segments were added, removed or reordered, selecting the
+    /// same position and restoring the scroll offset afterwards.
+    fn rebuild_rows(
+        &mut self,
+        timer: &Timer,
+        config: &Config,
+        layout: RowLayout,
+        selected_index: Option<i32>,
+    ) {
+        let scroll_offset = self.scroller.vadjustment().value();
         self.build_rows(timer, config);
+        self.layout = layout;
+
+        if let Some(index) = selected_index
+            && let Some(row) = self.list.row_at_index(index)
+        {
+            self.list.select_row(Some(&row));
+        }
+        // The new rows have no size yet, so the offset would be clamped right away
+        let adjustment = self.scroller.vadjustment();
+        glib::idle_add_local_once(move || adjustment.set_value(scroll_offset));
     }
 
     fn build_rows(&mut self, timer: &Timer, config: &Config) {
@@ -339,18 +653,153 @@ impl SegmentList {
             self.rows.push(row);
         }
 
+        self.groups = timer_groups(timer, config);
+        self.group_headers = self
+            .groups
+            .iter()
+            .map(|group| {
+                let header = GroupHeader::new(group);
+                self.rows[group.end].add_group_header(&header);
+                header
+            })
+            .collect();
+        self.apply_groups(timer, config);
+
         // Refresh caches
         self.last_phase = timer.current_phase();
         self.last_comparison = timer.current_comparison().to_string();
     }
 
+    /// Disables the search during a run and remembers the scroll position while
+    /// searching, restoring it once the search is cleared.
+    fn update_search(&mut self, phase: TimerPhase) {
+        let searchable = !(phase.is_running() || phase.is_paused());
+        if self.search_bar.is_sensitive() != searchable {
+            self.search_bar.set_sensitive(searchable);
+        }
+        if !searchable && !self.search_entry.text().is_empty() {
+            self.search_bar.set_search_mode(false);
+            self.search_entry.set_text("");
+        }
+
+        let adjustment = self.scroller.vadjustment();
+        match (self.search_query(phase).is_some(), self.search_scroll) {
+            (true, None) => self.search_scroll = Some(adjustment.value()),
+            (false, Some(value)) => {
+                self.search_scroll = None;
+                // The rows shown again only make the list taller on the next layout
+                let handler = Rc::new(Cell::new(None));
+                let handler_binding = handler.clone();
+                handler.set(Some(adjustment.connect_upper_notify(move |adjustment| {
+                    adjustment.set_value(value);
+                    if let Some(handler) = handler_binding.take() {
+                        adjustment.disconnect(handler);
+                    }
+                })));
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows only the segments matching the current search text.
+    fn apply_search(&mut self, timer: &Timer, config: &Config) {
+        self.update_search(timer.current_phase());
+        self.apply_groups(timer, config);
+    }
+
+    /// The lowercase search text, `None` when there is nothing to search for or an
+    /// attempt is running.
+    fn search_query(&self, phase: TimerPhase) -> Option<String> {
+        if phase.is_running() || phase.is_paused() {
+            return None;
+        }
+        let query = self.search_entry.text().trim().to_lowercase();
+        (!query.is_empty()).then_some(query)
+    }
+
+    fn groups_shown(&self, timer: &Timer, config: &Config) -> GroupsShown {
+        GroupsShown {
+            split_index: timer.current_split_index(),
+            collapsed: self
+                .groups
+                .iter()
+                .map(|group| is_collapsed(group, timer, config))
+                .collect(),
+            search: self.search_query(timer.current_phase()),
+        }
+    }
+
+    /// Hides the subsplits of collapsed groups and titles the rows accordingly. While
+    /// searching, only the segments matching the search are shown instead.
+    fn apply_groups(&mut self, timer: &Timer, config: &Config) {
+        self.groups_shown = self.groups_shown(timer, config);
+        self.hidden = hidden_segments(timer, config);
+        if let Some(query) = self.search_query(timer.current_phase()) {
+            for (hidden, segment) in self.hidden.iter_mut().zip(timer.run().segments()) {
+                *hidden = !matches_search(segment.name(), &query);
+            }
+        }
+        for (row, &hidden) in self.rows.iter().zip(&self.hidden) {
+            if row.row.is_visible() == hidden {
+                row.row.set_visible(!hidden);
+            }
+        }
+        if !config.style.subsplits.unwrap_or(false) {
+            return;
+        }
+
+        let mut titles: Vec<&str> = timer
+            .run()
+            .segments()
+            .iter()
+            .map(|segment| display_name(segment.name()))
+            .collect();
+        for (group, header) in self.groups.iter().zip(&self.group_headers) {
+            let collapsed = is_collapsed(group, timer, config);
+            header.toggle.set_icon_name(if collapsed {
+                "pan-end-symbolic"
+            } else {
+                "pan-down-symbolic"
+            });
+            header.toggle.set_tooltip_text(Some(if collapsed {
+                "Expand Group"
+            } else {
+                "Collapse Group"
+            }));
+            header.time_label.set_visible(collapsed);
+            if collapsed {
+                // The closing row stands for the whole group
+                if let Some(title) = titles.get_mut(group.end) {
+                    *title = &group.name;
+                }
+                header.time_label.set_label(
+                    &config
+                        .format
+                        .segment
+                        .format_duration(&group_duration(group, timer)),
+                );
+            }
+        }
+        for (row, title) in self.rows.iter().zip(titles) {
+            row.set_title(title);
+        }
+    }
+
     fn compute_scroller_height(timer: &Timer, config: &Config) -> i32 {
         let segments_requested = config.style.max_segments_displayed.unwrap_or(10);
-
-        if segments_requested < timer.run().len() - 1 {
-            SegmentRow::get_natural_height() * segments_requested as i32
+        let len = timer.run().len();
+        // Rows hidden in collapsed groups take no space
+        let hidden = hidden_segments(timer, config)
+            .iter()
+            .take(len - 1)
+            .filter(|&&hidden| hidden)
+            .count();
+        let rows = len - 1 - hidden;
+
+        if segments_requested < rows {
+            SegmentRow::get_natural_height(config) * segments_requested as i32
         } else {
-            SegmentRow::get_natural_height() * (timer.run().len() as i32 - 1)
+            SegmentRow::get_natural_height(config) * rows as i32
         }
     }
 }
@@ -359,6 +808,8 @@ impl SegmentList {
 pub struct SegmentRow {
     row: ActionRow,
     suffix: SegmentSuffix,
+    // Whether the segment was gold on the last refresh, it only flashes when it becomes one
+    gold: bool,
 }
 
 impl SegmentRow {
@@ -378,21 +829,23 @@ impl SegmentRow {
             .hexpand(true)
             .title_lines(1)
             .build();
+        if config.style.compact_rows.unwrap_or(false) {
+            row.add_css_class(COMPACT_ROW_CLASS);
+        }
 
-        let icon = segment.icon();
-        let mut data = icon.data().to_vec();
+        let data = segment.icon().data();
 
         if !data.is_empty() && config.style.show_icons.unwrap_or(true) {
-            if !data.ends_with(&[0x82]) {
-                // PNG data must end in AE 42 60 82 (IEND CRC)
-                // For some fucking reason, the data obtained from livesplit-core misses the last byte
-                data.push(0x82);
+            let cache_size = config
+                .style
+                .icon_cache_size
+                .unwrap_or(DEFAULT_ICON_CACHE_SIZE);
+            // Undecodable icons are skipped, the row still renders without one
+            if let Some(texture) = segment_icon_texture(data, cache_size) {
+                let image = gtk4::Image::from_paintable(Some(&texture));
+                image.set_pixel_size(icon_size(config.style.icon_size));
+                row.add_prefix(&image);
             }
-            let bytes = glib::Bytes::from(&data);
-            let texture = gtk4::gdk::Texture::from_bytes(&bytes).unwrap();
-            let image = gtk4::Image::from_paintable(Some(&texture));
-            image.set_pixel_size(24); // Slightly bigger than font
-            row.add_prefix(&image);
         }
 
         if Some(index) == opt_current_segment_index {
@@ -405,7 +858,8 @@ impl SegmentRow {
         // Add no transition for more responsive updates
         row.add_css_class("no-transition");
 
-        Self { row, suffix }
+        let gold = suffix.is_gold();
+        Self { row, suffix, gold }
     }
 
     pub fn refresh(
@@ -424,28 +878,94 @@ impl SegmentRow {
 
         self.suffix
             .compute_segment(timer, config, opt_current_segment_index, index, segment);
+
+        let gold = self.suffix.is_gold();
+        if gold && !self.gold && config.style.animations.unwrap_or(false) {
+            self.flash_gold();
+        }
+        self.gold = gold;
     }
 
-    fn get_natural_height() -> i32 {
+    /// Highlights the row for a second.
+    fn flash_gold(&self) {
+        self.row.add_css_class(GOLD_FLASH_CLASS);
+        let row = self.row.downgrade();
+        glib::timeout_add_local_once(GOLD_FLASH_DURATION, move || {
+            if let Some(row) = row.upgrade() {
+                row.remove_css_class(GOLD_FLASH_CLASS);
+            }
+        });
+    }
+
+    fn set_title(&self, title: &str) {
+        if self.row.title().as_str() != title {
+            self.row.set_title(title);
+        }
+    }
+
+    /// Puts the group expander in front of the title and the group time right before
+    /// the delta and comparison.
+    fn add_group_header(&self, header: &GroupHeader) {
+        self.row.add_prefix(&header.toggle);
+        self.row.add_suffix(&header.time_label);
+        if let Some(suffixes) = self.suffix.container().parent() {
+            header
+                .time_label
+                .insert_before(&suffixes, Some(self.suffix.container()));
+        }
+    }
+
+    fn get_natural_height(config: &Config) -> i32 {
         // We create an action row and measure its natural height
         let row = ActionRow::builder().title("Test").build();
+        if config.style.compact_rows.unwrap_or(false) {
+            row.add_css_class(COMPACT_ROW_CLASS);
+        }
         let monospace_label = Label::builder()
             .label("00:00:00")
             .css_classes(["timer", "monospace"])
             .build();
         row.add_suffix(&monospace_label);
+        // Icons bigger than the text make the rows taller
+        if config.style.show_icons.unwrap_or(true) {
+            let image = gtk4::Image::builder()
+                .pixel_size(icon_size(config.style.icon_size))
+                .build();
+            row.add_prefix(&image);
+        }
         row.measure(gtk4::Orientation::Vertical, -1).0 + 5 // Account for padding
     }
 }
 
+const GOLD_FLASH_CLASS: &str = "gold-flash";
+const GOLD_FLASH_DURATION: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Classes `compute_segment` may put on the delta label.
+const DELTA_LABEL_CLASSES: [&str; 8] = [
+    "goldsplit",
+    "greensplit",
+    "lostgreensplit",
+    "gainedredsplit",
+    "redsplit",
+    "delta-intensity-low",
+    "delta-intensity-medium",
+    "delta-intensity-high",
+];
+
 // A segment suffix contains both the delta and the comparison labels, and renders them in a box, that is meant to be attached to a SegmentRow
 pub struct SegmentSuffix {
     container: CenterBox,
     delta_label: Label,
     comparison_label: Label,
+    // Whether the passed segment beat its gold, whatever the split format shows
+    gold: Cell<bool>,
 }
 
 impl SegmentSuffix {
+    fn is_gold(&self) -> bool {
+        self.gold.get()
+    }
+
     pub fn new(
         timer: &Timer,
         config: &Config,
@@ -460,12 +980,26 @@ impl SegmentSuffix {
         let delta_label = Label::builder()
             .halign(Align::Center)
             .valign(Align::Center)
-            .css_classes(["timer", "monospace"])
+            .width_request(
+                config
+                    .style
+                    .delta_min_width
+                    .unwrap_or(DEFAULT_DELTA_MIN_WIDTH),
+            )
+            .xalign(1.0)
+            .css_classes(["timer", "monospace", "tabular"])
             .build();
         let comparison_label = Label::builder()
             .halign(Align::Center)
             .valign(Align::Center)
-            .css_classes(["timer", "monospace", "comparison"])
+            .width_request(
+                config
+                    .style
+                    .comparison_min_width
+                    .unwrap_or(DEFAULT_COMPARISON_MIN_WIDTH),
+            )
+            .xalign(1.0)
+            .css_classes(["timer", "monospace", "tabular", "comparison"])
             .build();
         container.set_start_widget(Some(&delta_label));
         container.set_end_widget(Some(&comparison_label));
@@ -474,6 +1008,7 @@ impl SegmentSuffix {
             container,
             delta_label,
             comparison_label,
+            gold: Cell::new(false),
         };
         suffix.compute_segment(timer, config, opt_current_segment_index, index, segment);
 
@@ -493,7 +1028,10 @@ impl SegmentSuffix {
         index: usize,
         segment: &livesplit_core::Segment,
     ) {
-        let segment_comparison_time = segment_comparison_time(segment, timer);
+        let method = timer.current_timing_method();
+        let comparison = segment.comparison(timer.current_comparison());
+        let (segment_comparison_time, comparison_derived) =
+            display_duration(&comparison, method, config);
         let (previous_split_time, gold_duration, previous_comparison_duration) =
             previous_split_combined_gold_and_prev_comparison(timer, index);
         let segment_comparison_duration = segment_comparison_time
@@ -502,16 +1040,15 @@ impl SegmentSuffix {
             .abs();
 
         self.comparison_label.set_label(
-            config
-                .format
-                .segment
-                .format_split_time(
-                    &segment.comparison(timer.current_comparison()),
-                    timer.current_timing_method(),
-                )
-                .as_str(),
+            format_display_time(&config.format.segment, &comparison, method, config).as_str(),
         );
         self.delta_label.set_label("");
+        // Rows are reused across updates, drop the classes of the previous result
+        for class in DELTA_LABEL_CLASSES {
+            self.delta_label.remove_css_class(class);
+        }
+        self.comparison_label.remove_css_class("skipped");
+        self.gold.set(false);
         if let Some(current_segment_index) = opt_current_segment_index {
             if current_segment_index > index {
                 self.compute_passed_segment(
@@ -519,6 +1056,7 @@ impl SegmentSuffix {
                     config,
                     segment,
                     segment_comparison_time,
+                    comparison_derived,
                     previous_split_time,
                     segment_comparison_duration,
                     gold_duration,
@@ -531,6 +1069,7 @@ impl SegmentSuffix {
                     config,
                     index,
                     segment_comparison_time,
+                    comparison_derived,
                     previous_split_time,
                     gold_duration,
                 );
@@ -545,42 +1084,65 @@ impl SegmentSuffix {
         config: &Config,
         segment: &livesplit_core::Segment,
         segment_comparison_time: time::Duration,
+        comparison_derived: bool,
         previous_split_time: time::Duration,
         segment_comparison_duration: time::Duration,
         gold_duration: time::Duration,
     ) {
-        let split_time = segment_split_time(segment, timer);
+        let method = timer.current_timing_method();
+        let (split_time, split_derived) = display_duration(&segment.split_time(), method, config);
 
         if split_time == time::Duration::ZERO {
-            self.comparison_label.set_label("--");
+            // The comparison time is already set by compute_segment
+            if !config.style.skipped_show_comparison.unwrap_or(false) {
+                self.comparison_label.set_label("--");
+            }
             self.delta_label.set_label("");
+            if config.style.mark_skipped_segments.unwrap_or(false) {
+                self.comparison_label.add_css_class("skipped");
+            }
         } else {
             let diff = split_time
                 .checked_sub(segment_comparison_time)
                 .unwrap_or_default();
+            let split_duration = split_time
+                .checked_sub(previous_split_time)
+                .unwrap_or_default();
+            self.gold
+                .set(is_gold_segment(split_duration, gold_duration));
+            let split_format = config.general.split_format;
 
-            self.comparison_label.set_label(
-                config
-                    .format
-                    .segment
-                    .format_split_time(&segment.split_time(), timer.current_timing_method())
-                    .as_str(),
-            );
-            if segment_comparison_time != time::Duration::ZERO {
-                self.delta_label
-                    .set_label(format_signed(diff, config).as_str());
-
-                let split_duration = split_time
-                    .checked_sub(previous_split_time)
-                    .unwrap_or_default();
-
-                self.delta_label.add_css_class(classify_split_label(
+            let split_label = if split_format == SplitDisplay::Delta {
+                String::new()
+            } else {
+                format_display_time(
+                    &config.format.segment,
+                    &segment.split_time(),
+                    method,
+                    config,
+                )
+            };
+            self.comparison_label.set_label(&split_label);
+            if segment_comparison_time != time::Duration::ZERO
+                && split_format != SplitDisplay::AbsoluteTime
+            {
+                self.delta_label.set_label(&mark_derived(
+                    format_signed(diff, config),
+                    split_derived || comparison_derived,
+                ));
+
+                let class = classify_split_label(
                     segment_comparison_duration,
                     split_duration,
                     diff,
                     gold_duration,
                     false,
-                ));
+                );
+                self.delta_label.add_css_class(class);
+                if class != "goldsplit" && config.style.scale_delta_colors.unwrap_or(false) {
+                    self.delta_label
+                        .add_css_class(delta_intensity_class(diff, segment_comparison_time));
+                }
             }
         }
     }
@@ -592,6 +1154,7 @@ impl SegmentSuffix {
         config: &Config,
         index: usize,
         segment_comparison_time: time::Duration,
+        comparison_derived: bool,
         previous_split_time: time::Duration,
         gold_duration: time::Duration,
     ) {
@@ -609,19 +1172,97 @@ impl SegmentSuffix {
         } else {
             time::Duration::ZERO
         };
-        if segment_comparison_time != time::Duration::ZERO
-            && (diff.is_positive()
-                || (gold_duration != time::Duration::ZERO && split_running_time >= gold_duration))
-        {
-            self.delta_label
-                .set_label(format_signed(diff, config).as_str());
+        if show_live_delta(
+            config.style.always_show_live_delta.unwrap_or(false),
+            segment_comparison_time,
+            diff,
+            split_running_time,
+            gold_duration,
+        ) {
+            self.delta_label.set_label(&mark_derived(
+                format_signed(diff, config),
+                comparison_derived,
+            ));
         }
     }
 }
 
+/// Whether the running segment shows its delta. Without `always` it only appears once
+/// the attempt is behind the comparison or slower than the best segment. Segments
+/// without a comparison time never show one.
+fn show_live_delta(
+    always: bool,
+    segment_comparison_time: time::Duration,
+    diff: time::Duration,
+    split_running_time: time::Duration,
+    gold_duration: time::Duration,
+) -> bool {
+    if segment_comparison_time == time::Duration::ZERO {
+        return false;
+    }
+    always
+        || diff.is_positive()
+        || (gold_duration != time::Duration::ZERO && split_running_time >= gold_duration)
+}
+
+#[cfg(test)]
+mod live_delta_tests {
+    use super::show_live_delta;
+    use time::Duration;
+
+    #[test]
+    fn threshold_hides_delta_while_ahead_and_faster_than_gold() {
+        let comparison = Duration::seconds(60);
+        // 5s ahead, 20s into a segment with a 30s gold
+        assert!(!show_live_delta(
+            false,
+            comparison,
+            Duration::seconds(-5),
+            Duration::seconds(20),
+            Duration::seconds(30),
+        ));
+        // Behind the comparison
+        assert!(show_live_delta(
+            false,
+            comparison,
+            Duration::seconds(2),
+            Duration::seconds(20),
+            Duration::seconds(30),
+        ));
+        // Ahead, but slower than the gold
+        assert!(show_live_delta(
+            false,
+            comparison,
+            Duration::seconds(-5),
+            Duration::seconds(31),
+            Duration::seconds(30),
+        ));
+    }
+
+    #[test]
+    fn always_shows_delta_while_ahead() {
+        assert!(show_live_delta(
+            true,
+            Duration::seconds(60),
+            Duration::seconds(-5),
+            Duration::seconds(20),
+            Duration::seconds(30),
+        ));
+        // Still nothing to compare against
+        assert!(!show_live_delta(
+            true,
+            Duration::ZERO,
+            Duration::seconds(-5),
+            Duration::seconds(20),
+            Duration::seconds(30),
+        ));
+    }
+}
+
 #[cfg(test)]
 mod segment_row_ui_tests {
     use super::*;
+    use crate::utils::comparisons::DERIVED_TIME_MARKER;
     use adw::prelude::*;
     use gtk4;
     use std::sync::Once;
@@ -676,4 +1317,537 @@ mod segment_row_ui_tests {
             "Expected current-segment class"
         );
     }
+
+    #[gtk4::test]
+    fn segment_suffix_labels_use_configured_min_widths() {
+        gtk_test_init();
+
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("Split A"));
+        let timer = livesplit_core::Timer::new(run).expect("timer");
+        let mut config = Config::default();
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, None, 0, segment);
+        assert_eq!(suffix.delta_label.width_request(), DEFAULT_DELTA_MIN_WIDTH);
+        assert_eq!(
+            suffix.comparison_label.width_request(),
+            DEFAULT_COMPARISON_MIN_WIDTH
+        );
+        assert!(suffix.delta_label.has_css_class("tabular"));
+        assert!(suffix.comparison_label.has_css_class("tabular"));
+
+        config.style.delta_min_width = Some(90);
+        config.style.comparison_min_width = Some(120);
+        let suffix = SegmentSuffix::new(&timer, &config, None, 0, segment);
+        assert_eq!(suffix.delta_label.width_request(), 90);
+        assert_eq!(suffix.comparison_label.width_request(), 120);
+    }
+
+    #[gtk4::test]
+    fn compact_rows_fit_more_segments() {
+        gtk_test_init();
+        // The compact row style lives in the bundled stylesheet
+        let provider = gtk4::CssProvider::new();
+        provider.load_from_string(include_str!("../../../data/css/tuxsplit.css"));
+        gtk4::style_context_add_provider_for_display(
+            &gtk4::gdk::Display::default().expect("display"),
+            &provider,
+            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+
+        let mut run = livesplit_core::Run::new();
+        for name in ["A", "B", "C", "D", "E", "F"] {
+            run.push_segment(livesplit_core::Segment::new(name));
+        }
+        let timer = livesplit_core::Timer::new(run).expect("timer");
+        let mut config = Config::default();
+        let regular = SegmentList::compute_scroller_height(&timer, &config);
+
+        config.style.compact_rows = Some(true);
+        let compact = SegmentList::compute_scroller_height(&timer, &config);
+        assert!(compact < regular, "{compact} should be below {regular}");
+
+        let segment = &timer.run().segments()[0];
+        assert!(
+            SegmentRow::new(&timer, &config, None, 0, segment)
+                .row()
+                .has_css_class(COMPACT_ROW_CLASS)
+        );
+    }
+
+    #[gtk4::test]
+    fn horizontal_orientation_lets_splits_scroll_sideways() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("A"));
+        let timer = livesplit_core::Timer::new(run).expect("timer");
+        let list = SegmentList::new(&timer, &Config::default());
+
+        list.set_orientation(TimerOrientation::Horizontal);
+        assert_eq!(
+            list.scroller.policy(),
+            (PolicyType::Automatic, PolicyType::Automatic)
+        );
+
+        list.set_orientation(TimerOrientation::Vertical);
+        assert_eq!(
+            list.scroller.policy(),
+            (PolicyType::Never, PolicyType::Automatic)
+        );
+    }
+
+    #[gtk4::test]
+    fn collapsed_subsplit_groups_show_only_their_group_row() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        for name in ["Intro", "-A", "-B", "{Group}C", "End"] {
+            run.push_segment(livesplit_core::Segment::new(name));
+        }
+        let timer = livesplit_core::Timer::new(run).expect("timer");
+        let mut config = Config::default();
+        config.style.subsplits = Some(true);
+        let expanded_height = SegmentList::compute_scroller_height(&timer, &config);
+
+        config.set_group_collapsed("Group", true);
+        let mut list = SegmentList::new(&timer, &config);
+        assert!(!list.rows[1].row().is_visible());
+        assert!(!list.rows[2].row().is_visible());
+        assert!(list.rows[3].row().is_visible());
+        assert_eq!(list.rows[3].row().title().as_str(), "Group");
+        assert!(list.group_headers[0].time_label.is_visible());
+        assert!(SegmentList::compute_scroller_height(&timer, &config) < expanded_height);
+
+        config.set_group_collapsed("Group", false);
+        list.update(&timer, &config, true);
+        assert!(list.rows[1].row().is_visible());
+        assert_eq!(list.rows[1].row().title().as_str(), "A");
+        assert_eq!(list.rows[3].row().title().as_str(), "C");
+        assert!(!list.group_headers[0].time_label.is_visible());
+    }
+
+    #[gtk4::test]
+    fn body_shows_the_note_of_the_current_segment() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("A"));
+        run.push_segment(livesplit_core::Segment::new("B"));
+        crate::utils::notes::set_segment_note(&mut run, 1, "Skip the cutscene");
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let config = Config::default();
+
+        let mut body = TimerBody::new(&timer, &config);
+        let note = body.notes.container().clone();
+        assert!(!note.is_visible());
+
+        // The first segment has no note
+        timer.start();
+        body.refresh(&timer, &config, false);
+        assert!(!note.is_visible());
+
+        timer.split();
+        body.refresh(&timer, &config, false);
+        assert!(note.is_visible());
+        assert_eq!(note.label().as_str(), "Skip the cutscene");
+
+        timer.reset(false);
+        body.refresh(&timer, &config, false);
+        assert!(!note.is_visible());
+    }
+
+    #[gtk4::test]
+    fn groups_are_only_reapplied_when_they_change() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        for name in ["Intro", "-A", "-B", "{Group}C", "End"] {
+            run.push_segment(livesplit_core::Segment::new(name));
+        }
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let mut config = Config::default();
+        config.style.subsplits = Some(true);
+        config.set_group_collapsed("Group", true);
+        let mut list = SegmentList::new(&timer, &config);
+
+        // A plain tick leaves the rows alone
+        list.rows[3].row().set_title("Stale");
+        list.update(&timer, &config, false);
+        assert_eq!(list.rows[3].row().title().as_str(), "Stale");
+
+        // Entering the group expands it
+        timer.start();
+        list.update(&timer, &config, false);
+        timer.split();
+        list.update(&timer, &config, false);
+        assert!(list.rows[1].row().is_visible());
+        assert_eq!(list.rows[3].row().title().as_str(), "C");
+    }
+
+    #[gtk4::test]
+    fn both_resets_make_the_splits_selectable_again() {
+        gtk_test_init();
+        let config = Config::default();
+        for update_splits in [true, false] {
+            let mut run = livesplit_core::Run::new();
+            run.push_segment(livesplit_core::Segment::new("A"));
+            run.push_segment(livesplit_core::Segment::new("B"));
+            let mut timer = livesplit_core::Timer::new(run).expect("timer");
+            let mut list = SegmentList::new(&timer, &config);
+
+            timer.start();
+            list.update(&timer, &config, false);
+            assert_eq!(list.list.selection_mode(), SelectionMode::None);
+
+            timer.reset(update_splits);
+            list.update(&timer, &config, false);
+            assert_eq!(list.list.selection_mode(), SelectionMode::Single);
+            assert!(list.list.selected_row().is_none());
+        }
+    }
+
+    #[gtk4::test]
+    fn home_and_end_jump_to_the_first_and_last_segment() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        for name in ["A", "B", "C"] {
+            run.push_segment(livesplit_core::Segment::new(name));
+        }
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let config = Config::default();
+        let mut list = SegmentList::new(&timer, &config);
+
+        assert!(select_last_segment(
+            &list.list,
+            &list.last_segment_list,
+            &list.scroller
+        ));
+        assert!(list.last_segment_list.selected_row().is_some());
+
+        assert!(select_first_segment(&list.list, &list.scroller));
+        assert_eq!(list.list.selected_row().map(|row| row.index()), Some(0));
+        assert!(list.last_segment_list.selected_row().is_none());
+
+        // The splits aren't selectable while running, the last segment is
+        timer.start();
+        list.update(&timer, &config, false);
+        assert!(!select_first_segment(&list.list, &list.scroller));
+        assert!(list.list.selected_row().is_none());
+        assert!(select_last_segment(
+            &list.list,
+            &list.last_segment_list,
+            &list.scroller
+        ));
+    }
+
+    #[test]
+    fn search_matches_names_without_case_or_markers() {
+        assert!(matches_search("Forsaken City", "city"));
+        assert!(matches_search("-Old Site", "old"));
+        assert!(!matches_search("-Old Site", "-"));
+        assert!(matches_search("{Chapters 1-2}Celestial Resort", "resort"));
+        assert!(!matches_search("Summit", "city"));
+    }
+
+    #[gtk4::test]
+    fn searching_shows_only_matching_segments_until_running() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        for name in ["Forsaken City", "Old Site", "City Rooftops", "Summit"] {
+            run.push_segment(livesplit_core::Segment::new(name));
+        }
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let config = Config::default();
+        let mut list = SegmentList::new(&timer, &config);
+
+        list.search_entry.set_text("CITY");
+        list.apply_search(&timer, &config);
+        let visible: Vec<bool> = list.rows.iter().map(|row| row.row().is_visible()).collect();
+        assert_eq!(visible, [true, false, true, false]);
+
+        // The live view always shows every split
+        timer.start();
+        list.update(&timer, &config, false);
+        assert!(list.rows.iter().all(|row| row.row().is_visible()));
+        assert!(!list.search_bar.is_sensitive());
+        assert!(list.search_entry.text().is_empty());
+
+        timer.reset(false);
+        list.update(&timer, &config, false);
+        assert!(list.search_bar.is_sensitive());
+    }
+
+    #[gtk4::test]
+    fn time_changes_reuse_rows_and_new_segments_rebuild_them() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("A"));
+        run.push_segment(livesplit_core::Segment::new("B"));
+        run.push_segment(livesplit_core::Segment::new("C"));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let config = Config::default();
+        let mut list = SegmentList::new(&timer, &config);
+        let first_row = list.rows[0].row().clone();
+
+        let mut run = timer.run().clone();
+        run.segment_mut(0).set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(10.0))),
+        );
+        timer.set_run(run).expect("same segment count");
+        list.update(&timer, &config, true);
+        assert_eq!(list.rows[0].row(), &first_row);
+
+        let mut run = timer.run().clone();
+        run.push_segment(livesplit_core::Segment::new("D"));
+        timer.set_run(run).expect("valid run");
+        list.update(&timer, &config, true);
+        assert_eq!(list.rows.len(), 4);
+        assert_ne!(list.rows[0].row(), &first_row);
+    }
+
+    #[gtk4::test]
+    fn refreshing_a_row_drops_stale_delta_classes() {
+        gtk_test_init();
+        let timer = timer_with_skipped_segment();
+        let mut config = Config::default();
+        config.style.mark_skipped_segments = Some(true);
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        suffix.delta_label.add_css_class("redsplit");
+        assert!(suffix.comparison_label.has_css_class("skipped"));
+
+        config.style.mark_skipped_segments = Some(false);
+        suffix.compute_segment(&timer, &config, Some(1), 0, segment);
+        assert!(!suffix.delta_label.has_css_class("redsplit"));
+        assert!(!suffix.comparison_label.has_css_class("skipped"));
+    }
+
+    // Happens with game time or a start offset; the live delta is computed with
+    // saturating arithmetic, so it must not panic
+    #[gtk4::test]
+    fn comparison_ahead_of_the_live_time_does_not_panic() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        let mut first = livesplit_core::Segment::new("Split A");
+        first.set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(1000.0))),
+        );
+        run.push_segment(first);
+        run.push_segment(livesplit_core::Segment::new("Split B"));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(livesplit_core::TimingMethod::RealTime);
+        timer.start();
+        let mut config = Config::default();
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(0), 0, segment);
+        assert_eq!(suffix.delta_label.label().as_str(), "");
+
+        config.style.always_show_live_delta = Some(true);
+        suffix.compute_segment(&timer, &config, Some(0), 0, segment);
+        assert!(suffix.delta_label.label().starts_with('-'));
+    }
+
+    // Two segments with the first one skipped and the second one running
+    fn timer_with_skipped_segment() -> livesplit_core::Timer {
+        let mut run = livesplit_core::Run::new();
+        let mut first = livesplit_core::Segment::new("Split A");
+        first.set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(10.0))),
+        );
+        run.push_segment(first);
+        run.push_segment(livesplit_core::Segment::new("Split B"));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(livesplit_core::TimingMethod::RealTime);
+        timer.start();
+        timer.skip_split();
+        timer
+    }
+
+    #[gtk4::test]
+    fn passed_splits_follow_the_split_format() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        let mut first = livesplit_core::Segment::new("Split A");
+        first.set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(30.0))),
+        );
+        run.push_segment(first);
+        run.push_segment(livesplit_core::Segment::new("Split B"));
+        // Splitting right away takes the 10 second offset, 20 seconds ahead
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(10.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(livesplit_core::TimingMethod::RealTime);
+        timer.start();
+        timer.split();
+        let mut config = Config::default();
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        let split_time = suffix.comparison_label.label().to_string();
+        let delta = suffix.delta_label.label().to_string();
+        assert!(split_time.contains("10"), "{split_time}");
+        assert!(delta.starts_with('-'), "{delta}");
+
+        config.general.split_format = SplitDisplay::Delta;
+        suffix.compute_segment(&timer, &config, Some(1), 0, segment);
+        assert_eq!(suffix.comparison_label.label().as_str(), "");
+        assert_eq!(suffix.delta_label.label().as_str(), delta);
+
+        config.general.split_format = SplitDisplay::AbsoluteTime;
+        suffix.compute_segment(&timer, &config, Some(1), 0, segment);
+        assert_eq!(suffix.comparison_label.label().as_str(), split_time);
+        assert_eq!(suffix.delta_label.label().as_str(), "");
+        for class in DELTA_LABEL_CLASSES {
+            assert!(!suffix.delta_label.has_css_class(class));
+        }
+    }
+
+    #[gtk4::test]
+    fn passed_split_delta_marks_game_time_fallback() {
+        gtk_test_init();
+        let mut run = livesplit_core::Run::new();
+        let mut first = livesplit_core::Segment::new("Split A");
+        // Real time only, so game time mode has nothing to compare against
+        first.set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(30.0))),
+        );
+        run.push_segment(first);
+        run.push_segment(livesplit_core::Segment::new("Split B"));
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(10.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(livesplit_core::TimingMethod::GameTime);
+        timer.start();
+        timer.split();
+        let mut config = Config::default();
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        assert_eq!(suffix.delta_label.label().as_str(), "");
+
+        config.general.game_time_fallback = true;
+        suffix.compute_segment(&timer, &config, Some(1), 0, segment);
+        let delta = suffix.delta_label.label().to_string();
+        assert!(delta.starts_with('-'), "{delta}");
+        assert!(delta.ends_with(DERIVED_TIME_MARKER), "{delta}");
+    }
+
+    /// A started timer whose first split, taken right away, beats the 20 second gold.
+    fn timer_about_to_gold() -> livesplit_core::Timer {
+        let mut run = livesplit_core::Run::new();
+        let mut first = livesplit_core::Segment::new("Split A");
+        first.set_personal_best_split_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(30.0))),
+        );
+        first.set_best_segment_time(
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(20.0))),
+        );
+        run.push_segment(first);
+        run.push_segment(livesplit_core::Segment::new("Split B"));
+        // Splitting right away takes the 10 second offset, beating the 20 second gold
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(10.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(livesplit_core::TimingMethod::RealTime);
+        timer.start();
+        timer
+    }
+
+    #[gtk4::test]
+    fn rows_flash_only_when_becoming_gold() {
+        gtk_test_init();
+        let mut timer = timer_about_to_gold();
+        let mut config = Config::default();
+        config.style.animations = Some(true);
+
+        let mut row = SegmentRow::new(&timer, &config, Some(0), 0, &timer.run().segments()[0]);
+        assert!(!row.row().has_css_class(GOLD_FLASH_CLASS));
+
+        timer.split();
+        row.refresh(&timer, &config, Some(1), 0, &timer.run().segments()[0]);
+        assert!(row.row().has_css_class(GOLD_FLASH_CLASS));
+
+        // Staying gold doesn't flash again
+        row.row().remove_css_class(GOLD_FLASH_CLASS);
+        row.refresh(&timer, &config, Some(1), 0, &timer.run().segments()[0]);
+        assert!(!row.row().has_css_class(GOLD_FLASH_CLASS));
+
+        // Nor does anything flash with animations off
+        timer.undo_split();
+        row.refresh(&timer, &config, Some(0), 0, &timer.run().segments()[0]);
+        config.style.animations = Some(false);
+        timer.split();
+        row.refresh(&timer, &config, Some(1), 0, &timer.run().segments()[0]);
+        assert!(!row.row().has_css_class(GOLD_FLASH_CLASS));
+    }
+
+    #[gtk4::test]
+    fn gold_flashes_without_a_delta_shown() {
+        gtk_test_init();
+        let mut timer = timer_about_to_gold();
+        let mut config = Config::default();
+        config.style.animations = Some(true);
+        config.general.split_format = SplitDisplay::AbsoluteTime;
+
+        let mut row = SegmentRow::new(&timer, &config, Some(0), 0, &timer.run().segments()[0]);
+        timer.split();
+        row.refresh(&timer, &config, Some(1), 0, &timer.run().segments()[0]);
+        assert!(row.suffix.delta_label.label().is_empty());
+        assert!(row.row().has_css_class(GOLD_FLASH_CLASS));
+    }
+
+    #[gtk4::test]
+    fn skipped_segment_shows_dashes_by_default() {
+        gtk_test_init();
+
+        let timer = timer_with_skipped_segment();
+        let config = Config::default();
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        assert_eq!(suffix.comparison_label.label().as_str(), "--");
+        assert_eq!(suffix.delta_label.label().as_str(), "");
+        assert!(!suffix.comparison_label.has_css_class("skipped"));
+    }
+
+    #[gtk4::test]
+    fn skipped_segment_can_be_marked() {
+        gtk_test_init();
+
+        let timer = timer_with_skipped_segment();
+        let mut config = Config::default();
+        config.style.mark_skipped_segments = Some(true);
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        assert_eq!(suffix.comparison_label.label().as_str(), "--");
+        assert!(suffix.comparison_label.has_css_class("skipped"));
+    }
+
+    #[gtk4::test]
+    fn skipped_segment_can_show_comparison_time() {
+        gtk_test_init();
+
+        let timer = timer_with_skipped_segment();
+        let mut config = Config::default();
+        config.style.skipped_show_comparison = Some(true);
+        let segment = &timer.run().segments()[0];
+
+        let suffix = SegmentSuffix::new(&timer, &config, Some(1), 0, segment);
+        let expected = format_display_time(
+            &config.format.segment,
+            &segment.comparison(timer.current_comparison()),
+            timer.current_timing_method(),
+            &config,
+        );
+        assert_ne!(expected, "--");
+        assert_eq!(suffix.comparison_label.label().as_str(), expected);
+        assert_eq!(suffix.delta_label.label().as_str(), "");
+        assert!(!suffix.comparison_label.has_css_class("skipped"));
+    }
 }
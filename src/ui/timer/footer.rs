@@ -1,9 +1,9 @@
-use crate::config::Config;
+use crate::config::{Config, IdleDisplay, TimerOrientation};
 use crate::formatters::label::format_label;
-use crate::ui::info::{
-    ALL_ADDITIONAL_INFOS, AdditionalInfo, AdditionalInfoKind, BestPossibleTimeInfo,
-    CurrentPaceInfo, PbChanceInfo, PossibleTimeSaveInfo, PrevSegmentBestInfo, PrevSegmentDiffInfo,
-    TotalPlaytimeInfo,
+use crate::utils::comparisons::{
+    classify_split_label, current_attempt_running_duration, display_duration, display_time,
+    format_display_time, format_signed, is_new_personal_best, mark_derived,
+    previous_split_combined_gold_and_prev_comparison, segment_comparison_time, sum_of_best,
 };
 
 use glib;
@@ -12,14 +12,14 @@ use gtk4::{
     Align, Box as GtkBox, CenterBox, Label, ListBox, Orientation::Horizontal, Orientation::Vertical,
 };
 
-use livesplit_core::{Timer, TimerPhase};
+use livesplit_core::comparison::personal_best;
+use livesplit_core::{Timer, TimerPhase, TimingMethod};
 
 pub struct TimerFooter {
     container: GtkBox,
     timer_container: CenterBox,
     segment_comparison: SegmentComparison,
     running_timer: RunningTimer,
-    additional_info: AdditionalInfoFooter,
 }
 
 impl TimerFooter {
@@ -47,20 +47,17 @@ impl TimerFooter {
 
         timer_container.set_start_widget(Some(segment_comparison.container()));
         timer_container.set_end_widget(Some(running_timer.container()));
+        segment_comparison
+            .container()
+            .set_visible(!config.style.compact.unwrap_or(false));
 
-        let additional_info = AdditionalInfoFooter::new(timer, config);
-
-        let separator = gtk4::Separator::builder().build();
         container.append(&timer_container);
-        container.append(&separator);
-        container.append(&additional_info.container());
 
         Self {
             container,
             timer_container,
             segment_comparison,
             running_timer,
-            additional_info,
         }
     }
 
@@ -68,10 +65,27 @@ impl TimerFooter {
         &self.container
     }
 
+    /// Side by side with the other sections the footer is narrow, so the comparison
+    /// goes above the running timer instead of next to it.
+    pub fn set_orientation(&self, orientation: TimerOrientation) {
+        self.timer_container.set_orientation(match orientation {
+            TimerOrientation::Vertical => Horizontal,
+            TimerOrientation::Horizontal => Vertical,
+        });
+    }
+
     pub fn refresh(&mut self, timer: &Timer, config: &Config) {
-        self.segment_comparison.update(timer, config);
-        self.running_timer.update(timer, config);
-        self.additional_info.update(timer, config);
+        let selected = self
+            .segment_comparison
+            .selected_row_index(timer.run().len());
+        let data = compute_footer_data(timer, config, selected, timer.current_phase());
+        // The compact layout only keeps the running timer
+        let compact = config.style.compact.unwrap_or(false);
+        self.segment_comparison.container().set_visible(!compact);
+        if !compact {
+            self.segment_comparison.render(&data);
+        }
+        self.running_timer.render(&data);
 
         self.timer_container
             .set_start_widget(Some(self.segment_comparison.container()));
@@ -80,59 +94,203 @@ impl TimerFooter {
     }
 }
 
-pub struct AdditionalInfoFooter {
-    additional_info: Vec<Box<dyn AdditionalInfo>>,
+/// Everything the footer displays, computed without touching any widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FooterData {
+    pub best_value: String,
+    /// Comparison name with trailing colon, e.g. "PB:".
+    pub comparison_label: String,
+    pub comparison_value: String,
+    /// Whole part of the running timer, separator included.
+    pub timer_whole: String,
+    /// Fractional digits of the running timer.
+    pub timer_fraction: String,
+    pub timer_active: bool,
+    /// Delta color class of the running timer, with `style.color_main_timer`.
+    pub timer_class: Option<&'static str>,
+    /// Timer of the other timing method, when enabled.
+    pub secondary_timer: Option<String>,
+    /// Time saved or lost against the comparison, once the run has ended.
+    pub final_delta: Option<FinalDelta>,
 }
 
-impl AdditionalInfoFooter {
-    pub fn new(timer: &Timer, config: &Config) -> Self {
-        let additional_info: Vec<Box<dyn AdditionalInfo>> = vec![
-            Box::new(PrevSegmentDiffInfo::new(timer, config)),
-            Box::new(PrevSegmentBestInfo::new(timer, config)),
-            Box::new(BestPossibleTimeInfo::new(timer, config)),
-            Box::new(PossibleTimeSaveInfo::new(timer, config)),
-            Box::new(CurrentPaceInfo::new(timer, config)),
-            Box::new(TotalPlaytimeInfo::new(timer, config)),
-            Box::new(PbChanceInfo::new(timer, config)),
-        ];
-
-        // Initialize visibility based on config at creation time.
-        let mut this = Self { additional_info };
-        this.update(timer, config);
-        this
+/// Summary of a finished run, e.g. "+12.34 vs PB" or "-3.21, new PB!".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalDelta {
+    pub text: String,
+    /// Delta color class, "greensplit" or "redsplit".
+    pub class: &'static str,
+}
+
+/// Computes the footer for the given selected row and timer phase.
+pub fn compute_footer_data(
+    timer: &Timer,
+    config: &Config,
+    selected_index: Option<usize>,
+    phase: TimerPhase,
+) -> FooterData {
+    let (best_value, comparison_value) =
+        match footer_target(timer, selected_index, config.general.idle_display, phase) {
+            FooterTarget::Segment(index) => segment_values(timer, config, index),
+            FooterTarget::WholeRun => whole_run_values(timer, config),
+        };
+
+    let (timer_whole, timer_fraction) = split_timer_label(
+        &config.format.timer.format_timer(timer),
+        config.format.timer.decimal_separator(),
+    );
+
+    FooterData {
+        best_value,
+        comparison_label: format!("{}:", format_label(timer.current_comparison())),
+        comparison_value,
+        timer_whole,
+        timer_fraction,
+        timer_active: phase == TimerPhase::Running,
+        timer_class: if phase == TimerPhase::Running
+            && config.style.color_main_timer.unwrap_or(false)
+        {
+            live_timer_class(timer)
+        } else {
+            None
+        },
+        secondary_timer: config
+            .style
+            .show_secondary_timer
+            .unwrap_or(false)
+            .then(|| secondary_timer_text(timer, config)),
+        final_delta: if phase == TimerPhase::Ended {
+            final_delta(timer, config)
+        } else {
+            None
+        },
     }
+}
 
-    pub fn update(&mut self, timer: &Timer, config: &Config) {
-        for (kind, info) in ALL_ADDITIONAL_INFOS.iter().zip(&mut self.additional_info) {
-            info.update(timer, config);
-            let vis_cfg = &config.general.additional_info;
-            let visible = match kind {
-                AdditionalInfoKind::PrevSegmentDiff => vis_cfg.show_prev_segment_diff,
-                AdditionalInfoKind::PrevSegmentBest => vis_cfg.show_prev_segment_best,
-                AdditionalInfoKind::BestPossibleTime => vis_cfg.show_best_possible_time,
-                AdditionalInfoKind::PossibleTimeSave => vis_cfg.show_possible_time_save,
-                AdditionalInfoKind::CurrentPace => vis_cfg.show_current_pace,
-                AdditionalInfoKind::TotalPlaytime => vis_cfg.show_total_playtime,
-                AdditionalInfoKind::PbChance => vis_cfg.show_pb_chance,
-            };
-            info.container().set_visible(visible);
-        }
+/// Delta class of the current segment so far: ahead or behind its comparison split
+/// time, gaining or losing against its comparison segment. `None` without a comparison.
+fn live_timer_class(timer: &Timer) -> Option<&'static str> {
+    let index = timer.current_split_index()?;
+    let comparison = segment_comparison_time(timer.run().segments().get(index)?, timer);
+    if comparison == time::Duration::ZERO {
+        return None;
     }
+    let (previous_split, _, previous_comparison) =
+        previous_split_combined_gold_and_prev_comparison(timer, index);
+    let current = current_attempt_running_duration(timer);
+    let segment_duration = current.checked_sub(previous_split).unwrap_or_default();
+    // Every segment starts below its gold, so the running segment is never gold: passing
+    // its own duration as the gold keeps it green or red
+    let class = classify_split_label(
+        comparison
+            .checked_sub(previous_comparison)
+            .unwrap_or_default(),
+        segment_duration,
+        current.checked_sub(comparison).unwrap_or_default(),
+        segment_duration,
+        false,
+    );
+    (!class.is_empty() && class != "goldsplit").then_some(class)
+}
 
-    pub fn container(&self) -> GtkBox {
-        let container = GtkBox::builder()
-            .orientation(Vertical)
-            .halign(Align::Fill)
-            .hexpand(true)
-            .spacing(6)
-            .build();
+/// Final time against the comparison's final split time, `None` when the comparison has
+/// no final time.
+fn final_delta(timer: &Timer, config: &Config) -> Option<FinalDelta> {
+    let (comparison, derived) = display_time(
+        &timer
+            .run()
+            .segments()
+            .last()?
+            .comparison(timer.current_comparison()),
+        timer.current_timing_method(),
+        config.general.game_time_fallback,
+    );
+    let comparison = comparison?.to_duration();
+    let final_time = current_attempt_running_duration(timer);
+    let diff = final_time - comparison;
+
+    let signed = mark_derived(format_signed(diff, config), derived);
+    let text = if !is_new_personal_best(timer) {
+        format!("{signed} vs {}", format_label(timer.current_comparison()))
+    } else if timer.current_comparison() == personal_best::NAME {
+        format!("{signed}, new PB!")
+    } else {
+        format!(
+            "{signed} vs {}, new PB!",
+            format_label(timer.current_comparison())
+        )
+    };
+    // A whole run has no gold, passing the final time as one keeps it green or red
+    let class = classify_split_label(comparison, final_time, diff, final_time, false);
+    Some(FinalDelta { text, class })
+}
 
-        for info in &self.additional_info {
-            container.append(info.container());
-        }
+/// Best segment and per-segment comparison time of the segment at `selected_index`.
+fn segment_values(timer: &Timer, config: &Config, selected_index: usize) -> (String, String) {
+    let segments = timer.run().segments();
+    let Some(segment) = segments.get(selected_index) else {
+        return ("--".to_owned(), "--".to_owned());
+    };
+
+    let method = timer.current_timing_method();
+    // Previous segment's comparison time (under current timing method)
+    let (previous_comparison_time, previous_derived) = if selected_index > 0 {
+        display_duration(
+            &segments[selected_index - 1].comparison(timer.current_comparison()),
+            method,
+            config,
+        )
+    } else {
+        (time::Duration::ZERO, false)
+    };
+
+    // Build values
+    let best_value_text = format_display_time(
+        &config.format.comparison,
+        &segment.best_segment_time(),
+        method,
+        config,
+    );
+
+    let comparison_value_text = {
+        let (segment_comparison_time, derived) = display_duration(
+            &segment.comparison(timer.current_comparison()),
+            method,
+            config,
+        );
 
-        container
-    }
+        let per_segment_time = segment_comparison_time
+            .checked_sub(previous_comparison_time)
+            .unwrap_or_default()
+            .abs();
+        mark_derived(
+            config.format.comparison.format_duration(&per_segment_time),
+            derived || previous_derived,
+        )
+    };
+
+    (best_value_text, comparison_value_text)
+}
+
+/// Sum of best and final comparison time of the whole run.
+fn whole_run_values(timer: &Timer, config: &Config) -> (String, String) {
+    let method = timer.current_timing_method();
+    let format = &config.format.comparison;
+
+    let best_value_text = format.format_duration_opt(sum_of_best(timer.run(), method));
+    let comparison_value_text = timer.run().segments().last().map_or_else(
+        || format.format_time_span_opt(None),
+        |s| {
+            format_display_time(
+                format,
+                &s.comparison(timer.current_comparison()),
+                method,
+                config,
+            )
+        },
+    );
+
+    (best_value_text, comparison_value_text)
 }
 
 /// Left pane in the footer:
@@ -178,7 +336,7 @@ impl SegmentComparison {
         };
         this.primary_list_ref.set(Some(primary_list));
         this.last_list_ref.set(Some(last_list));
-        this.rebuild(timer, config);
+        this.update(timer, config);
         this
     }
     pub fn container(&self) -> &GtkBox {
@@ -186,72 +344,40 @@ impl SegmentComparison {
     }
 
     pub fn update(&mut self, timer: &Timer, config: &Config) {
-        self.rebuild(timer, config);
+        let selected = self.selected_row_index(timer.run().len());
+        self.render(&compute_footer_data(
+            timer,
+            config,
+            selected,
+            timer.current_phase(),
+        ));
     }
 
-    fn rebuild(&mut self, timer: &Timer, config: &Config) {
-        // Compute which segment to display
-        let segments = timer.run().segments();
-        let selected_index = if timer.current_phase().is_running() {
-            timer.current_split_index().unwrap_or(0)
-        } else {
-            let mut idx = self
-                .primary_list_ref
-                .upgrade()
-                .and_then(|l| l.selected_row())
-                .map(|row| row.index() as usize);
-            if idx.is_none()
-                && let Some(last_list) = self.last_list_ref.upgrade()
-                && last_list.selected_row().is_some()
-            {
-                idx = Some(segments.len().saturating_sub(1));
-            }
-            idx.unwrap_or(0)
+    fn selected_row_index(&self, segment_count: usize) -> Option<usize> {
+        let idx = self
+            .primary_list_ref
+            .upgrade()
+            .and_then(|l| l.selected_row())
+            .map(|row| row.index() as usize);
+        if idx.is_none()
+            && let Some(last_list) = self.last_list_ref.upgrade()
+            && last_list.selected_row().is_some()
+        {
+            return Some(segment_count.saturating_sub(1));
         }
-        .min(segments.len().saturating_sub(1));
-
-        let segment = &segments[selected_index];
-
-        // Previous segment's comparison time (under current timing method)
-        let previous_comparison_time = if selected_index > 0 {
-            segments[selected_index - 1]
-                .comparison_timing_method(timer.current_comparison(), timer.current_timing_method())
-                .unwrap_or_default()
-                .to_duration()
-        } else {
-            time::Duration::ZERO
-        };
-
-        // Build values
-        let best_value_text = config
-            .format
-            .comparison
-            .format_split_time(&segment.best_segment_time(), timer.current_timing_method());
-
-        let comparison_label_text = format!("{}:", format_label(timer.current_comparison()));
-
-        let comparison_value_text = {
-            let segment_comparison_time = segment
-                .comparison_timing_method(timer.current_comparison(), timer.current_timing_method())
-                .unwrap_or_default()
-                .to_duration();
-
-            let per_segment_time = segment_comparison_time
-                .checked_sub(previous_comparison_time)
-                .unwrap_or_default()
-                .abs();
-            config.format.comparison.format_duration(&per_segment_time)
-        };
+        idx
+    }
 
+    fn render(&mut self, data: &FooterData) {
         // Update stored labels in place
-        if self.best_value.label().as_str() != best_value_text {
-            self.best_value.set_label(&best_value_text);
+        if self.best_value.label().as_str() != data.best_value {
+            self.best_value.set_label(&data.best_value);
         }
-        if self.comparison_label.label().as_str() != comparison_label_text {
-            self.comparison_label.set_label(&comparison_label_text);
+        if self.comparison_label.label().as_str() != data.comparison_label {
+            self.comparison_label.set_label(&data.comparison_label);
         }
-        if self.comparison_value.label().as_str() != comparison_value_text {
-            self.comparison_value.set_label(&comparison_value_text);
+        if self.comparison_value.label().as_str() != data.comparison_value {
+            self.comparison_value.set_label(&data.comparison_value);
         }
     }
 
@@ -294,52 +420,83 @@ impl SegmentComparison {
     }
 }
 
-/// Right pane in the footer: the running timer display.
+/// What the footer's left pane describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FooterTarget {
+    Segment(usize),
+    WholeRun,
+}
+
+/// Picks the segment shown in the footer. A running attempt always shows its current
+/// segment; otherwise the selected row wins, and with nothing selected while idle the
+/// `IdleDisplay` option decides between the first segment and the whole run.
+fn footer_target(
+    timer: &Timer,
+    selected: Option<usize>,
+    idle: IdleDisplay,
+    phase: TimerPhase,
+) -> FooterTarget {
+    let last = timer.run().len().saturating_sub(1);
+
+    if phase.is_running() {
+        return FooterTarget::Segment(timer.current_split_index().unwrap_or(0).min(last));
+    }
+    match (selected, idle) {
+        (Some(index), _) => FooterTarget::Segment(index.min(last)),
+        (None, IdleDisplay::WholeRun) if phase.is_not_running() => FooterTarget::WholeRun,
+        (None, _) => FooterTarget::Segment(0),
+    }
+}
+
+/// Right pane in the footer: the running timer display, optionally followed by a
+/// smaller timer for the other timing method.
 pub struct RunningTimer {
     wrapper: GtkBox,
     timer_box: GtkBox,
     hms_label: Label,
     ms_label: Label,
+    secondary_label: Label,
+    final_delta_label: Label,
 }
 
 impl RunningTimer {
     pub fn new(timer: &Timer, config: &Config) -> Self {
         let wrapper = GtkBox::builder()
-            .orientation(Horizontal)
+            .orientation(Vertical)
             .halign(Align::End)
             .build();
 
         let timer_box = GtkBox::new(Horizontal, 0);
         timer_box.add_css_class("timer");
-        if timer.current_phase() == TimerPhase::Running {
-            timer_box.add_css_class("active-timer");
-        } else {
-            timer_box.add_css_class("inactive-timer");
-        }
-
-        let formatted = config.format.timer.format_timer(timer);
-        let (left, right) = if let Some((l, r)) = formatted.rsplit_once('.') {
-            (format!("{l}."), r.to_owned())
-        } else {
-            (formatted.clone(), String::new())
-        };
 
-        let hms_label = Label::builder().label(left).build();
+        let hms_label = Label::builder().build();
         hms_label.add_css_class("bigtimer");
 
-        let ms_label = Label::builder().label(right).margin_top(14).build();
+        let ms_label = Label::builder().margin_top(14).build();
         ms_label.add_css_class("smalltimer");
 
         timer_box.append(&hms_label);
         timer_box.append(&ms_label);
         wrapper.append(&timer_box);
 
-        Self {
+        let secondary_label = Label::builder().halign(Align::End).build();
+        secondary_label.add_css_class("timer");
+        secondary_label.add_css_class("secondary-timer");
+        wrapper.append(&secondary_label);
+
+        let final_delta_label = Label::builder().halign(Align::End).visible(false).build();
+        wrapper.append(&final_delta_label);
+
+        let mut this = Self {
             wrapper,
             timer_box,
             hms_label,
             ms_label,
-        }
+            secondary_label,
+            final_delta_label,
+        };
+        this.update(timer, config);
+        this
     }
 
     pub fn container(&self) -> &GtkBox {
@@ -347,32 +504,81 @@ impl RunningTimer {
     }
 
     pub fn update(&mut self, timer: &Timer, config: &Config) {
-        self.rebuild(timer, config);
+        self.render(&compute_footer_data(
+            timer,
+            config,
+            None,
+            timer.current_phase(),
+        ));
     }
 
-    fn rebuild(&mut self, timer: &Timer, config: &Config) {
-        self.timer_box.set_css_classes(match timer.current_phase() {
-            TimerPhase::Running => &["timer", "active-timer"],
-            _ => &["timer", "inactive-timer"],
-        });
+    fn render(&mut self, data: &FooterData) {
+        match (data.timer_active, data.timer_class) {
+            (true, Some(class)) => self.timer_box.set_css_classes(&["timer", class]),
+            (true, None) => self.timer_box.set_css_classes(&["timer", "active-timer"]),
+            (false, _) => self.timer_box.set_css_classes(&["timer", "inactive-timer"]),
+        }
 
         // Update labels only if changed
-        let formatted = config.format.timer.format_timer(timer);
-        let (left, right) = if let Some((l, r)) = formatted.rsplit_once('.') {
-            (format!("{l}."), r.to_owned())
-        } else {
-            (formatted.clone(), String::new())
-        };
+        if self.hms_label.label().as_str() != data.timer_whole {
+            self.hms_label.set_label(&data.timer_whole);
+        }
+        if self.ms_label.label().as_str() != data.timer_fraction {
+            self.ms_label.set_label(&data.timer_fraction);
+        }
 
-        if self.hms_label.label().as_str() != left {
-            self.hms_label.set_label(&left);
+        self.secondary_label
+            .set_visible(data.secondary_timer.is_some());
+        if let Some(text) = &data.secondary_timer
+            && self.secondary_label.label().as_str() != text
+        {
+            self.secondary_label.set_label(text);
         }
-        if self.ms_label.label().as_str() != right {
-            self.ms_label.set_label(&right);
+
+        // Gone again as soon as the run is reset
+        self.final_delta_label
+            .set_visible(data.final_delta.is_some());
+        if let Some(delta) = &data.final_delta {
+            if self.final_delta_label.label().as_str() != delta.text {
+                self.final_delta_label.set_label(&delta.text);
+            }
+            self.final_delta_label
+                .set_css_classes(&["timer", "final-delta", delta.class]);
         }
     }
 }
 
+/// The timing method the secondary timer shows: whichever one isn't current.
+fn secondary_timing_method(current: TimingMethod) -> TimingMethod {
+    match current {
+        TimingMethod::RealTime => TimingMethod::GameTime,
+        TimingMethod::GameTime => TimingMethod::RealTime,
+    }
+}
+
+/// Secondary timer text, prefixed with its timing method, e.g. "GT 1:23.45".
+fn secondary_timer_text(timer: &Timer, config: &Config) -> String {
+    let method = secondary_timing_method(timer.current_timing_method());
+    let prefix = match method {
+        TimingMethod::RealTime => "RT",
+        TimingMethod::GameTime => "GT",
+    };
+    format!(
+        "{prefix} {}",
+        config.format.timer.format_timer_with_method(timer, method)
+    )
+}
+
+/// Splits a formatted timer into the big (whole part, separator included) and
+/// small (fractional digits) labels.
+fn split_timer_label(formatted: &str, separator: char) -> (String, String) {
+    if let Some((l, r)) = formatted.rsplit_once(separator) {
+        (format!("{l}{separator}"), r.to_owned())
+    } else {
+        (formatted.to_owned(), String::new())
+    }
+}
+
 #[cfg(test)]
 mod footer_ui_tests {
     use super::*;
@@ -389,6 +595,103 @@ mod footer_ui_tests {
         });
     }
 
+    fn run_with_pb() -> livesplit_core::Run {
+        let rt = |secs: f64| {
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(secs)))
+        };
+        let mut run = livesplit_core::Run::new();
+        let mut s0 = livesplit_core::Segment::new("S0");
+        s0.set_personal_best_split_time(rt(10.0));
+        s0.set_best_segment_time(rt(9.0));
+        let mut s1 = livesplit_core::Segment::new("S1");
+        s1.set_personal_best_split_time(rt(30.0));
+        s1.set_best_segment_time(rt(18.0));
+        run.push_segment(s0);
+        run.push_segment(s1);
+        run
+    }
+
+    #[test]
+    fn footer_target_idle_follows_option() {
+        let timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+
+        assert_eq!(
+            footer_target(
+                &timer,
+                None,
+                IdleDisplay::FirstSegment,
+                timer.current_phase()
+            ),
+            FooterTarget::Segment(0)
+        );
+        assert_eq!(
+            footer_target(&timer, None, IdleDisplay::WholeRun, timer.current_phase()),
+            FooterTarget::WholeRun
+        );
+        // A selected row always wins
+        for idle in [IdleDisplay::FirstSegment, IdleDisplay::WholeRun] {
+            assert_eq!(
+                footer_target(&timer, Some(1), idle, timer.current_phase()),
+                FooterTarget::Segment(1)
+            );
+            assert_eq!(
+                footer_target(&timer, Some(7), idle, timer.current_phase()),
+                FooterTarget::Segment(1)
+            );
+        }
+    }
+
+    #[test]
+    fn footer_target_running_uses_current_segment() {
+        let mut timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        timer.start();
+        timer.split();
+
+        for idle in [IdleDisplay::FirstSegment, IdleDisplay::WholeRun] {
+            assert_eq!(
+                footer_target(&timer, None, idle, timer.current_phase()),
+                FooterTarget::Segment(1)
+            );
+            assert_eq!(
+                footer_target(&timer, Some(0), idle, timer.current_phase()),
+                FooterTarget::Segment(1)
+            );
+        }
+    }
+
+    #[gtk4::test]
+    fn idle_footer_renders_first_segment_or_whole_run() {
+        gtk_test_init();
+
+        let timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        let primary = ListBox::new();
+        let last = ListBox::new();
+        let mut config = Config::default();
+        let format = config.format.comparison.clone();
+
+        let first = SegmentComparison::new(&timer, &config, &primary, &last);
+        assert_eq!(
+            first.best_value.label().as_str(),
+            format.format_duration(&time::Duration::seconds(9))
+        );
+        assert_eq!(
+            first.comparison_value.label().as_str(),
+            format.format_duration(&time::Duration::seconds(10))
+        );
+
+        config.general.idle_display = IdleDisplay::WholeRun;
+        let whole = SegmentComparison::new(&timer, &config, &primary, &last);
+        assert_eq!(
+            whole.best_value.label().as_str(),
+            format.format_duration(&time::Duration::seconds(27))
+        );
+        assert_eq!(
+            whole.comparison_value.label().as_str(),
+            format.format_duration(&time::Duration::seconds(30))
+        );
+    }
+
     #[gtk4::test]
     fn running_timer_negative_offset_displays_split_labels() {
         gtk_test_init();
@@ -513,6 +816,59 @@ mod footer_ui_tests {
         );
     }
 
+    #[gtk4::test]
+    fn secondary_timer_is_hidden_by_default_and_shows_other_method() {
+        gtk_test_init();
+
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("Split 1"));
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(10.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(TimingMethod::RealTime);
+        timer.set_loading_times(livesplit_core::TimeSpan::from_seconds(2.0));
+        let mut config = Config::default();
+
+        let mut rt = RunningTimer::new(&timer, &config);
+        assert!(!rt.secondary_label.is_visible());
+
+        config.style.show_secondary_timer = Some(true);
+        rt.update(&timer, &config);
+        assert!(rt.secondary_label.is_visible());
+        assert!(rt.secondary_label.has_css_class("secondary-timer"));
+        assert_eq!(
+            rt.secondary_label.label().as_str(),
+            format!(
+                "GT {}",
+                config
+                    .format
+                    .timer
+                    .format_timer_with_method(&timer, TimingMethod::GameTime)
+            )
+        );
+        // The main timer keeps showing real time
+        assert_eq!(rt.hms_label.label().as_str(), "10.");
+
+        timer.set_current_timing_method(TimingMethod::GameTime);
+        rt.update(&timer, &config);
+        assert!(rt.secondary_label.label().starts_with("RT "));
+    }
+
+    #[test]
+    fn split_timer_label_uses_configured_separator() {
+        assert_eq!(
+            split_timer_label("1:02.34", '.'),
+            ("1:02.".to_owned(), "34".to_owned())
+        );
+        assert_eq!(
+            split_timer_label("1:02,34", ','),
+            ("1:02,".to_owned(), "34".to_owned())
+        );
+        assert_eq!(
+            split_timer_label("1:02", ','),
+            ("1:02".to_owned(), String::new())
+        );
+    }
+
     #[gtk4::test]
     fn segment_comparison_structure_and_texts() {
         gtk_test_init();
@@ -583,4 +939,208 @@ mod footer_ui_tests {
         // Ensure update works without panics and keeps structure
         sc.update(&timer, &config);
     }
+
+    #[gtk4::test]
+    fn average_segments_comparison_label_and_value() {
+        gtk_test_init();
+
+        let time_rt = |seconds: f64| {
+            livesplit_core::Time::new()
+                .with_real_time(Some(livesplit_core::TimeSpan::from_seconds(seconds)))
+        };
+        let mut run = livesplit_core::Run::new();
+        let mut segment = livesplit_core::Segment::new("Split 1");
+        segment.segment_history_mut().insert(1, time_rt(10.0));
+        segment.segment_history_mut().insert(2, time_rt(20.0));
+        run.push_segment(segment);
+        run.add_attempt_with_index(time_rt(10.0), 1, None, None, None);
+        run.add_attempt_with_index(time_rt(20.0), 2, None, None, None);
+        crate::utils::comparisons::ensure_average_comparison(&mut run);
+
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let mut config = Config::default();
+        config.general.comparison =
+            Some(livesplit_core::comparison::average_segments::NAME.to_owned());
+        config.configure_timer(&mut timer);
+
+        let list = ListBox::new();
+        let last_list = ListBox::new();
+        let sc = SegmentComparison::new(&timer, &config, &list, &last_list);
+
+        let vbox: GtkBox = sc.container().first_child().unwrap().downcast().unwrap();
+        let comparison_box: GtkBox = vbox
+            .first_child()
+            .and_then(|best| best.next_sibling())
+            .unwrap()
+            .downcast()
+            .unwrap();
+        let comp_label: Label = comparison_box.first_child().unwrap().downcast().unwrap();
+        let comp_value: Label = comp_label.next_sibling().unwrap().downcast().unwrap();
+
+        assert_eq!(comp_label.label().as_str(), "Average:");
+        // Populated from the segment history
+        assert_ne!(comp_value.label().as_str(), "--");
+    }
+
+    #[gtk4::test]
+    fn compact_footer_only_shows_running_timer() {
+        gtk_test_init();
+
+        let timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        let primary = ListBox::new();
+        let last = ListBox::new();
+        let mut config = Config::default();
+        config.style.compact = Some(true);
+
+        let mut footer = TimerFooter::new(&timer, &config, &primary, &last);
+        footer.refresh(&timer, &config);
+        assert!(!footer.segment_comparison.container().is_visible());
+        assert!(footer.running_timer.container().is_visible());
+
+        config.style.compact = Some(false);
+        footer.refresh(&timer, &config);
+        assert!(footer.segment_comparison.container().is_visible());
+    }
+
+    #[test]
+    fn footer_data_for_selected_indices() {
+        let timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        let config = Config::default();
+        let format = &config.format.comparison;
+
+        let none = compute_footer_data(&timer, &config, None, TimerPhase::NotRunning);
+        assert_eq!(
+            none.best_value,
+            format.format_duration(&time::Duration::seconds(9))
+        );
+        assert_eq!(
+            none.comparison_value,
+            format.format_duration(&time::Duration::seconds(10))
+        );
+        assert_eq!(none.comparison_label, "PB:");
+
+        // Second segment: comparison minus the previous split
+        let second = compute_footer_data(&timer, &config, Some(1), TimerPhase::NotRunning);
+        assert_eq!(
+            second.best_value,
+            format.format_duration(&time::Duration::seconds(18))
+        );
+        assert_eq!(
+            second.comparison_value,
+            format.format_duration(&time::Duration::seconds(20))
+        );
+
+        // Out of range clamps to the last segment
+        let clamped = compute_footer_data(&timer, &config, Some(7), TimerPhase::NotRunning);
+        assert_eq!(clamped, second);
+    }
+
+    #[test]
+    fn footer_data_follows_phase() {
+        let mut timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        let mut config = Config::default();
+        config.general.idle_display = IdleDisplay::WholeRun;
+        let format = config.format.comparison.clone();
+
+        let idle = compute_footer_data(&timer, &config, None, TimerPhase::NotRunning);
+        assert_eq!(
+            idle.best_value,
+            format.format_duration(&time::Duration::seconds(27))
+        );
+        assert!(!idle.timer_active);
+        assert_eq!(idle.secondary_timer, None);
+
+        timer.start();
+        timer.split();
+        let running = compute_footer_data(&timer, &config, Some(0), TimerPhase::Running);
+        // The current segment wins over the selection
+        assert_eq!(
+            running.best_value,
+            format.format_duration(&time::Duration::seconds(18))
+        );
+        assert!(running.timer_active);
+
+        let paused = compute_footer_data(&timer, &config, Some(0), TimerPhase::Paused);
+        assert!(!paused.timer_active);
+
+        config.style.show_secondary_timer = Some(true);
+        let with_secondary = compute_footer_data(&timer, &config, None, TimerPhase::Running);
+        assert!(with_secondary.secondary_timer.is_some());
+        assert_eq!(with_secondary.final_delta, None);
+    }
+
+    #[gtk4::test]
+    fn running_timer_takes_the_live_delta_color() {
+        gtk_test_init();
+
+        // Right after the start, the 5 second offset is ahead of the 10 second PB split
+        let mut run = run_with_pb();
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(5.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        timer.set_current_timing_method(TimingMethod::RealTime);
+        let mut config = Config::default();
+        let mut rt = RunningTimer::new(&timer, &config);
+        timer.start();
+
+        let plain = compute_footer_data(&timer, &config, None, TimerPhase::Running);
+        assert_eq!(plain.timer_class, None);
+
+        config.style.color_main_timer = Some(true);
+        let ahead = compute_footer_data(&timer, &config, None, TimerPhase::Running);
+        assert_eq!(ahead.timer_class, Some("greensplit"));
+        rt.update(&timer, &config);
+        assert!(rt.timer_box.has_css_class("greensplit"));
+        assert!(!rt.timer_box.has_css_class("active-timer"));
+
+        // Neutral again once the timer stops
+        timer.pause();
+        rt.update(&timer, &config);
+        assert!(rt.timer_box.has_css_class("inactive-timer"));
+        assert!(!rt.timer_box.has_css_class("greensplit"));
+    }
+
+    #[gtk4::test]
+    fn final_delta_shows_until_reset() {
+        gtk_test_init();
+
+        // Finishing right away with a 40 second offset is about 10 seconds behind the PB
+        let mut run = run_with_pb();
+        run.set_offset(livesplit_core::TimeSpan::from_seconds(40.0));
+        let mut timer = livesplit_core::Timer::new(run).expect("timer");
+        let config = Config::default();
+        let mut rt = RunningTimer::new(&timer, &config);
+        assert!(!rt.final_delta_label.is_visible());
+
+        timer.start();
+        timer.split();
+        timer.split();
+        let ended = compute_footer_data(&timer, &config, None, TimerPhase::Ended);
+        let delta = ended.final_delta.expect("final delta");
+        assert!(delta.text.starts_with('+'), "{}", delta.text);
+        assert!(delta.text.ends_with(" vs PB"), "{}", delta.text);
+        assert_eq!(delta.class, "redsplit");
+
+        rt.update(&timer, &config);
+        assert!(rt.final_delta_label.is_visible());
+        assert!(rt.final_delta_label.has_css_class("redsplit"));
+
+        timer.reset(false);
+        rt.update(&timer, &config);
+        assert!(!rt.final_delta_label.is_visible());
+    }
+
+    #[test]
+    fn faster_final_time_is_a_new_pb() {
+        let mut timer = livesplit_core::Timer::new(run_with_pb()).expect("timer");
+        timer.start();
+        timer.split();
+        timer.split();
+
+        let delta = compute_footer_data(&timer, &Config::default(), None, TimerPhase::Ended)
+            .final_delta
+            .expect("final delta");
+        assert!(delta.text.starts_with('-'), "{}", delta.text);
+        assert!(delta.text.ends_with(", new PB!"), "{}", delta.text);
+        assert_eq!(delta.class, "greensplit");
+    }
 }
@@ -0,0 +1,156 @@
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use gtk4::gdk::Texture;
+use tracing::warn;
+
+use crate::config::{DEFAULT_ICON_CACHE_SIZE, DEFAULT_ICON_SIZE, MAX_ICON_SIZE, MIN_ICON_SIZE};
+use crate::utils::lru::LruCache;
+
+/// The configured `style.icon_size`, kept within `MIN_ICON_SIZE..=MAX_ICON_SIZE`.
+pub fn icon_size(configured: Option<i32>) -> i32 {
+    configured
+        .unwrap_or(DEFAULT_ICON_SIZE)
+        .clamp(MIN_ICON_SIZE, MAX_ICON_SIZE)
+}
+
+thread_local! {
+    // Decoded segment icons, keyed by a hash of their raw bytes.
+    static ICON_CACHE: RefCell<LruCache<u64, Texture>> =
+        RefCell::new(LruCache::new(DEFAULT_ICON_CACHE_SIZE));
+}
+
+/// Hash identifying an icon by its raw bytes.
+pub fn icon_hash(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the texture for the given icon bytes, decoding them only on a cache miss.
+/// `None` (with a warning) when the bytes are not an image GDK can load.
+///
+/// `capacity` bounds how many textures are kept alive; the least recently used
+/// ones are dropped first.
+pub fn segment_icon_texture(data: &[u8], capacity: usize) -> Option<Texture> {
+    let key = icon_hash(data);
+    ICON_CACHE.with_borrow_mut(|cache| {
+        if cache.capacity() != capacity {
+            cache.set_capacity(capacity);
+        }
+        if let Some(texture) = cache.get(&key) {
+            return Some(texture);
+        }
+        let texture = decode_icon(data)?;
+        cache.insert(key, texture.clone());
+        Some(texture)
+    })
+}
+
+/// Drops the decoded texture of an icon that was replaced, instead of waiting for it to
+/// become the least recently used one.
+pub fn forget_segment_icon(data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    let key = icon_hash(data);
+    ICON_CACHE.with_borrow_mut(|cache| {
+        cache.remove(&key);
+    });
+}
+
+/// Decodes PNG, JPEG or any other format GDK supports.
+fn decode_icon(data: &[u8]) -> Option<Texture> {
+    let error = match Texture::from_bytes(&glib::Bytes::from(data)) {
+        Ok(texture) => return Some(texture),
+        Err(error) => error,
+    };
+    // Some splits files store PNG icons with the final byte of the IEND CRC cut off
+    // (it happens upstream, we pass the bytes through untouched). Only that exact
+    // damage is repaired; anything else is skipped.
+    if let Some(repaired) = repair_truncated_png(data)
+        && let Ok(texture) = Texture::from_bytes(&glib::Bytes::from_owned(repaired))
+    {
+        return Some(texture);
+    }
+    warn!("Skipping segment icon that could not be decoded: {error}");
+    None
+}
+
+/// PNG data whose trailing IEND chunk lacks the last CRC byte, with that byte restored.
+fn repair_truncated_png(data: &[u8]) -> Option<Vec<u8>> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    // IEND chunk type followed by its (constant) CRC AE 42 60 82, minus the last byte
+    const TRUNCATED_IEND: &[u8] = b"IEND\xAE\x42\x60";
+    if !data.starts_with(PNG_SIGNATURE) || !data.ends_with(TRUNCATED_IEND) {
+        return None;
+    }
+    let mut repaired = data.to_vec();
+    repaired.push(0x82);
+    Some(repaired)
+}
+
+#[cfg(test)]
+mod icon_decoding_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    #[test]
+    fn icon_sizes_stay_in_range() {
+        assert_eq!(icon_size(None), DEFAULT_ICON_SIZE);
+        assert_eq!(icon_size(Some(32)), 32);
+        assert_eq!(icon_size(Some(0)), MIN_ICON_SIZE);
+        assert_eq!(icon_size(Some(512)), MAX_ICON_SIZE);
+    }
+
+    #[gtk4::test]
+    fn corrupt_bytes_are_skipped_without_panicking() {
+        gtk_test_init();
+
+        assert!(segment_icon_texture(b"definitely not an image", 4).is_none());
+        assert!(segment_icon_texture(b"\x89PNG\r\n\x1a\ngarbage", 4).is_none());
+        assert!(segment_icon_texture(&[0xFF, 0xD8, 0xFF, 0x00], 4).is_none());
+    }
+
+    fn one_pixel_png() -> glib::Bytes {
+        let pixel = glib::Bytes::from_static(&[255, 0, 0, 255]);
+        gtk4::gdk::MemoryTexture::new(1, 1, gtk4::gdk::MemoryFormat::R8g8b8a8, &pixel, 4)
+            .save_to_png_bytes()
+    }
+
+    #[gtk4::test]
+    fn icons_are_decoded_once_until_forgotten() {
+        gtk_test_init();
+        let png = one_pixel_png();
+
+        // Rebuilding rows asks for the same icon again and gets the same texture back
+        let first = segment_icon_texture(&png, 4).expect("decoded");
+        let again = segment_icon_texture(&png, 4).expect("cached");
+        assert_eq!(first, again);
+
+        forget_segment_icon(&png);
+        let redecoded = segment_icon_texture(&png, 4).expect("decoded");
+        assert_ne!(first, redecoded);
+    }
+
+    #[test]
+    fn only_truncated_png_iend_is_repaired() {
+        let truncated = b"\x89PNG\r\n\x1a\n...IEND\xAE\x42\x60";
+        let repaired = repair_truncated_png(truncated).expect("repaired");
+        assert_eq!(repaired.len(), truncated.len() + 1);
+        assert!(repaired.ends_with(b"IEND\xAE\x42\x60\x82"));
+
+        // Intact PNGs and other formats are left alone
+        assert!(repair_truncated_png(&repaired).is_none());
+        assert!(repair_truncated_png(&[0xFF, 0xD8, 0xFF, 0x60]).is_none());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::config::{AdditionalInfoVisibility, Config, InfoLayout};
+use crate::ui::info::{
+    ALL_ADDITIONAL_INFOS, AdditionalInfo, AdditionalInfoKind, AttemptCountInfo, AverageSegmentInfo,
+    BestPossibleTimeInfo, CurrentPaceInfo, GoalTimeInfo, PbChanceInfo, PossibleTimeSaveInfo,
+    PrevSegmentBestInfo, PrevSegmentDiffInfo, SegmentProgressInfo, SumOfBestInfo,
+    TotalPlaytimeInfo,
+};
+
+use adw::prelude::*;
+use gtk4::{
+    Align, Box as GtkBox, FlowBox, FlowBoxChild, Orientation::Horizontal, Orientation::Vertical,
+    SelectionMode, Widget,
+};
+
+use livesplit_core::Timer;
+
+/// `InfoPanel`
+/// Renders the additional info rows (previous segment, best possible time, PB chance, ...)
+/// between the segment list and the footer.
+///
+/// Every info is built once; the ones disabled in `config.general.additional_info` are
+/// only hidden, so toggling them in the preferences applies on the next refresh.
+pub struct InfoPanel {
+    container: GtkBox,
+    infos: Vec<Box<dyn AdditionalInfo>>,
+}
+
+impl InfoPanel {
+    pub fn new(timer: &Timer, config: &Config) -> Self {
+        let container = GtkBox::builder()
+            .orientation(Vertical)
+            .halign(Align::Fill)
+            .hexpand(true)
+            .build();
+
+        let infos: Vec<Box<dyn AdditionalInfo>> = vec![
+            Box::new(PrevSegmentDiffInfo::new(timer, config)),
+            Box::new(PrevSegmentBestInfo::new(timer, config)),
+            Box::new(BestPossibleTimeInfo::new(timer, config)),
+            Box::new(PossibleTimeSaveInfo::new(timer, config)),
+            Box::new(CurrentPaceInfo::new(timer, config)),
+            Box::new(TotalPlaytimeInfo::new(timer, config)),
+            Box::new(PbChanceInfo::new(timer, config)),
+            Box::new(SumOfBestInfo::new(timer, config)),
+            Box::new(SegmentProgressInfo::new(timer, config)),
+            Box::new(AttemptCountInfo::new(timer, config)),
+            Box::new(AverageSegmentInfo::new(timer, config)),
+            Box::new(GoalTimeInfo::new(timer, config)),
+        ];
+
+        let rows = build_info_container(config.style.info_layout.unwrap_or_default());
+        for info in &infos {
+            if let Some(grid) = rows.downcast_ref::<FlowBox>() {
+                grid.append(info.container());
+            } else if let Some(list) = rows.downcast_ref::<GtkBox>() {
+                list.append(info.container());
+            }
+        }
+        container.append(&rows);
+
+        // Initialize visibility based on config at creation time.
+        let mut this = Self { container, infos };
+        this.refresh(timer, config);
+        this
+    }
+
+    /// Access the GTK container to attach this component in the parent UI.
+    pub fn container(&self) -> &GtkBox {
+        &self.container
+    }
+
+    /// Update every info and show only the enabled ones.
+    pub fn refresh(&mut self, timer: &Timer, config: &Config) {
+        let vis_cfg = &config.general.additional_info;
+        let mut any_visible = false;
+
+        for (kind, info) in ALL_ADDITIONAL_INFOS.iter().zip(&mut self.infos) {
+            let visible = is_enabled(vis_cfg, kind);
+            any_visible |= visible;
+
+            if visible {
+                info.update(timer, config);
+            }
+            info.container().set_visible(visible);
+            // In the grid layout the row is wrapped, hide the wrapper too so it leaves no gap
+            if let Some(child) = info.container().parent().and_downcast::<FlowBoxChild>() {
+                child.set_visible(visible);
+            }
+        }
+
+        self.container.set_visible(any_visible);
+    }
+}
+
+fn is_enabled(vis_cfg: &AdditionalInfoVisibility, kind: &AdditionalInfoKind) -> bool {
+    match kind {
+        AdditionalInfoKind::PrevSegmentDiff => vis_cfg.show_prev_segment_diff,
+        AdditionalInfoKind::PrevSegmentBest => vis_cfg.show_prev_segment_best,
+        AdditionalInfoKind::BestPossibleTime => vis_cfg.show_best_possible_time,
+        AdditionalInfoKind::PossibleTimeSave => vis_cfg.show_possible_time_save,
+        AdditionalInfoKind::CurrentPace => vis_cfg.show_current_pace,
+        AdditionalInfoKind::TotalPlaytime => vis_cfg.show_total_playtime,
+        AdditionalInfoKind::PbChance => vis_cfg.show_pb_chance,
+        AdditionalInfoKind::SumOfBest => vis_cfg.show_sum_of_best,
+        AdditionalInfoKind::SegmentProgress => vis_cfg.show_segment_progress,
+        AdditionalInfoKind::AttemptCount => vis_cfg.show_attempt_count,
+        AdditionalInfoKind::AverageSegment => vis_cfg.show_average_segment,
+        AdditionalInfoKind::GoalTime => vis_cfg.show_goal_time,
+    }
+}
+
+/// Empty container holding the additional info rows for the given layout.
+fn build_info_container(layout: InfoLayout) -> Widget {
+    match layout {
+        InfoLayout::Vertical => GtkBox::builder()
+            .orientation(Vertical)
+            .halign(Align::Fill)
+            .hexpand(true)
+            .spacing(6)
+            .build()
+            .upcast(),
+        InfoLayout::Grid => FlowBox::builder()
+            .orientation(Horizontal)
+            .halign(Align::Fill)
+            .hexpand(true)
+            .homogeneous(true)
+            .min_children_per_line(2)
+            .max_children_per_line(4)
+            .column_spacing(12)
+            .row_spacing(6)
+            .selection_mode(SelectionMode::None)
+            .build()
+            .upcast(),
+    }
+}
+
+#[cfg(test)]
+mod info_panel_tests {
+    use super::*;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+            let _ = adw::init();
+        });
+    }
+
+    fn make_timer() -> Timer {
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("Split 1"));
+        Timer::new(run).expect("timer")
+    }
+
+    /// The widgets holding the info rows, in display order.
+    fn rows(panel: &InfoPanel) -> Vec<Widget> {
+        let inner = panel.container().first_child().expect("rows container");
+        let mut rows = Vec::new();
+        let mut child = inner.first_child();
+        while let Some(w) = child {
+            child = w.next_sibling();
+            rows.push(w);
+        }
+        rows
+    }
+
+    #[gtk4::test]
+    fn info_layout_picks_container_and_orientation() {
+        gtk_test_init();
+
+        let vertical = build_info_container(InfoLayout::Vertical);
+        let vertical: GtkBox = vertical.downcast().expect("GtkBox for vertical layout");
+        assert_eq!(vertical.orientation(), Vertical);
+
+        let grid = build_info_container(InfoLayout::Grid);
+        let grid: FlowBox = grid.downcast().expect("FlowBox for grid layout");
+        assert_eq!(grid.orientation(), Horizontal);
+    }
+
+    #[gtk4::test]
+    fn grid_layout_holds_every_info() {
+        gtk_test_init();
+
+        let mut config = Config::default();
+        config.style.info_layout = Some(InfoLayout::Grid);
+
+        let panel = InfoPanel::new(&make_timer(), &config);
+        assert_eq!(rows(&panel).len(), ALL_ADDITIONAL_INFOS.len());
+        assert!(rows(&panel)[0].is::<FlowBoxChild>());
+    }
+
+    #[gtk4::test]
+    fn toggling_visibility_applies_on_refresh() {
+        gtk_test_init();
+
+        let timer = make_timer();
+        let mut config = Config::default();
+        config.general.additional_info.show_pb_chance = false;
+
+        let mut panel = InfoPanel::new(&timer, &config);
+        let pb_chance = ALL_ADDITIONAL_INFOS
+            .iter()
+            .position(|k| matches!(k, AdditionalInfoKind::PbChance))
+            .expect("pb chance kind");
+        assert!(!rows(&panel)[pb_chance].is_visible());
+
+        config.general.additional_info.show_pb_chance = true;
+        panel.refresh(&timer, &config);
+        assert!(rows(&panel)[pb_chance].is_visible());
+        assert!(panel.container().is_visible());
+    }
+
+    #[gtk4::test]
+    fn panel_hidden_when_every_info_is_disabled() {
+        gtk_test_init();
+
+        let timer = make_timer();
+        let mut config = Config::default();
+        let vis = &mut config.general.additional_info;
+        vis.show_prev_segment_diff = false;
+        vis.show_prev_segment_best = false;
+        vis.show_best_possible_time = false;
+        vis.show_possible_time_save = false;
+        vis.show_current_pace = false;
+        vis.show_total_playtime = false;
+        vis.show_pb_chance = false;
+        vis.show_sum_of_best = false;
+        vis.show_segment_progress = false;
+        vis.show_attempt_count = false;
+        vis.show_average_segment = false;
+        vis.show_goal_time = false;
+
+        let panel = InfoPanel::new(&timer, &config);
+        assert!(!panel.container().is_visible());
+    }
+}
@@ -1,26 +1,76 @@
 pub mod body;
 pub mod footer;
 pub mod header;
+pub mod icons;
+pub mod info_panel;
+pub mod notes;
+pub mod progress;
+pub mod subsplits;
 
+use crate::config::{Config, InfoPosition, TimerOrientation};
+use crate::ui::sounds;
 use crate::ui::timer::body::TimerBody;
 use crate::ui::timer::footer::TimerFooter;
 use crate::ui::timer::header::TimerHeader;
+use crate::ui::timer::info_panel::InfoPanel;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use core::time::Duration;
+use std::time::{Instant, SystemTime};
 
-use adw::Clamp;
 use adw::prelude::*;
-use gtk4::{Align, Box as GtkBox, Orientation::Vertical};
+use adw::{Clamp, StatusPage};
+use gtk4::{
+    Align, Box as GtkBox,
+    Orientation::{Horizontal, Vertical},
+    Stack, Widget,
+};
 
-use crate::context::TuxSplitContext;
+use crate::context::{TuxSplitContext, suspend_gap};
+use livesplit_core::{Timer, TimerPhase, TimingMethod};
+
+/// Maximum width of the timer when its sections are stacked.
+const VERTICAL_MAX_WIDTH: i32 = 900;
+/// Maximum width of the timer when its sections are side by side.
+const HORIZONTAL_MAX_WIDTH: i32 = 1800;
+/// How often an unchanged timer is redrawn anyway, so config edits show up while idle.
+const IDLE_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Cheap summary of the timer state the widgets show. The refresh loop skips redrawing
+/// while it stays the same, which is the case whenever the timer isn't running.
+#[derive(Debug, Clone, PartialEq)]
+struct RefreshFingerprint {
+    phase: TimerPhase,
+    split_index: Option<usize>,
+    /// Current real and game time in milliseconds.
+    time_ms: [Option<i64>; 2],
+    timing_method: TimingMethod,
+    comparison: String,
+}
+
+impl RefreshFingerprint {
+    fn of(timer: &Timer) -> Self {
+        let time = timer.current_time();
+        Self {
+            phase: timer.current_phase(),
+            split_index: timer.current_split_index(),
+            time_ms: [time.real_time, time.game_time]
+                .map(|t| t.map(|t| t.total_milliseconds() as i64)),
+            timing_method: timer.current_timing_method(),
+            comparison: timer.current_comparison().to_owned(),
+        }
+    }
+}
 
 pub struct TuxSplitTimer {
     clamp: Clamp,
+    // The timer sections, or the prompt to load splits
+    stack: Stack,
     header: Rc<RefCell<TimerHeader>>,
     body: Rc<RefCell<TimerBody>>,
+    info_panel: Rc<RefCell<InfoPanel>>,
     footer: Rc<RefCell<TimerFooter>>,
     refresh_source: Option<glib::SourceId>,
 }
@@ -28,7 +78,7 @@ pub struct TuxSplitTimer {
 impl TuxSplitTimer {
     /// Create the timer widget (header/body/footer composed) but does NOT start refresh loop.
     pub fn new() -> Self {
-        let clamp = Clamp::builder().maximum_size(900).build();
+        let clamp = Clamp::builder().maximum_size(VERTICAL_MAX_WIDTH).build();
 
         let container = GtkBox::builder()
             .orientation(Vertical)
@@ -49,6 +99,8 @@ impl TuxSplitTimer {
 
         let cfg = ctx.config();
         let body = Rc::new(RefCell::new(TimerBody::new(&timer_read, &cfg)));
+        TimerBody::connect_search_changed(&body);
+        let info_panel = Rc::new(RefCell::new(InfoPanel::new(&timer_read, &cfg)));
         let footer = Rc::new(RefCell::new(TimerFooter::new(
             &timer_read,
             &cfg,
@@ -57,15 +109,48 @@ impl TuxSplitTimer {
         )));
         drop(timer_read);
 
+        header
+            .borrow()
+            .container()
+            .set_visible(!cfg.style.compact.unwrap_or(false));
         container.append(header.borrow().container());
         container.append(body.borrow().container());
+        container.append(info_panel.borrow().container());
         container.append(footer.borrow().container());
+        arrange_sections(
+            &container,
+            cfg.style.info_position.unwrap_or_default(),
+            [
+                header.borrow().container().upcast_ref(),
+                body.borrow().container().upcast_ref(),
+                info_panel.borrow().container().upcast_ref(),
+                footer.borrow().container().upcast_ref(),
+            ],
+        );
+        let orientation = cfg.style.orientation.unwrap_or_default();
+        apply_orientation(&clamp, &container, orientation);
+        body.borrow().set_orientation(orientation);
+        footer.borrow().set_orientation(orientation);
 
-        clamp.set_child(Some(&container));
+        let prompt = StatusPage::builder()
+            .icon_name("document-open-symbolic")
+            .title("No Splits Loaded")
+            .description("Load splits from the menu to start timing")
+            .build();
+        let stack = Stack::new();
+        stack.add_named(&container, Some(TIMER_PAGE));
+        stack.add_named(&prompt, Some(PROMPT_PAGE));
+        show_prompt(&stack, skips_refresh(&ctx.timer().read().unwrap(), &cfg));
+        clamp.set_child(Some(&stack));
 
         {
             // Connect global run-changed to force a rebuild of timer UI.
+            let clamp_binding = clamp.downgrade();
+            let container_binding = container.downgrade();
+            let stack_binding = stack.downgrade();
+            let header_binding = header.clone();
             let body_binding = body.clone();
+            let info_binding = info_panel.clone();
             let footer_binding = footer.clone();
             TuxSplitContext::get_instance().connect_local("run-changed", false, move |_| {
                 let ctx = TuxSplitContext::get_instance();
@@ -74,7 +159,36 @@ impl TuxSplitTimer {
                     shared.read().unwrap().clone()
                 };
                 let c = ctx.config();
+                if let Some(stack) = stack_binding.upgrade() {
+                    show_prompt(&stack, skips_refresh(&t, &c));
+                }
+                let compact = c.style.compact.unwrap_or(false);
+                header_binding.borrow().container().set_visible(!compact);
+                if !compact {
+                    header_binding.borrow_mut().refresh(&t);
+                }
+                if let Some(container) = container_binding.upgrade() {
+                    arrange_sections(
+                        &container,
+                        c.style.info_position.unwrap_or_default(),
+                        [
+                            header_binding.borrow().container().upcast_ref(),
+                            body_binding.borrow().container().upcast_ref(),
+                            info_binding.borrow().container().upcast_ref(),
+                            footer_binding.borrow().container().upcast_ref(),
+                        ],
+                    );
+                }
+                let orientation = c.style.orientation.unwrap_or_default();
+                if let Some(clamp) = clamp_binding.upgrade()
+                    && let Some(container) = container_binding.upgrade()
+                {
+                    apply_orientation(&clamp, &container, orientation);
+                }
+                body_binding.borrow().set_orientation(orientation);
+                footer_binding.borrow().set_orientation(orientation);
                 body_binding.borrow_mut().refresh(&t, &c, true);
+                info_binding.borrow_mut().refresh(&t, &c);
                 footer_binding.borrow_mut().refresh(&t, &c);
                 None
             });
@@ -82,8 +196,10 @@ impl TuxSplitTimer {
 
         Self {
             clamp,
+            stack,
             header,
             body,
+            info_panel,
             footer,
             refresh_source: None,
         }
@@ -98,20 +214,89 @@ impl TuxSplitTimer {
             return; // Already running
         }
 
+        let stack_binding = self.stack.downgrade();
         let header_binding = self.header.clone();
         let body_binding = self.body.clone();
+        let info_binding = self.info_panel.clone();
         let footer_binding = self.footer.clone();
+        let mut last_comparison = String::new();
+        let mut last_tick = SystemTime::now();
+        let (mut last_phase, mut last_split_index) = {
+            let shared = TuxSplitContext::get_instance().timer();
+            let timer = shared.read().unwrap();
+            (timer.current_phase(), timer.current_split_index())
+        };
+        let mut last_fingerprint: Option<RefreshFingerprint> = None;
+        let mut last_refresh = Instant::now();
 
         let source_id = glib::timeout_add_local(Duration::from_millis(16), move || {
             let ctx = TuxSplitContext::get_instance();
+
+            // The loop doesn't run while the machine sleeps, so a suspend shows up as a gap
+            let now = SystemTime::now();
+            if let Some(gap) = suspend_gap(last_tick, now) {
+                ctx.pause_after_suspend(gap);
+            }
+            last_tick = now;
+
+            // A phase change always alters the fingerprint, so it is never skipped
+            let fingerprint = RefreshFingerprint::of(&ctx.timer().read().unwrap());
+            if last_fingerprint.as_ref() == Some(&fingerprint)
+                && last_refresh.elapsed() < IDLE_REFRESH_INTERVAL
+            {
+                return glib::ControlFlow::Continue;
+            }
+            last_fingerprint = Some(fingerprint);
+            last_refresh = Instant::now();
+
+            // Nothing to draw behind the load prompt
+            let prompt = skips_refresh(&ctx.timer().read().unwrap(), &ctx.config());
+            if let Some(stack) = stack_binding.upgrade() {
+                show_prompt(&stack, prompt);
+            }
+            if prompt {
+                return glib::ControlFlow::Continue;
+            }
+
             let t = {
                 let shared = ctx.timer();
                 shared.read().unwrap().clone()
             };
 
+            // Splits, resets and finished runs all move the phase or the split index
+            if last_phase != t.current_phase() || last_split_index != t.current_split_index() {
+                // A primed timer hasn't started, so it plays what the integrations see
+                if let Some(previous) = ctx.observed_phase(last_phase, t.current_phase())
+                    && let Some(event) = sounds::sound_event(previous, last_split_index, &t)
+                {
+                    sounds::play(&ctx.config().style.sounds, event);
+                }
+                last_split_index = t.current_split_index();
+            }
+
+            // Every input path changes the phase through the shared timer, watch it here
+            if last_phase != t.current_phase() {
+                let previous = std::mem::replace(&mut last_phase, t.current_phase());
+                ctx.phase_changed(previous, &t);
+            }
+
+            // Hotkeys switch comparisons behind our back, so notify listeners from here
+            if last_comparison != t.current_comparison() {
+                let first_tick = last_comparison.is_empty();
+                t.current_comparison().clone_into(&mut last_comparison);
+                if !first_tick {
+                    ctx.remember_comparison(t.current_comparison());
+                    ctx.emit_comparison_changed();
+                }
+            }
+
             let c = ctx.config();
-            header_binding.borrow_mut().refresh(&t);
+            // The compact layout hides the header, nothing to update
+            if !c.style.compact.unwrap_or(false) {
+                header_binding.borrow_mut().refresh(&t);
+            }
             body_binding.borrow_mut().refresh(&t, &c, false);
+            info_binding.borrow_mut().refresh(&t, &c);
             footer_binding.borrow_mut().refresh(&t, &c);
 
             glib::ControlFlow::Continue
@@ -126,3 +311,193 @@ impl TuxSplitTimer {
         }
     }
 }
+
+/// Orders the header, body, info panel and footer (given in that order) inside the
+/// timer container according to the info panel position.
+fn arrange_sections(container: &GtkBox, position: InfoPosition, sections: [&Widget; 4]) {
+    let [header, body, info, footer] = sections;
+    let order = match position {
+        InfoPosition::AboveSplits => [header, info, body, footer],
+        InfoPosition::AboveFooter => [header, body, info, footer],
+        InfoPosition::BelowFooter => [header, body, footer, info],
+    };
+    let mut previous: Option<&Widget> = None;
+    for widget in order {
+        container.reorder_child_after(widget, previous);
+        previous = Some(widget);
+    }
+}
+
+const TIMER_PAGE: &str = "timer";
+const PROMPT_PAGE: &str = "prompt";
+
+/// Whether the refresh loop leaves the timer alone and the load prompt is shown: the
+/// placeholder run is loaded and `general.default_run_prompt` is on.
+fn skips_refresh(timer: &Timer, config: &Config) -> bool {
+    config.shows_default_run_prompt(timer.run())
+}
+
+fn show_prompt(stack: &Stack, prompt: bool) {
+    let page = if prompt { PROMPT_PAGE } else { TIMER_PAGE };
+    if stack.visible_child_name().as_deref() != Some(page) {
+        stack.set_visible_child_name(page);
+    }
+}
+
+/// Stacks the timer sections or places them side by side, widening the clamp for the latter.
+fn apply_orientation(clamp: &Clamp, container: &GtkBox, orientation: TimerOrientation) {
+    let (gtk_orientation, max_width) = match orientation {
+        TimerOrientation::Vertical => (Vertical, VERTICAL_MAX_WIDTH),
+        TimerOrientation::Horizontal => (Horizontal, HORIZONTAL_MAX_WIDTH),
+    };
+    container.set_orientation(gtk_orientation);
+    clamp.set_maximum_size(max_width);
+}
+
+#[cfg(test)]
+mod refresh_fingerprint_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment};
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        run.push_segment(Segment::new("B"));
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn idle_timer_keeps_its_fingerprint() {
+        let timer = timer();
+        assert_eq!(
+            RefreshFingerprint::of(&timer),
+            RefreshFingerprint::of(&timer)
+        );
+    }
+
+    #[test]
+    fn phase_split_and_comparison_changes_are_noticed() {
+        let mut timer = timer();
+        let idle = RefreshFingerprint::of(&timer);
+
+        timer.start();
+        timer.pause();
+        let paused = RefreshFingerprint::of(&timer);
+        assert_ne!(paused, idle);
+        // Paused time stands still
+        assert_eq!(RefreshFingerprint::of(&timer), paused);
+
+        timer.resume();
+        timer.split();
+        let split = RefreshFingerprint::of(&timer);
+        assert_eq!(split.split_index, Some(1));
+
+        timer.switch_to_next_comparison();
+        assert_ne!(RefreshFingerprint::of(&timer).comparison, split.comparison);
+    }
+}
+
+#[cfg(test)]
+mod default_run_prompt_tests {
+    use super::*;
+
+    #[test]
+    fn placeholder_run_skips_the_refresh_until_started() {
+        let mut config = Config::default();
+        let mut timer = Timer::new(config.parse_run_or_default()).expect("timer");
+        assert!(!skips_refresh(&timer, &config));
+
+        config.general.default_run_prompt = true;
+        assert!(skips_refresh(&timer, &config));
+
+        // Timing it anyway makes it a regular run
+        timer.start();
+        assert!(!skips_refresh(&timer, &config));
+    }
+
+    #[test]
+    fn loaded_runs_always_refresh() {
+        let mut config = Config::default();
+        config.general.default_run_prompt = true;
+        let mut run = livesplit_core::Run::new();
+        run.push_segment(livesplit_core::Segment::new("Time"));
+        let timer = Timer::new(run).expect("timer");
+        assert!(!skips_refresh(&timer, &config));
+    }
+}
+
+#[cfg(test)]
+mod arrange_sections_tests {
+    use super::*;
+    use gtk4::Label;
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    fn child_names(container: &GtkBox) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut child = container.first_child();
+        while let Some(widget) = child {
+            names.push(widget.widget_name().to_string());
+            child = widget.next_sibling();
+        }
+        names
+    }
+
+    #[gtk4::test]
+    fn info_panel_follows_configured_position() {
+        gtk_test_init();
+
+        let container = GtkBox::new(Vertical, 0);
+        let sections: Vec<Widget> = ["header", "body", "info", "footer"]
+            .into_iter()
+            .map(|name| {
+                let label = Label::new(None);
+                label.set_widget_name(name);
+                container.append(&label);
+                label.upcast()
+            })
+            .collect();
+        let sections = [&sections[0], &sections[1], &sections[2], &sections[3]];
+
+        arrange_sections(&container, InfoPosition::AboveSplits, sections);
+        assert_eq!(
+            child_names(&container),
+            ["header", "info", "body", "footer"]
+        );
+
+        arrange_sections(&container, InfoPosition::BelowFooter, sections);
+        assert_eq!(
+            child_names(&container),
+            ["header", "body", "footer", "info"]
+        );
+
+        arrange_sections(&container, InfoPosition::AboveFooter, sections);
+        assert_eq!(
+            child_names(&container),
+            ["header", "body", "info", "footer"]
+        );
+    }
+
+    #[gtk4::test]
+    fn orientation_switches_container_and_clamp() {
+        gtk_test_init();
+
+        let clamp = Clamp::builder().maximum_size(VERTICAL_MAX_WIDTH).build();
+        let container = GtkBox::new(Vertical, 0);
+
+        apply_orientation(&clamp, &container, TimerOrientation::Horizontal);
+        assert_eq!(container.orientation(), Horizontal);
+        assert_eq!(clamp.maximum_size(), HORIZONTAL_MAX_WIDTH);
+
+        apply_orientation(&clamp, &container, TimerOrientation::Vertical);
+        assert_eq!(container.orientation(), Vertical);
+        assert_eq!(clamp.maximum_size(), VERTICAL_MAX_WIDTH);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::utils::notes::segment_note;
+
+use gtk4::prelude::*;
+use gtk4::{Label, pango::WrapMode};
+
+use livesplit_core::Timer;
+
+/// Note of the running segment below the splits, hidden when it has none.
+pub struct NoteDisplay {
+    label: Label,
+}
+
+impl NoteDisplay {
+    pub fn new(timer: &Timer) -> Self {
+        let label = Label::builder()
+            .wrap(true)
+            .wrap_mode(WrapMode::WordChar)
+            .xalign(0.0)
+            .margin_top(6)
+            .css_classes(["segment-note"])
+            .build();
+        let mut this = Self { label };
+        this.refresh(timer);
+        this
+    }
+
+    pub fn container(&self) -> &Label {
+        &self.label
+    }
+
+    pub fn refresh(&mut self, timer: &Timer) {
+        let note = timer
+            .current_split_index()
+            .and_then(|index| segment_note(timer.run(), index));
+        self.label.set_visible(note.is_some());
+        if let Some(note) = note
+            && self.label.label().as_str() != note
+        {
+            self.label.set_label(note);
+        }
+    }
+}
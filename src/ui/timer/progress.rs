@@ -0,0 +1,173 @@
+use crate::config::Config;
+use crate::utils::comparisons::{segment_comparison_time, segment_split_time};
+
+use gtk4::ProgressBar;
+use gtk4::prelude::*;
+
+use livesplit_core::{Timer, TimerPhase};
+
+/// Thin bar under the splits showing how far into the run the attempt is, coloured by
+/// whether the last split was ahead of or behind the comparison.
+pub struct RunProgress {
+    bar: ProgressBar,
+}
+
+impl RunProgress {
+    pub fn new(timer: &Timer, config: &Config) -> Self {
+        let bar = ProgressBar::builder()
+            .hexpand(true)
+            .margin_top(6)
+            .css_classes(["run-progress"])
+            .build();
+        let mut this = Self { bar };
+        this.refresh(timer, config);
+        this
+    }
+
+    pub fn container(&self) -> &ProgressBar {
+        &self.bar
+    }
+
+    pub fn refresh(&mut self, timer: &Timer, config: &Config) {
+        let show = config.style.show_progress_bar.unwrap_or(false);
+        self.bar.set_visible(show);
+        if !show {
+            return;
+        }
+
+        let fraction = run_progress(
+            timer.current_phase(),
+            timer.current_split_index(),
+            timer.run().len(),
+        );
+        if (self.bar.fraction() - fraction).abs() > f64::EPSILON {
+            self.bar.set_fraction(fraction);
+        }
+
+        let (add, remove) = match ahead_of_comparison(timer) {
+            Some(true) => ("progress-ahead", "progress-behind"),
+            Some(false) => ("progress-behind", "progress-ahead"),
+            None => {
+                self.bar.remove_css_class("progress-ahead");
+                self.bar.remove_css_class("progress-behind");
+                return;
+            }
+        };
+        self.bar.remove_css_class(remove);
+        self.bar.add_css_class(add);
+    }
+}
+
+/// Completed share of the run: empty before the attempt starts, full once it ended.
+fn run_progress(phase: TimerPhase, current_index: Option<usize>, segment_count: usize) -> f64 {
+    match phase {
+        TimerPhase::NotRunning => 0.0,
+        TimerPhase::Ended => 1.0,
+        TimerPhase::Running | TimerPhase::Paused => {
+            if segment_count == 0 {
+                return 0.0;
+            }
+            current_index.unwrap_or(0).min(segment_count) as f64 / segment_count as f64
+        }
+    }
+}
+
+/// Whether the last completed split was at or ahead of its comparison time. `None`
+/// before the first split, or when that split has nothing to compare against.
+fn ahead_of_comparison(timer: &Timer) -> Option<bool> {
+    let completed = match timer.current_phase() {
+        TimerPhase::NotRunning => return None,
+        TimerPhase::Ended => timer.run().len(),
+        TimerPhase::Running | TimerPhase::Paused => timer.current_split_index().unwrap_or(0),
+    };
+    let segment = timer.run().segments()[..completed.min(timer.run().len())]
+        .iter()
+        .rev()
+        .find(|segment| segment_split_time(segment, timer) != time::Duration::ZERO)?;
+
+    let comparison = segment_comparison_time(segment, timer);
+    if comparison == time::Duration::ZERO {
+        return None;
+    }
+    Some(segment_split_time(segment, timer) <= comparison)
+}
+
+#[cfg(test)]
+mod run_progress_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+    use std::sync::Once;
+
+    static INIT: Once = Once::new();
+
+    fn gtk_test_init() {
+        INIT.call_once(|| {
+            gtk4::init().expect("Failed to init GTK");
+        });
+    }
+
+    #[test]
+    fn progress_follows_phase_and_split_index() {
+        assert_eq!(run_progress(TimerPhase::NotRunning, None, 4), 0.0);
+        assert_eq!(run_progress(TimerPhase::Running, Some(0), 4), 0.0);
+        assert_eq!(run_progress(TimerPhase::Running, Some(1), 4), 0.25);
+        assert_eq!(run_progress(TimerPhase::Paused, Some(3), 4), 0.75);
+        assert_eq!(run_progress(TimerPhase::Ended, Some(4), 4), 1.0);
+        assert_eq!(run_progress(TimerPhase::Running, Some(0), 0), 0.0);
+    }
+
+    fn timer_with_pb(pb_first_split: f64) -> Timer {
+        let mut run = Run::new();
+        let mut first = Segment::new("S0");
+        first.set_personal_best_split_time(
+            Time::new().with_real_time(Some(TimeSpan::from_seconds(pb_first_split))),
+        );
+        run.push_segment(first);
+        run.push_segment(Segment::new("S1"));
+        let mut timer = Timer::new(run).expect("timer");
+        timer.set_current_timing_method(TimingMethod::RealTime);
+        timer
+    }
+
+    #[test]
+    fn no_colour_before_the_first_split() {
+        let mut timer = timer_with_pb(1000.0);
+        assert_eq!(ahead_of_comparison(&timer), None);
+        timer.start();
+        assert_eq!(ahead_of_comparison(&timer), None);
+    }
+
+    #[test]
+    fn colour_follows_last_split() {
+        // Splitting right away is far ahead of a 1000s PB split
+        let mut timer = timer_with_pb(1000.0);
+        timer.start();
+        timer.split();
+        assert_eq!(ahead_of_comparison(&timer), Some(true));
+
+        // And behind a PB split of almost nothing
+        let mut timer = timer_with_pb(0.000_001);
+        timer.start();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        timer.split();
+        assert_eq!(ahead_of_comparison(&timer), Some(false));
+    }
+
+    #[gtk4::test]
+    fn bar_is_hidden_unless_enabled() {
+        gtk_test_init();
+
+        let mut timer = timer_with_pb(1000.0);
+        let mut config = Config::default();
+        let mut progress = RunProgress::new(&timer, &config);
+        assert!(!progress.container().is_visible());
+
+        config.style.show_progress_bar = Some(true);
+        timer.start();
+        timer.split();
+        progress.refresh(&timer, &config);
+        assert!(progress.container().is_visible());
+        assert_eq!(progress.container().fraction(), 0.5);
+        assert!(progress.container().has_css_class("progress-ahead"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Grouped segments following the LiveSplit subsplits naming: segments starting with
+//! "-" belong to the group closed by the next segment named "{Group}Name".
+//!
+//! For example "-Forsaken City", "-Old Site" and "{Chapters 1-2}Celestial Resort" form
+//! the "Chapters 1-2" group.
+
+use crate::config::Config;
+use crate::utils::comparisons::{
+    previous_split_combined_gold_and_prev_comparison, segment_comparison_time, segment_split_time,
+};
+
+use livesplit_core::Timer;
+
+/// Segments `start..=end`, the last one closing the group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentGroup {
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The group name of a closing segment, e.g. "Chapter 1" for "{Chapter 1}Summit".
+fn closing_group(name: &str) -> Option<&str> {
+    let (group, _) = name.strip_prefix('{')?.split_once('}')?;
+    Some(group)
+}
+
+/// The groups of the given segment names. Subsplits nothing closes aren't grouped.
+pub fn segment_groups<'a>(names: impl IntoIterator<Item = &'a str>) -> Vec<SegmentGroup> {
+    let mut groups = Vec::new();
+    let mut start = None;
+    for (index, name) in names.into_iter().enumerate() {
+        if name.starts_with('-') {
+            start.get_or_insert(index);
+        } else if let Some(group) = closing_group(name) {
+            let start = start.take().unwrap_or(index);
+            if start < index {
+                groups.push(SegmentGroup {
+                    name: group.to_owned(),
+                    start,
+                    end: index,
+                });
+            }
+        } else {
+            start = None;
+        }
+    }
+    groups
+}
+
+/// The segment name without the subsplit markers.
+pub fn display_name(name: &str) -> &str {
+    if let Some(subsplit) = name.strip_prefix('-') {
+        return subsplit;
+    }
+    match name.strip_prefix('{').and_then(|rest| rest.split_once('}')) {
+        Some((group, "")) => group,
+        Some((_, rest)) => rest,
+        None => name,
+    }
+}
+
+/// The groups of the timer's run, none unless `style.subsplits` is on.
+pub fn timer_groups(timer: &Timer, config: &Config) -> Vec<SegmentGroup> {
+    if !config.style.subsplits.unwrap_or(false) {
+        return Vec::new();
+    }
+    segment_groups(timer.run().segments().iter().map(|segment| segment.name()))
+}
+
+/// Whether the group shows only its closing row. The group of the current segment is
+/// always expanded, so the running segment is never hidden.
+pub fn is_collapsed(group: &SegmentGroup, timer: &Timer, config: &Config) -> bool {
+    config.is_group_collapsed(&group.name)
+        && !timer
+            .current_split_index()
+            .is_some_and(|index| (group.start..=group.end).contains(&index))
+}
+
+/// Which segment rows are hidden inside collapsed groups.
+pub fn hidden_segments(timer: &Timer, config: &Config) -> Vec<bool> {
+    let mut hidden = vec![false; timer.run().len()];
+    for group in timer_groups(timer, config) {
+        if is_collapsed(&group, timer, config) {
+            hidden[group.start..group.end].fill(true);
+        }
+    }
+    hidden
+}
+
+/// Sum of the segment times of the group: the attempt's once it is split, the
+/// comparison's before.
+pub fn group_duration(group: &SegmentGroup, timer: &Timer) -> time::Duration {
+    let segments = timer.run().segments();
+    let split = segment_split_time(&segments[group.end], timer);
+    if split == time::Duration::ZERO {
+        let previous_comparison = group
+            .start
+            .checked_sub(1)
+            .map_or(time::Duration::ZERO, |previous| {
+                segment_comparison_time(&segments[previous], timer)
+            });
+        segment_comparison_time(&segments[group.end], timer)
+            .checked_sub(previous_comparison)
+            .unwrap_or_default()
+    } else {
+        let (previous_split, _, _) =
+            previous_split_combined_gold_and_prev_comparison(timer, group.start);
+        split.checked_sub(previous_split).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod subsplits_tests {
+    use super::*;
+    use livesplit_core::{Run, Segment, Time, TimeSpan};
+
+    const NAMES: [&str; 6] = [
+        "Prologue",
+        "-Forsaken City",
+        "-Old Site",
+        "{Chapters 1-2}Celestial Resort",
+        "-Golden Ridge",
+        "Summit",
+    ];
+
+    #[test]
+    fn groups_end_at_the_closing_segment() {
+        assert_eq!(
+            segment_groups(NAMES),
+            vec![SegmentGroup {
+                name: "Chapters 1-2".to_owned(),
+                start: 1,
+                end: 3,
+            }]
+        );
+        // A closing segment without subsplits has nothing to collapse
+        assert!(segment_groups(["{Alone}Segment", "Other"]).is_empty());
+    }
+
+    #[test]
+    fn display_names_drop_the_markers() {
+        assert_eq!(display_name("-Old Site"), "Old Site");
+        assert_eq!(
+            display_name("{Chapters 1-2}Celestial Resort"),
+            "Celestial Resort"
+        );
+        assert_eq!(display_name("{Chapter 3}"), "Chapter 3");
+        assert_eq!(display_name("Summit"), "Summit");
+    }
+
+    fn timer() -> Timer {
+        let mut run = Run::new();
+        for (index, name) in NAMES.iter().enumerate() {
+            let mut segment = Segment::new(*name);
+            segment.set_personal_best_split_time(
+                Time::new().with_real_time(Some(TimeSpan::from_seconds(10.0 * (index + 1) as f64))),
+            );
+            run.push_segment(segment);
+        }
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn collapsed_groups_hide_their_subsplits() {
+        let mut timer = timer();
+        let mut config = Config::default();
+        config.style.subsplits = Some(true);
+        assert_eq!(hidden_segments(&timer, &config), vec![false; 6]);
+
+        config.set_group_collapsed("Chapters 1-2", true);
+        assert_eq!(
+            hidden_segments(&timer, &config),
+            vec![false, true, true, false, false, false]
+        );
+
+        // The group being run is expanded
+        timer.start();
+        timer.split();
+        assert_eq!(hidden_segments(&timer, &config), vec![false; 6]);
+
+        config.style.subsplits = Some(false);
+        timer.reset(false);
+        assert_eq!(hidden_segments(&timer, &config), vec![false; 6]);
+    }
+
+    #[test]
+    fn group_duration_sums_the_comparison_segments() {
+        let timer = timer();
+        let group = &segment_groups(NAMES)[0];
+        // From the 10 second split of the prologue to the 40 second group end
+        assert_eq!(group_duration(group, &timer), time::Duration::seconds(30));
+    }
+}
@@ -1,8 +1,12 @@
-use crate::config::Config;
-use livesplit_core::{Timer, analysis::sum_of_segments::best::calculate as calculate_sob};
+use crate::config::{Config, PaceTarget};
+use crate::formatters::TimeFormat;
+use livesplit_core::{
+    Run, RunEditor, Time, TimeSpan, Timer, TimingMethod,
+    analysis::sum_of_segments::best::calculate as calculate_sob,
+    comparison::{AverageSegments, BalancedPB, average_segments, balanced_pb},
+};
 
 pub fn current_attempt_running_duration(timer: &Timer) -> time::Duration {
-    use livesplit_core::TimingMethod;
     let current_dur = timer
         .current_attempt_duration()
         .to_duration()
@@ -38,6 +42,183 @@ pub fn real_time_sob(timer: &Timer) -> time::Duration {
     .to_duration()
 }
 
+/// Makes sure the run carries the generated "Average Segments" comparison (averaged
+/// from the segment history), so it can be picked like Personal Best or Best Segments.
+/// Runs saved with a trimmed list of comparison generators get it added back.
+pub fn ensure_average_comparison(run: &mut Run) {
+    if run.comparisons().any(|c| c == average_segments::NAME) {
+        return;
+    }
+    run.comparison_generators_mut()
+        .push(Box::new(AverageSegments));
+    run.regenerate_comparisons();
+}
+
+/// Makes sure the run carries the generated "Balanced PB" comparison, which spreads the
+/// Personal Best's final time over the segments according to the segment history.
+///
+/// It needs a finished Personal Best to balance: without one its split times stay
+/// empty and segments show "--" like for any missing comparison time.
+pub fn ensure_balanced_pb_comparison(run: &mut Run) {
+    if run.comparisons().any(|c| c == balanced_pb::NAME) {
+        return;
+    }
+    run.comparison_generators_mut().push(Box::new(BalancedPB));
+    run.regenerate_comparisons();
+}
+
+/// Adds every generated comparison TuxSplit offers beyond the run's own ones.
+pub fn ensure_generated_comparisons(run: &mut Run) {
+    ensure_average_comparison(run);
+    ensure_balanced_pb_comparison(run);
+}
+
+/// Average duration of the segment at `index`, taken from the "Average Segments"
+/// comparison (see [`ensure_average_comparison`]). `None` when the segment has no
+/// history to average.
+pub fn average_segment_duration(
+    run: &Run,
+    index: usize,
+    method: TimingMethod,
+) -> Option<time::Duration> {
+    let split_time = |i: usize| {
+        run.segments()
+            .get(i)?
+            .comparison_timing_method(average_segments::NAME, method)
+    };
+    let end = split_time(index)?;
+    let start = if index == 0 {
+        livesplit_core::TimeSpan::zero()
+    } else {
+        split_time(index - 1)?
+    };
+    Some((end - start).to_duration())
+}
+
+/// Appended to times that were derived from real time instead of read as game time.
+pub const DERIVED_TIME_MARKER: &str = "*";
+
+/// The part of `time` shown for `method`. With `game_time_fallback`, a missing game
+/// time falls back to the real time; the flag tells whether that happened.
+pub fn display_time(
+    time: &Time,
+    method: TimingMethod,
+    game_time_fallback: bool,
+) -> (Option<TimeSpan>, bool) {
+    match time[method] {
+        None if method == TimingMethod::GameTime
+            && game_time_fallback
+            && time.real_time.is_some() =>
+        {
+            (time.real_time, true)
+        }
+        span => (span, false),
+    }
+}
+
+/// Formats a split time like `TimeFormat::format_split_time`, honoring the
+/// `general.game-time-fallback` option and marking derived values.
+pub fn format_display_time(
+    format: &TimeFormat,
+    time: &Time,
+    method: TimingMethod,
+    config: &Config,
+) -> String {
+    let (span, derived) = display_time(time, method, config.general.game_time_fallback);
+    mark_derived(format.format_time_span_opt(span), derived)
+}
+
+/// `display_time` as a duration for computing deltas, zero when there is no time.
+pub fn display_duration(
+    time: &Time,
+    method: TimingMethod,
+    config: &Config,
+) -> (time::Duration, bool) {
+    let (span, derived) = display_time(time, method, config.general.game_time_fallback);
+    (span.unwrap_or_default().to_duration(), derived)
+}
+
+/// Appends `DERIVED_TIME_MARKER` to `text` when it shows a derived time.
+pub fn mark_derived(text: String, derived: bool) -> String {
+    if derived {
+        format!("{text}{DERIVED_TIME_MARKER}")
+    } else {
+        text
+    }
+}
+
+/// The generated comparison made of the best segment times.
+pub const BEST_SEGMENTS_COMPARISON: &str = "Best Segments";
+
+/// Name of the comparison holding an imported reference run (e.g. the world record).
+pub const WORLD_RECORD_COMPARISON: &str = "World Record";
+
+/// Adds the Personal Best of `reference` to `run` as the comparison `name`, replacing
+/// an earlier import of the same name. Segments are matched by name, so the reference
+/// may skip or add splits. `None` when the run can't be edited or the name is invalid.
+pub fn import_reference_comparison(run: Run, reference: &Run, name: &str) -> Option<Run> {
+    let mut editor = RunEditor::new(run).ok()?;
+    if editor.run().custom_comparisons().iter().any(|c| c == name) {
+        editor.remove_comparison(name);
+    }
+    editor.import_comparison(reference, name).ok()?;
+    Some(editor.close())
+}
+
+/// Name used for a pinned comparison when none is given.
+pub const DEFAULT_PINNED_COMPARISON: &str = "Pinned PB";
+
+/// Returns `name`, or `name (2)`, `name (3)`, ... if the run already has a
+/// comparison (custom or generated) called like that.
+pub fn unique_comparison_name(run: &Run, name: &str) -> String {
+    let name = name.trim();
+    let name = if name.is_empty() {
+        DEFAULT_PINNED_COMPARISON
+    } else {
+        name
+    };
+    let taken = |candidate: &str| run.comparisons().any(|c| c == candidate);
+    if !taken(name) {
+        return name.to_owned();
+    }
+    (2..)
+        .map(|n| format!("{name} ({n})"))
+        .find(|candidate| !taken(candidate))
+        .expect("some suffix is free")
+}
+
+/// Adds a custom comparison holding a copy of the current Personal Best split times,
+/// so it stays put when the PB improves. Returns the run and the name it was added
+/// under, which differs from `name` when that one was taken. `None` when the run
+/// can't be edited or `RunEditor` refuses the name (e.g. a `[Race]` prefix).
+pub fn pin_personal_best_comparison(run: Run, name: &str) -> Option<(Run, String)> {
+    let name = unique_comparison_name(&run, name);
+    let mut editor = RunEditor::new(run).ok()?;
+    editor.add_comparison(name.as_str()).ok()?;
+    let mut run = editor.close();
+    for segment in run.segments_mut() {
+        let pb = segment.personal_best_split_time();
+        *segment.comparison_mut(&name) = pb;
+    }
+    Some((run, name))
+}
+
+/// Sum of best segments of the run as saved, ignoring the attempt in progress.
+/// `None` when no segment has a best time yet.
+pub fn sum_of_best(run: &Run, method: TimingMethod) -> Option<time::Duration> {
+    if run
+        .segments()
+        .iter()
+        .all(|s| s.best_segment_time()[method].is_none())
+    {
+        return None;
+    }
+
+    let mut predictions = vec![None; run.len() + 1];
+    calculate_sob(run.segments(), &mut predictions, false, false, method)
+        .map(livesplit_core::TimeSpan::to_duration)
+}
+
 pub fn best_segment_duration(segment: &livesplit_core::Segment, timer: &Timer) -> time::Duration {
     use livesplit_core::TimingMethod;
     if timer.current_timing_method() == TimingMethod::GameTime {
@@ -73,8 +254,17 @@ pub fn segment_split_time(segment: &livesplit_core::Segment, timer: &Timer) -> t
 }
 
 pub fn segment_best_time(segment: &livesplit_core::Segment, timer: &Timer) -> time::Duration {
+    segment_time_in_comparison(segment, timer, BEST_SEGMENTS_COMPARISON)
+}
+
+/// Split time of the segment in the given comparison, zero when it has none.
+pub fn segment_time_in_comparison(
+    segment: &livesplit_core::Segment,
+    timer: &Timer,
+    comparison: &str,
+) -> time::Duration {
     segment
-        .comparison_timing_method("Best Segments", timer.current_timing_method())
+        .comparison_timing_method(comparison, timer.current_timing_method())
         .unwrap_or_default()
         .to_duration()
 }
@@ -86,6 +276,49 @@ pub fn segment_comparison_time(segment: &livesplit_core::Segment, timer: &Timer)
         .to_duration()
 }
 
+/// The segment being run, or the first one while no attempt is running. `None` only
+/// for a run without segments.
+pub fn current_or_first_segment(timer: &Timer) -> Option<&livesplit_core::Segment> {
+    timer
+        .current_split()
+        .or_else(|| timer.run().segments().first())
+}
+
+/// Possible time save summed over the segments from `from_index` to the end of the run:
+/// for each segment, how much slower the comparison is than its best segment. Segments
+/// without a comparison or best time are skipped.
+pub fn total_possible_time_save(timer: &Timer, from_index: usize) -> time::Duration {
+    let segments = timer.run().segments();
+    let mut previous_comparison_time = if from_index > 0 {
+        segments
+            .get(from_index - 1)
+            .map_or(time::Duration::ZERO, |s| segment_comparison_time(s, timer))
+    } else {
+        time::Duration::ZERO
+    };
+
+    let mut total = time::Duration::ZERO;
+    for segment in segments.iter().skip(from_index) {
+        let comparison_time = segment_comparison_time(segment, timer);
+        if comparison_time == time::Duration::ZERO {
+            continue;
+        }
+        let gold = best_segment_duration(segment, timer);
+        if gold != time::Duration::ZERO {
+            let save = comparison_time
+                .checked_sub(previous_comparison_time)
+                .unwrap_or_default()
+                .checked_sub(gold)
+                .unwrap_or_default();
+            if save.is_positive() {
+                total = total.checked_add(save).unwrap_or_default();
+            }
+        }
+        previous_comparison_time = comparison_time;
+    }
+    total
+}
+
 pub fn previous_comparison_values(timer: &Timer, index: usize) -> (time::Duration, time::Duration) {
     use livesplit_core::TimingMethod;
     let segments = timer.run().segments();
@@ -112,15 +345,16 @@ pub fn previous_comparison_values(timer: &Timer, index: usize) -> (time::Duratio
     }
 }
 
-pub fn best_comparison_values(timer: &Timer, index: usize) -> (time::Duration, time::Duration) {
+pub fn best_comparison_values(
+    timer: &Timer,
+    index: usize,
+    comparison: &str,
+) -> (time::Duration, time::Duration) {
     use livesplit_core::TimingMethod;
     let segments = timer.run().segments();
     if index > 0 {
         let prev = &segments[index - 1];
-        let prev_best_duration = prev
-            .comparison_timing_method("Best Segments", timer.current_timing_method())
-            .unwrap_or_default()
-            .to_duration();
+        let prev_best_duration = segment_time_in_comparison(prev, timer, comparison);
         let prev_split_time = if timer.current_timing_method() == TimingMethod::GameTime {
             prev.split_time()
                 .game_time
@@ -138,6 +372,41 @@ pub fn best_comparison_values(timer: &Timer, index: usize) -> (time::Duration, t
     }
 }
 
+/// Final time the projected finish is colored against, `None` when the target has no
+/// time (no finished PB, no goal set or no imported World Record).
+pub fn pace_target_time(
+    target: PaceTarget,
+    run: &Run,
+    method: TimingMethod,
+    goal_time: Option<TimeSpan>,
+) -> Option<TimeSpan> {
+    match target {
+        PaceTarget::PersonalBest => run.segments().last()?.personal_best_split_time()[method],
+        PaceTarget::GoalTime => goal_time,
+        PaceTarget::WorldRecord => {
+            run.segments().last()?.comparison(WORLD_RECORD_COMPARISON)[method]
+        }
+    }
+}
+
+/// Color class of the projected finish: green under the target, red over it, none
+/// without a target or right on it.
+pub fn pace_class(pace: TimeSpan, target: Option<TimeSpan>) -> Option<&'static str> {
+    let target = target?;
+    if pace < target {
+        Some("greensplit")
+    } else if pace > target {
+        Some("redsplit")
+    } else {
+        None
+    }
+}
+
+/// How far the projected final time is from the goal, negative when under it.
+pub fn projected_goal_delta(pace: TimeSpan, goal: TimeSpan) -> time::Duration {
+    pace.to_duration() - goal.to_duration()
+}
+
 pub fn format_signed(diff: time::Duration, config: &Config) -> String {
     let sign = if diff.is_positive() {
         "+"
@@ -146,11 +415,16 @@ pub fn format_signed(diff: time::Duration, config: &Config) -> String {
     } else {
         "~"
     };
-    let abs = diff.abs();
-    let formatted = config.format.split.format_segment_time(&abs);
+    let formatted = config.format.split.format_duration_magnitude(&diff);
     format!("{sign}{formatted}")
 }
 
+/// Whether a segment taking `split_duration` is a new gold: faster than the combined
+/// best segments it covers, or the first time there is one.
+pub fn is_gold_segment(split_duration: time::Duration, gold_duration: time::Duration) -> bool {
+    split_duration < gold_duration || gold_duration == time::Duration::ZERO
+}
+
 pub fn classify_split_label(
     comparison_duration: time::Duration,
     split_duration: time::Duration,
@@ -161,7 +435,7 @@ pub fn classify_split_label(
     if running {
         return "";
     }
-    if split_duration < goldsplit_duration || goldsplit_duration == time::Duration::ZERO {
+    if is_gold_segment(split_duration, goldsplit_duration) {
         "goldsplit"
     } else if diff.is_negative() {
         if split_duration <= comparison_duration {
@@ -180,11 +454,33 @@ pub fn classify_split_label(
     }
 }
 
+/// CSS class scaling the delta color with how far the split is from the comparison,
+/// relative to the comparison's split time: under 2.5% is "low", under 10% "medium",
+/// anything beyond "high". Returns an empty class when there is nothing to compare to.
+pub fn delta_intensity_class(
+    diff: time::Duration,
+    comparison_time: time::Duration,
+) -> &'static str {
+    if comparison_time <= time::Duration::ZERO || diff == time::Duration::ZERO {
+        return "";
+    }
+    let ratio = diff.abs().as_seconds_f64() / comparison_time.as_seconds_f64();
+    if ratio < 0.025 {
+        "delta-intensity-low"
+    } else if ratio < 0.10 {
+        "delta-intensity-medium"
+    } else {
+        "delta-intensity-high"
+    }
+}
+
 pub fn previous_split_combined_gold_and_prev_comparison(
     timer: &Timer,
     index: usize,
 ) -> (time::Duration, time::Duration, time::Duration) {
     let segments = timer.run().segments();
+    // Out of range (or empty runs) count as the last segment
+    let index = index.min(segments.len().saturating_sub(1));
     let mut last_non_skipped: Option<usize> = None;
     if index > 0 {
         for k in (0..index).rev() {
@@ -199,9 +495,9 @@ pub fn previous_split_combined_gold_and_prev_comparison(
     // until the last non-skipped, or the beginning.
     let start = last_non_skipped.map_or(0, |k| k + 1);
     let mut combined_gold = time::Duration::ZERO;
-    for k in start..=index {
+    for segment in segments.get(start..=index).into_iter().flatten() {
         combined_gold = combined_gold
-            .checked_add(best_segment_duration(&segments[k], timer))
+            .checked_add(best_segment_duration(segment, timer))
             .unwrap_or_default();
     }
 
@@ -217,6 +513,164 @@ pub fn previous_split_combined_gold_and_prev_comparison(
     (previous_split_time, combined_gold, previous_comparison_time)
 }
 
+/// Whether the attempt that just ended beats the Personal Best, or there was none yet.
+pub fn is_new_personal_best(timer: &Timer) -> bool {
+    let method = timer.current_timing_method();
+    let Some(final_time) = timer.current_time()[method] else {
+        return false;
+    };
+    timer
+        .run()
+        .segments()
+        .last()
+        .and_then(|segment| segment.personal_best_split_time()[method])
+        .is_none_or(|pb| final_time < pb)
+}
+
+#[cfg(test)]
+mod pace_color_tests {
+    use super::*;
+    use livesplit_core::Segment;
+
+    fn seconds(s: f64) -> TimeSpan {
+        TimeSpan::from_seconds(s)
+    }
+
+    fn run_with_pb_and_wr() -> Run {
+        let mut run = Run::new();
+        run.custom_comparisons_mut()
+            .push(WORLD_RECORD_COMPARISON.to_owned());
+        let mut segment = Segment::new("End");
+        segment.set_personal_best_split_time(Time::new().with_real_time(Some(seconds(100.0))));
+        *segment.comparison_mut(WORLD_RECORD_COMPARISON) =
+            Time::new().with_real_time(Some(seconds(90.0)));
+        run.push_segment(segment);
+        run
+    }
+
+    #[test]
+    fn pace_under_goal_is_green_and_over_is_red() {
+        let goal = Some(seconds(95.0));
+        assert_eq!(pace_class(seconds(94.0), goal), Some("greensplit"));
+        assert_eq!(pace_class(seconds(96.0), goal), Some("redsplit"));
+        assert_eq!(pace_class(seconds(95.0), goal), None);
+    }
+
+    #[test]
+    fn no_target_means_no_color() {
+        assert_eq!(pace_class(seconds(94.0), None), None);
+        let run = run_with_pb_and_wr();
+        assert_eq!(
+            pace_target_time(PaceTarget::GoalTime, &run, TimingMethod::RealTime, None),
+            None
+        );
+        assert_eq!(
+            pace_target_time(PaceTarget::PersonalBest, &run, TimingMethod::GameTime, None),
+            None
+        );
+    }
+
+    #[test]
+    fn projected_delta_to_goal() {
+        let goal = seconds(1800.0);
+
+        let under = projected_goal_delta(seconds(1790.5), goal);
+        assert_eq!(under, time::Duration::milliseconds(-9500));
+        assert_eq!(pace_class(seconds(1790.5), Some(goal)), Some("greensplit"));
+
+        let over = projected_goal_delta(seconds(1812.0), goal);
+        assert_eq!(over, time::Duration::seconds(12));
+        assert_eq!(pace_class(seconds(1812.0), Some(goal)), Some("redsplit"));
+
+        assert_eq!(projected_goal_delta(goal, goal), time::Duration::ZERO);
+    }
+
+    #[test]
+    fn target_follows_the_configured_choice() {
+        let run = run_with_pb_and_wr();
+        let goal = Some(seconds(95.0));
+        let target = |choice| pace_target_time(choice, &run, TimingMethod::RealTime, goal);
+        assert_eq!(target(PaceTarget::PersonalBest), Some(seconds(100.0)));
+        assert_eq!(target(PaceTarget::GoalTime), goal);
+        assert_eq!(target(PaceTarget::WorldRecord), Some(seconds(90.0)));
+
+        // 97s beats the PB but neither the goal nor the World Record
+        let pace = seconds(97.0);
+        assert_eq!(
+            pace_class(pace, target(PaceTarget::PersonalBest)),
+            Some("greensplit")
+        );
+        assert_eq!(
+            pace_class(pace, target(PaceTarget::GoalTime)),
+            Some("redsplit")
+        );
+        assert_eq!(
+            pace_class(pace, target(PaceTarget::WorldRecord)),
+            Some("redsplit")
+        );
+    }
+}
+
+#[cfg(test)]
+mod delta_intensity_tests {
+    use super::delta_intensity_class;
+    use time::Duration;
+
+    #[test]
+    fn small_delta_is_low() {
+        let class = delta_intensity_class(Duration::seconds(1), Duration::seconds(100));
+        assert_eq!(class, "delta-intensity-low");
+    }
+
+    #[test]
+    fn medium_delta_is_medium() {
+        let class = delta_intensity_class(Duration::seconds(5), Duration::seconds(100));
+        assert_eq!(class, "delta-intensity-medium");
+    }
+
+    #[test]
+    fn large_delta_is_high() {
+        let class = delta_intensity_class(Duration::seconds(30), Duration::seconds(100));
+        assert_eq!(class, "delta-intensity-high");
+    }
+
+    #[test]
+    fn bucket_boundaries_round_up() {
+        let comparison = Duration::seconds(200);
+        assert_eq!(
+            delta_intensity_class(Duration::seconds(5), comparison),
+            "delta-intensity-medium"
+        );
+        assert_eq!(
+            delta_intensity_class(Duration::seconds(20), comparison),
+            "delta-intensity-high"
+        );
+    }
+
+    #[test]
+    fn time_saves_are_bucketed_like_losses() {
+        let comparison = Duration::seconds(100);
+        for secs in [1, 5, 30] {
+            assert_eq!(
+                delta_intensity_class(Duration::seconds(-secs), comparison),
+                delta_intensity_class(Duration::seconds(secs), comparison)
+            );
+        }
+    }
+
+    #[test]
+    fn no_class_without_comparison_or_delta() {
+        assert_eq!(
+            delta_intensity_class(Duration::seconds(3), Duration::ZERO),
+            ""
+        );
+        assert_eq!(
+            delta_intensity_class(Duration::ZERO, Duration::seconds(100)),
+            ""
+        );
+    }
+}
+
 #[cfg(test)]
 mod classify_split_labels_tests {
     use super::*;
@@ -330,6 +784,390 @@ mod classify_split_labels_tests {
     }
 }
 
+#[cfg(test)]
+mod average_comparison_tests {
+    use super::ensure_average_comparison;
+    use livesplit_core::comparison::average_segments;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+
+    fn time_rt(seconds: i64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds as f64)))
+    }
+
+    /// Two segments, two finished attempts: (10s, 20s) then (20s, 40s).
+    fn run_with_history() -> Run {
+        let mut run = Run::new();
+        run.comparison_generators_mut().clear();
+
+        let mut s0 = Segment::new("S0");
+        s0.segment_history_mut().insert(1, time_rt(10));
+        s0.segment_history_mut().insert(2, time_rt(20));
+        let mut s1 = Segment::new("S1");
+        s1.segment_history_mut().insert(1, time_rt(20));
+        s1.segment_history_mut().insert(2, time_rt(40));
+        run.push_segment(s0);
+        run.push_segment(s1);
+
+        run.add_attempt_with_index(time_rt(30), 1, None, None, None);
+        run.add_attempt_with_index(time_rt(60), 2, None, None, None);
+        run
+    }
+
+    fn average_split(run: &Run, index: usize) -> f64 {
+        run.segment(index)
+            .comparison_timing_method(average_segments::NAME, TimingMethod::RealTime)
+            .expect("average split time")
+            .total_seconds()
+    }
+
+    #[test]
+    fn adds_average_comparison_once() {
+        let mut run = run_with_history();
+        assert!(!run.comparisons().any(|c| c == average_segments::NAME));
+
+        ensure_average_comparison(&mut run);
+        ensure_average_comparison(&mut run);
+
+        let count = run
+            .comparisons()
+            .filter(|c| *c == average_segments::NAME)
+            .count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn average_split_times_are_within_history_range() {
+        let mut run = run_with_history();
+        ensure_average_comparison(&mut run);
+
+        let first = average_split(&run, 0);
+        assert!((10.0..=20.0).contains(&first), "first split {first}");
+
+        let second = average_split(&run, 1);
+        assert!((30.0..=60.0).contains(&second), "second split {second}");
+        assert!(second > first);
+    }
+}
+
+#[cfg(test)]
+mod average_segment_duration_tests {
+    use super::{average_segment_duration, ensure_average_comparison};
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+
+    fn time_rt(seconds: i64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds as f64)))
+    }
+
+    #[test]
+    fn none_without_history() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("S0"));
+        ensure_average_comparison(&mut run);
+
+        assert_eq!(
+            average_segment_duration(&run, 0, TimingMethod::RealTime),
+            None
+        );
+        assert_eq!(
+            average_segment_duration(&run, 5, TimingMethod::RealTime),
+            None
+        );
+    }
+
+    #[test]
+    fn averages_each_segment_from_history() {
+        let mut run = Run::new();
+        let mut s0 = Segment::new("S0");
+        s0.segment_history_mut().insert(1, time_rt(10));
+        s0.segment_history_mut().insert(2, time_rt(20));
+        let mut s1 = Segment::new("S1");
+        s1.segment_history_mut().insert(1, time_rt(20));
+        s1.segment_history_mut().insert(2, time_rt(40));
+        run.push_segment(s0);
+        run.push_segment(s1);
+        run.add_attempt_with_index(time_rt(30), 1, None, None, None);
+        run.add_attempt_with_index(time_rt(60), 2, None, None, None);
+        ensure_average_comparison(&mut run);
+
+        let first = average_segment_duration(&run, 0, TimingMethod::RealTime)
+            .expect("first average")
+            .as_seconds_f64();
+        assert!((10.0..=20.0).contains(&first), "first segment {first}");
+
+        let second = average_segment_duration(&run, 1, TimingMethod::RealTime)
+            .expect("second average")
+            .as_seconds_f64();
+        assert!((20.0..=40.0).contains(&second), "second segment {second}");
+    }
+}
+
+#[cfg(test)]
+mod game_time_fallback_tests {
+    use super::{DERIVED_TIME_MARKER, display_duration, display_time, format_display_time};
+    use crate::config::Config;
+    use livesplit_core::{Time, TimeSpan, TimingMethod};
+
+    fn rt_only(seconds: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)))
+    }
+
+    #[test]
+    fn game_time_falls_back_to_real_time_when_enabled() {
+        let time = rt_only(75.0);
+        let (span, derived) = display_time(&time, TimingMethod::GameTime, true);
+        assert_eq!(span, time.real_time);
+        assert!(derived);
+
+        let mut config = Config::default();
+        config.general.game_time_fallback = true;
+        let format = &config.format.segment;
+        assert_eq!(
+            format_display_time(format, &time, TimingMethod::GameTime, &config),
+            format!(
+                "{}{DERIVED_TIME_MARKER}",
+                format.format_time_span_opt(time.real_time)
+            )
+        );
+    }
+
+    #[test]
+    fn no_fallback_when_disabled_or_present() {
+        let time = rt_only(75.0);
+        assert_eq!(
+            display_time(&time, TimingMethod::GameTime, false),
+            (None, false)
+        );
+
+        let config = Config::default();
+        let format = &config.format.segment;
+        assert_eq!(
+            format_display_time(format, &time, TimingMethod::GameTime, &config),
+            format.format_time_span_opt(None)
+        );
+
+        // Real game time wins, and real time mode is untouched
+        let both = time.with_game_time(Some(TimeSpan::from_seconds(70.0)));
+        assert_eq!(
+            display_time(&both, TimingMethod::GameTime, true),
+            (both.game_time, false)
+        );
+        assert_eq!(
+            display_time(&time, TimingMethod::RealTime, true),
+            (time.real_time, false)
+        );
+    }
+
+    #[test]
+    fn display_duration_follows_the_fallback() {
+        let split = rt_only(75.0);
+        let mut config = Config::default();
+        assert_eq!(
+            display_duration(&split, TimingMethod::GameTime, &config),
+            (time::Duration::ZERO, false)
+        );
+
+        config.general.game_time_fallback = true;
+        assert_eq!(
+            display_duration(&split, TimingMethod::GameTime, &config),
+            (time::Duration::seconds(75), true)
+        );
+    }
+}
+
+#[cfg(test)]
+mod import_reference_comparison_tests {
+    use super::{WORLD_RECORD_COMPARISON, import_reference_comparison};
+    use livesplit_core::{Run, Segment, Time, TimeSpan, Timer, TimingMethod};
+
+    fn time_rt(seconds: f64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)))
+    }
+
+    fn run_with_splits(splits: &[(&str, f64)]) -> Run {
+        let mut run = Run::new();
+        for &(name, split) in splits {
+            let mut segment = Segment::new(name);
+            segment.set_personal_best_split_time(time_rt(split));
+            run.push_segment(segment);
+        }
+        run
+    }
+
+    fn wr_split(run: &Run, index: usize) -> Option<f64> {
+        run.segment(index)
+            .comparison_timing_method(WORLD_RECORD_COMPARISON, TimingMethod::RealTime)
+            .map(|t| t.total_seconds())
+    }
+
+    #[test]
+    fn imported_comparison_is_selectable_with_reference_times() {
+        let run = run_with_splits(&[("Forest", 60.0), ("Castle", 150.0)]);
+        let reference = run_with_splits(&[("Forest", 50.0), ("Castle", 120.0)]);
+
+        let run =
+            import_reference_comparison(run, &reference, WORLD_RECORD_COMPARISON).expect("import");
+        assert_eq!(wr_split(&run, 0), Some(50.0));
+        assert_eq!(wr_split(&run, 1), Some(120.0));
+
+        let mut timer = Timer::new(run).expect("timer");
+        assert!(
+            timer
+                .set_current_comparison(WORLD_RECORD_COMPARISON)
+                .is_ok()
+        );
+        assert_eq!(timer.current_comparison(), WORLD_RECORD_COMPARISON);
+    }
+
+    #[test]
+    fn segments_are_matched_by_name() {
+        let run = run_with_splits(&[("Forest", 60.0), ("Swamp", 100.0), ("Castle", 150.0)]);
+        let reference = run_with_splits(&[("Forest", 50.0), ("Castle", 120.0)]);
+
+        let run =
+            import_reference_comparison(run, &reference, WORLD_RECORD_COMPARISON).expect("import");
+        assert_eq!(wr_split(&run, 0), Some(50.0));
+        assert_eq!(wr_split(&run, 1), None);
+        assert_eq!(wr_split(&run, 2), Some(120.0));
+    }
+
+    #[test]
+    fn reimport_replaces_previous_times() {
+        let run = run_with_splits(&[("Forest", 60.0)]);
+        let first = run_with_splits(&[("Forest", 50.0)]);
+        let second = run_with_splits(&[("Forest", 45.0)]);
+
+        let run = import_reference_comparison(run, &first, WORLD_RECORD_COMPARISON)
+            .expect("first import");
+        let run = import_reference_comparison(run, &second, WORLD_RECORD_COMPARISON)
+            .expect("second import");
+
+        assert_eq!(wr_split(&run, 0), Some(45.0));
+        let count = run
+            .custom_comparisons()
+            .iter()
+            .filter(|c| *c == WORLD_RECORD_COMPARISON)
+            .count();
+        assert_eq!(count, 1);
+    }
+}
+
+#[cfg(test)]
+mod total_possible_time_save_tests {
+    use super::total_possible_time_save;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, Timer};
+    use time::Duration;
+
+    fn time_rt(seconds: i64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds as f64)))
+    }
+
+    /// PB segments of 10s, 15s and 20s against golds of 8s, 15s and 17s.
+    fn three_segment_timer() -> Timer {
+        let mut run = Run::new();
+        for (name, pb_split, gold) in [("S0", 10, 8), ("S1", 25, 15), ("S2", 45, 17)] {
+            let mut segment = Segment::new(name);
+            segment.set_personal_best_split_time(time_rt(pb_split));
+            segment.set_best_segment_time(time_rt(gold));
+            run.push_segment(segment);
+        }
+        Timer::new(run).expect("timer")
+    }
+
+    #[test]
+    fn sums_save_over_all_segments() {
+        let timer = three_segment_timer();
+        assert_eq!(total_possible_time_save(&timer, 0), Duration::seconds(5));
+    }
+
+    #[test]
+    fn only_counts_remaining_segments() {
+        let timer = three_segment_timer();
+        assert_eq!(total_possible_time_save(&timer, 1), Duration::seconds(3));
+        assert_eq!(total_possible_time_save(&timer, 2), Duration::seconds(3));
+        assert_eq!(total_possible_time_save(&timer, 3), Duration::ZERO);
+    }
+}
+
+#[cfg(test)]
+mod empty_run_guard_tests {
+    use super::{current_or_first_segment, previous_split_combined_gold_and_prev_comparison};
+    use livesplit_core::{Run, Segment, Time, TimeSpan, Timer};
+    use time::Duration;
+
+    #[test]
+    fn timers_never_hold_a_run_without_segments() {
+        // The guards below only matter if this ever changes
+        assert!(Timer::new(Run::new()).is_err());
+        let mut run = Run::new();
+        run.push_segment(Segment::new("S0"));
+        let mut timer = Timer::new(run).expect("timer");
+        assert!(timer.set_run(Run::new()).is_err());
+        assert_eq!(timer.run().len(), 1);
+    }
+
+    #[test]
+    fn current_or_first_segment_follows_the_attempt() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("S0"));
+        run.push_segment(Segment::new("S1"));
+        let mut timer = Timer::new(run).expect("timer");
+
+        assert_eq!(current_or_first_segment(&timer).unwrap().name(), "S0");
+        timer.start();
+        timer.split();
+        assert_eq!(current_or_first_segment(&timer).unwrap().name(), "S1");
+    }
+
+    #[test]
+    fn out_of_range_index_does_not_panic() {
+        let mut run = Run::new();
+        let mut segment = Segment::new("S0");
+        segment
+            .set_best_segment_time(Time::new().with_real_time(Some(TimeSpan::from_seconds(8.0))));
+        run.push_segment(segment);
+        let timer = Timer::new(run).expect("timer");
+
+        let (_, gold, _) = previous_split_combined_gold_and_prev_comparison(&timer, 5);
+        assert_eq!(gold, Duration::seconds(8));
+    }
+}
+
+#[cfg(test)]
+mod sum_of_best_tests {
+    use super::sum_of_best;
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+
+    fn time_rt(seconds: i64) -> Time {
+        Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds as f64)))
+    }
+
+    #[test]
+    fn none_without_best_segments() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("S0"));
+        run.push_segment(Segment::new("S1"));
+
+        assert_eq!(sum_of_best(&run, TimingMethod::RealTime), None);
+    }
+
+    #[test]
+    fn sums_best_segments() {
+        let mut run = Run::new();
+        let mut s0 = Segment::new("S0");
+        s0.set_best_segment_time(time_rt(10));
+        let mut s1 = Segment::new("S1");
+        s1.set_best_segment_time(time_rt(20));
+        run.push_segment(s0);
+        run.push_segment(s1);
+
+        assert_eq!(
+            sum_of_best(&run, TimingMethod::RealTime),
+            Some(time::Duration::seconds(30))
+        );
+        assert_eq!(sum_of_best(&run, TimingMethod::GameTime), None);
+    }
+}
+
 #[cfg(test)]
 mod skipped_segments_context_tests {
     use super::*;
@@ -503,3 +1341,101 @@ mod skipped_segments_context_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod pinned_comparison_tests {
+    use super::{DEFAULT_PINNED_COMPARISON, pin_personal_best_comparison, unique_comparison_name};
+    use livesplit_core::{Run, Segment, Time, TimeSpan, TimingMethod};
+
+    fn run_with_pb() -> Run {
+        let mut run = Run::new();
+        for (name, seconds) in [("A", 10.0), ("B", 25.0)] {
+            let mut segment = Segment::new(name);
+            segment.set_personal_best_split_time(
+                Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds))),
+            );
+            run.push_segment(segment);
+        }
+        run
+    }
+
+    fn pin(run: &mut Run, name: &str) -> String {
+        let (pinned, name) = pin_personal_best_comparison(run.clone(), name).expect("pinned");
+        *run = pinned;
+        name
+    }
+
+    #[test]
+    fn pinned_comparison_copies_pb_splits() {
+        let mut run = run_with_pb();
+        let name = pin(&mut run, "Good Run");
+
+        assert_eq!(name, "Good Run");
+        assert!(run.custom_comparisons().iter().any(|c| c == "Good Run"));
+        for segment in run.segments() {
+            assert_eq!(
+                segment.comparison_timing_method("Good Run", TimingMethod::RealTime),
+                segment.personal_best_split_time().real_time
+            );
+        }
+    }
+
+    #[test]
+    fn name_collisions_get_a_suffix() {
+        let mut run = run_with_pb();
+
+        assert_eq!(pin(&mut run, "Personal Best"), "Personal Best (2)");
+        assert_eq!(pin(&mut run, "Run"), "Run");
+        assert_eq!(pin(&mut run, " Run "), "Run (2)");
+        assert_eq!(unique_comparison_name(&run, "Run"), "Run (3)");
+    }
+
+    #[test]
+    fn names_the_run_editor_refuses_are_not_pinned() {
+        assert!(pin_personal_best_comparison(run_with_pb(), "[Race] Someone").is_none());
+    }
+
+    #[test]
+    fn empty_name_uses_default() {
+        let run = run_with_pb();
+        assert_eq!(
+            unique_comparison_name(&run, "  "),
+            DEFAULT_PINNED_COMPARISON
+        );
+    }
+}
+
+#[cfg(test)]
+mod balanced_pb_tests {
+    use super::ensure_generated_comparisons;
+    use livesplit_core::comparison::{average_segments, balanced_pb};
+    use livesplit_core::{Run, Segment, TimingMethod};
+
+    #[test]
+    fn generated_comparisons_include_balanced_pb_once() {
+        let mut run = Run::new();
+        run.comparison_generators_mut().clear();
+        run.push_segment(Segment::new("A"));
+
+        ensure_generated_comparisons(&mut run);
+        ensure_generated_comparisons(&mut run);
+
+        for name in [balanced_pb::NAME, average_segments::NAME] {
+            assert_eq!(run.comparisons().filter(|c| *c == name).count(), 1);
+        }
+    }
+
+    #[test]
+    fn balanced_pb_is_empty_without_a_personal_best() {
+        let mut run = Run::new();
+        run.comparison_generators_mut().clear();
+        run.push_segment(Segment::new("A"));
+        ensure_generated_comparisons(&mut run);
+
+        assert_eq!(
+            run.segment(0)
+                .comparison_timing_method(balanced_pb::NAME, TimingMethod::RealTime),
+            None
+        );
+    }
+}
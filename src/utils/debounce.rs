@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Runs an action once no new one was scheduled for `delay`.
+///
+/// Scheduling again while an action is pending replaces it and restarts the delay,
+/// so a burst of changes (e.g. dragging a slider) ends up as a single run of the last
+/// action. Actions run on the thread-default GLib main context. Clones share the
+/// pending action.
+#[derive(Clone)]
+pub struct Debouncer {
+    delay: Duration,
+    pending: Rc<RefCell<Option<glib::SourceId>>>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn schedule(&self, action: impl FnOnce() + 'static) {
+        self.cancel();
+        let pending = self.pending.clone();
+        let source = glib::timeout_add_local_once(self.delay, move || {
+            // The source is gone once it fired, it must not be removed again
+            pending.take();
+            action();
+        });
+        self.pending.replace(Some(source));
+    }
+
+    /// Drops the pending action without running it.
+    pub fn cancel(&self) {
+        if let Some(source) = self.pending.take() {
+            source.remove();
+        }
+    }
+}
+
+#[cfg(test)]
+mod debounce_tests {
+    use super::Debouncer;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn bursts_run_only_the_last_action_once() {
+        let context = glib::MainContext::new();
+        context
+            .with_thread_default(|| {
+                let debouncer = Debouncer::new(Duration::from_millis(20));
+                let runs = Rc::new(Cell::new(0));
+                let last = Rc::new(Cell::new(0));
+                for value in 1..=5 {
+                    let (runs, last) = (runs.clone(), last.clone());
+                    debouncer.schedule(move || {
+                        runs.set(runs.get() + 1);
+                        last.set(value);
+                    });
+                }
+
+                let deadline = Instant::now() + Duration::from_millis(200);
+                while Instant::now() < deadline {
+                    context.iteration(false);
+                }
+                assert_eq!(runs.get(), 1);
+                assert_eq!(last.get(), 5);
+            })
+            .expect("thread default context");
+    }
+}
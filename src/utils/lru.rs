@@ -0,0 +1,163 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Small least-recently-used cache.
+///
+/// Lookups and inserts mark the key as most recently used. When an insert
+/// pushes the cache over its capacity, the least recently used entries are
+/// evicted. Meant for a handful of entries (e.g. segment icons), so recency is
+/// tracked with a plain queue.
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns a clone of the cached value and marks it as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get(key)?.clone();
+        self.touch(key);
+        Some(value)
+    }
+
+    /// Inserts (or replaces) a value, evicting the least recently used entries if needed.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+        } else {
+            self.order.push_back(key);
+        }
+        self.evict();
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.order.retain(|k| k != key);
+        self.entries.remove(key)
+    }
+
+    /// Changes the capacity, evicting entries right away if the cache is now too big.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict();
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(pos) = self.order.iter().position(|k| k == key)
+            && let Some(k) = self.order.remove(pos)
+        {
+            self.order.push_back(k);
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod lru_tests {
+    use super::LruCache;
+
+    #[test]
+    fn evicts_oldest_when_over_capacity() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        cache.insert(3, "c");
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&1), "Oldest entry must be evicted");
+        assert!(cache.contains(&2));
+        assert!(cache.contains(&3));
+    }
+
+    #[test]
+    fn get_refreshes_recency() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some("a"));
+        cache.insert(3, "c");
+
+        assert!(cache.contains(&1), "Recently read entry must survive");
+        assert!(
+            !cache.contains(&2),
+            "Least recently used entry must be evicted"
+        );
+    }
+
+    #[test]
+    fn many_more_icons_than_limit_keeps_only_latest() {
+        let mut cache = LruCache::new(4);
+        for key in 0..100u64 {
+            cache.insert(key, key * 10);
+        }
+
+        assert_eq!(cache.len(), 4);
+        for key in 96..100u64 {
+            assert_eq!(cache.get(&key), Some(key * 10));
+        }
+        assert_eq!(cache.get(&0), None);
+    }
+
+    #[test]
+    fn reinserting_existing_key_does_not_grow() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(1, "b");
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&1), Some("b"));
+    }
+
+    #[test]
+    fn shrinking_capacity_evicts_immediately() {
+        let mut cache = LruCache::new(3);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        cache.insert(3, "c");
+        cache.set_capacity(1);
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.contains(&3));
+    }
+
+    #[test]
+    fn zero_capacity_is_treated_as_one() {
+        let mut cache = LruCache::new(0);
+        cache.insert(1, "a");
+
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+}
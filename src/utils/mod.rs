@@ -1 +1,4 @@
 pub mod comparisons;
+pub mod debounce;
+pub mod lru;
+pub mod notes;
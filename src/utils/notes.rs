@@ -0,0 +1,100 @@
+//! Per-segment notes, kept as permanent custom variables of the run so they are saved
+//! in the splits file.
+
+use livesplit_core::Run;
+
+const NOTE_VARIABLE_PREFIX: &str = "TuxSplit Note ";
+
+/// Name of the custom variable holding the note of the segment at `index`.
+fn note_variable(index: usize) -> String {
+    format!("{NOTE_VARIABLE_PREFIX}{}", index + 1)
+}
+
+/// The note of the segment at `index`, `None` when it has none.
+pub fn segment_note(run: &Run, index: usize) -> Option<&str> {
+    run.metadata()
+        .custom_variable_value(&note_variable(index))
+        .filter(|note| !note.trim().is_empty())
+}
+
+/// Sets the note of the segment at `index`, an empty note removes it.
+pub fn set_segment_note(run: &mut Run, index: usize, note: &str) {
+    let name = note_variable(index);
+    if note.trim().is_empty() {
+        run.metadata_mut().remove_custom_variable(&name);
+    } else {
+        let variable = run.metadata_mut().custom_variable_mut(name);
+        variable.set_value(note);
+        variable.permanent();
+    }
+}
+
+/// The notes of every segment, empty for segments without one.
+pub fn segment_notes(run: &Run) -> Vec<String> {
+    (0..run.len())
+        .map(|index| segment_note(run, index).unwrap_or_default().to_owned())
+        .collect()
+}
+
+/// Replaces every note, e.g. after segments were moved, added or removed.
+pub fn set_segment_notes(run: &mut Run, notes: &[String]) {
+    let stale: Vec<String> = run
+        .metadata()
+        .custom_variables()
+        .map(|(name, _)| name.to_owned())
+        .filter(|name| name.starts_with(NOTE_VARIABLE_PREFIX))
+        .collect();
+    for name in stale {
+        run.metadata_mut().remove_custom_variable(&name);
+    }
+    for (index, note) in notes.iter().enumerate() {
+        set_segment_note(run, index, note);
+    }
+}
+
+#[cfg(test)]
+mod notes_tests {
+    use super::*;
+    use livesplit_core::Segment;
+
+    fn run() -> Run {
+        let mut run = Run::new();
+        for name in ["A", "B", "C"] {
+            run.push_segment(Segment::new(name));
+        }
+        run
+    }
+
+    #[test]
+    fn notes_are_permanent_variables() {
+        let mut run = run();
+        set_segment_note(&mut run, 1, "Take the upper path");
+        assert_eq!(segment_note(&run, 1), Some("Take the upper path"));
+        assert_eq!(segment_note(&run, 0), None);
+        // Only permanent variables are written to the splits file
+        assert!(
+            run.metadata()
+                .custom_variable("TuxSplit Note 2")
+                .is_some_and(|variable| variable.is_permanent)
+        );
+
+        set_segment_note(&mut run, 1, "  ");
+        assert_eq!(segment_note(&run, 1), None);
+        assert_eq!(run.metadata().custom_variables().count(), 0);
+    }
+
+    #[test]
+    fn replacing_notes_drops_the_old_ones() {
+        let mut run = run();
+        set_segment_note(&mut run, 2, "Last");
+        let mut notes = segment_notes(&run);
+        assert_eq!(notes, ["", "", "Last"]);
+
+        // Moving the last segment to the front
+        let last = notes.remove(2);
+        notes.insert(0, last);
+        set_segment_notes(&mut run, &notes);
+        assert_eq!(segment_note(&run, 0), Some("Last"));
+        assert_eq!(segment_note(&run, 2), None);
+    }
+}